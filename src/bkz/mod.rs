@@ -0,0 +1,552 @@
+//! Block Korkine-Zolotarev (BKZ) reduction [SE94]
+//!
+//! BKZ strengthens LLL by working on blocks of `block_size` consecutive vectors: for each
+//! position `k`, the shortest vector of the lattice generated by the projections of
+//! `b_k, ..., b_{k + block_size - 1}` orthogonally to `b_0, ..., b_{k-1}` is found by
+//! enumeration. If it is significantly shorter than `b*_k`, it is inserted at position
+//! `k` and the resulting generating set is reduced with the L² algorithm (MLLL), which
+//! removes the linear dependency. A tour goes through all the positions, and the tours
+//! are repeated until the basis does not change.
+//!
+//! BKZ 2.0 [CN11] (`bkz2_reduce`) speeds up the enumeration with pruning, compensates the
+//! missed vectors by rerandomizing the blocks, and stops the tours when the slope of the
+//! Gram-Schmidt profile no longer improves.
+//!
+//! Self-dual BKZ [MW16] (`sd_bkz_reduce`) alternates primal tours with dual tours, which
+//! go through the basis backwards and maximize the last Gram-Schmidt vector of each block.
+//!
+//! HKZ reduction (`hkz_reduce`) is the limit case of a single block containing the whole
+//! basis.
+
+use crate::hnf;
+use crate::l2::{bigl2, ReductionBudget, ReductionError, ReductionParams, ReductionProgress};
+use crate::matrix::Matrix;
+use crate::metrics;
+use crate::svp;
+use crate::vector::BigVector;
+
+use rug::{rand::RandState, Integer, Rational};
+use std::time::Instant;
+
+/// eta factor of the L² reductions between the block reductions
+///
+/// The exact L² reduction returns `|mu| <= 1/2` with this value.
+pub(crate) const ETA: f64 = 0.5;
+
+/// delta factor of the L² reductions of the HKZ reduction
+///
+/// Its value does not matter much: the L² reduction never swaps two vectors of an HKZ
+/// reduced prefix.
+const HKZ_DELTA: f64 = 0.99;
+
+/// Lattice reduction (self-dual BKZ algorithm)
+///
+/// Each primal tour SVP-reduces the blocks `basis[k..k + block_size]` for increasing `k`,
+/// and each dual tour dual SVP-reduces them for decreasing `k` (see `slide`): the last
+/// Gram-Schmidt vector of the block is made as long as possible, using the dual basis of
+/// the projected block. Only full blocks are processed. The tours alternate until neither
+/// of them changes the basis.
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * block_size: number of vectors of the blocks
+///  * delta: delta factor of the basis reduction
+///
+/// The basis is reduced in-place. The zero vectors coming from linear dependencies are
+/// moved to the end of the basis, and their number is returned.
+///
+/// # Panics
+/// if delta < 1/4 or delta >= 1  
+/// if block_size < 2
+pub fn sd_bkz_reduce(basis: &mut Matrix<Integer>, block_size: usize, delta: f64) -> usize {
+    assert!(block_size >= 2);
+    let zeros = bigl2::lattice_reduce(basis, ETA, delta);
+    let (n, _) = basis.dimensions();
+    let rank = n - zeros;
+    if rank < block_size {
+        return zeros;
+    }
+
+    loop {
+        let mut changed = false;
+        for k in 0..=(rank - block_size) {
+            changed |= reduce_block(basis, k, k + block_size, delta, None);
+        }
+        for end in (block_size..=rank).rev() {
+            changed |= dual_reduce_block(basis, end - block_size, end, delta);
+        }
+        if !changed {
+            break;
+        }
+    }
+    zeros
+}
+
+/// Hermite-Korkine-Zolotarev (HKZ) reduction
+///
+/// For each position `k`, the shortest vector of the lattice generated by the projections
+/// of `b_k, ..., b_{d-1}` orthogonally to `b_0, ..., b_{k-1}` is found by exact
+/// enumeration and inserted at position `k`. In the reduced basis, each `b*_k` is a
+/// shortest vector of its projected lattice, and the basis is size-reduced (`|mu| <= 1/2`):
+/// in particular `b_0` is a shortest vector of the lattice.
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///
+/// The basis is reduced in-place. The zero vectors coming from linear dependencies are
+/// moved to the end of the basis, and their number is returned. The enumeration is
+/// exponential in the rank of the lattice: this is only practical in small dimensions.
+pub fn hkz_reduce(basis: &mut Matrix<Integer>) -> usize {
+    let zeros = bigl2::lattice_reduce(basis, ETA, HKZ_DELTA);
+    let (n, _) = basis.dimensions();
+    let rank = n - zeros;
+
+    let one = Rational::from(1);
+    for k in 0..rank.saturating_sub(1) {
+        if let Some(vector) = shortest_in_block(basis, k, rank, &one, None) {
+            insert(basis, k, vector, HKZ_DELTA);
+        }
+    }
+    zeros
+}
+
+/// Parameters of the BKZ 2.0 reduction
+///
+/// The parameters are set with a builder:
+///
+/// ```rust
+/// use lll_rs::bkz::BkzParams;
+///
+/// let params = BkzParams::new(20).linear_pruning().rerandomizations(4).auto_abort(1., 5);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct BkzParams {
+    /// Number of vectors of the blocks
+    block_size: usize,
+
+    /// delta factor of the basis reduction
+    delta: f64,
+
+    /// Pruning coefficients of the enumeration of the full blocks
+    pruning: Option<Vec<f64>>,
+
+    /// Number of rerandomizations of a block when the pruned enumeration fails
+    rerandomizations: usize,
+
+    /// Scale and number of tours of the auto-abort criterion
+    auto_abort: Option<(f64, usize)>,
+
+    /// Maximum number of tours
+    max_tours: Option<usize>,
+
+    /// Seed of the random generator of the rerandomizations
+    seed: u64,
+}
+
+impl BkzParams {
+    /// Parameters for blocks of `block_size` vectors
+    ///
+    /// Defaults to `delta = 0.99`, exact enumeration and tours until the basis does not
+    /// change, which is the plain BKZ algorithm.
+    pub fn new(block_size: usize) -> Self {
+        Self {
+            block_size,
+            delta: 0.99,
+            pruning: None,
+            rerandomizations: 0,
+            auto_abort: None,
+            max_tours: None,
+            seed: 0,
+        }
+    }
+
+    /// Set the delta factor of the basis reduction
+    pub fn delta(mut self, delta: f64) -> Self {
+        self.delta = delta;
+        self
+    }
+
+    /// Set the pruning coefficients of the enumeration of the blocks
+    ///
+    /// There must be one coefficient per vector of the block (see `svp::linear_pruning`).
+    /// The blocks at the end of the basis, which are shorter, are enumerated without
+    /// pruning.
+    ///
+    /// # Panics
+    /// if the first coefficient is not `1`, or if the coefficients are not positive and
+    /// nonincreasing
+    pub fn pruning(mut self, pruning: Vec<f64>) -> Self {
+        svp::check_pruning(&pruning);
+        self.pruning = Some(pruning);
+        self
+    }
+
+    /// Use linear pruning for the enumeration of the blocks
+    pub fn linear_pruning(self) -> Self {
+        let pruning = svp::linear_pruning(self.block_size);
+        self.pruning(pruning)
+    }
+
+    /// Set the number of rerandomizations of a block when its pruned enumeration fails
+    ///
+    /// The vectors of the block are replaced by random unimodular combinations of them
+    /// and reduced again (L² algorithm) before each new enumeration. This has no effect
+    /// without pruning: the exact enumeration never misses the shortest vector.
+    pub fn rerandomizations(mut self, rerandomizations: usize) -> Self {
+        self.rerandomizations = rerandomizations;
+        self
+    }
+
+    /// Stop the tours when the Gram-Schmidt profile stops flattening
+    ///
+    /// After each tour, the slope of the profile is computed (see `metrics::gso_slope`).
+    /// The reduction stops after `max_no_decrease` consecutive tours whose slope (in
+    /// absolute value) is not below `scale` times the best slope so far.
+    pub fn auto_abort(mut self, scale: f64, max_no_decrease: usize) -> Self {
+        self.auto_abort = Some((scale, max_no_decrease));
+        self
+    }
+
+    /// Set the maximum number of tours
+    pub fn max_tours(mut self, max_tours: usize) -> Self {
+        self.max_tours = Some(max_tours);
+        self
+    }
+
+    /// Set the seed of the random generator of the rerandomizations
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+/// Lattice reduction (BKZ algorithm)
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * block_size: number of vectors of the blocks
+///  * delta: delta factor of the basis reduction
+///
+/// The basis is reduced in-place. The zero vectors coming from linear dependencies are
+/// moved to the end of the basis, and their number is returned. A block size of `2`
+/// gives an LLL-reduced basis, and a block size equal to the rank of the lattice gives a
+/// shortest vector of the lattice as first vector (the enumeration is exponential in the
+/// block size).
+///
+/// # Panics
+/// if delta < 1/4 or delta >= 1  
+/// if block_size is zero
+pub fn bkz_reduce(basis: &mut Matrix<Integer>, block_size: usize, delta: f64) -> usize {
+    bkz2_reduce(basis, &BkzParams::new(block_size).delta(delta))
+}
+
+/// Lattice reduction (BKZ 2.0 algorithm)
+///
+/// Same as `bkz_reduce`, with the pruning, rerandomization and auto-abort options of
+/// `params`. With pruning, the first vector is no longer guaranteed to be a shortest
+/// vector of its block, but the blocks are enumerated much faster.
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * params: parameters of the reduction
+///
+/// The basis is reduced in-place (it is always LLL-reduced, even when the tours are
+/// stopped early). The zero vectors coming from linear dependencies are moved to the end
+/// of the basis, and their number is returned.
+///
+/// # Panics
+/// if delta < 1/4 or delta >= 1  
+/// if the block size is zero
+pub fn bkz2_reduce(basis: &mut Matrix<Integer>, params: &BkzParams) -> usize {
+    bkz2_reduce_with_budget(basis, params, ReductionBudget::default())
+        .expect("the reduction has no budget")
+}
+
+/// Lattice reduction (BKZ 2.0 algorithm) within a budget
+///
+/// Same as `bkz2_reduce`, but the reduction stops when `budget` is exhausted: its
+/// iterations are the tours (in addition to the maximum number of tours of `params`), and
+/// its wall time is checked between the block reductions (a single block reduction can
+/// overrun it).
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * params: parameters of the reduction
+///  * budget: limits on the tours and the wall time
+///
+/// The basis is reduced in-place. Returns the number of zero vectors or, when the budget
+/// is exhausted, `ReductionError::IterationLimit` or `ReductionError::TimeLimit` with the
+/// progress of the reduction at that point: the `iterations` are the
+/// completed tours, the `swaps` the inserted vectors and `index` the first vector of the
+/// next block. The basis then still generates the same lattice, and it is LLL-reduced
+/// unless the budget was exhausted during the initial L² reduction (the progress is then
+/// that of the L² reduction).
+///
+/// # Panics
+/// if delta < 1/4 or delta >= 1  
+/// if the block size is zero
+pub fn bkz2_reduce_with_budget(
+    basis: &mut Matrix<Integer>,
+    params: &BkzParams,
+    budget: ReductionBudget,
+) -> Result<usize, (ReductionError, ReductionProgress)> {
+    assert!(params.block_size > 0);
+    let start = Instant::now();
+    let l2_budget = ReductionBudget {
+        max_iterations: None,
+        ..budget
+    };
+    let l2_params = ReductionParams::default().eta(ETA).delta(params.delta);
+    let zeros = bigl2::lattice_reduce_with_budget(basis, &l2_params, l2_budget)?;
+    let (n, dim) = basis.dimensions();
+    let rank = n - zeros;
+
+    let mut progress = ReductionProgress {
+        iterations: 0,
+        swaps: 0,
+        index: 0,
+        rank,
+        elapsed: start.elapsed(),
+    };
+    let mut rand = RandState::new();
+    rand.seed(&Integer::from(params.seed));
+    let mut best_slope = f64::INFINITY;
+    let mut no_decrease = 0;
+    while params.max_tours.is_none_or(|max| progress.iterations < max) {
+        if budget
+            .max_iterations
+            .is_some_and(|max| progress.iterations >= max)
+        {
+            return Err((ReductionError::IterationLimit, progress));
+        }
+        let exhausted = || {
+            budget
+                .max_duration
+                .is_some_and(|max| start.elapsed() >= max)
+        };
+        let changed = tour(basis, rank, params, &mut rand, &mut progress, exhausted);
+        progress.elapsed = start.elapsed();
+        match changed {
+            Some(true) => progress.iterations += 1,
+            Some(false) => break,
+            None => return Err((ReductionError::TimeLimit, progress)),
+        }
+
+        if let Some((scale, max_no_decrease)) = params.auto_abort {
+            let r = metrics::gram_schmidt_r(&basis.submatrix(0..rank, 0..dim));
+            let slope = -metrics::gso_slope(&r);
+            if slope >= scale * best_slope {
+                no_decrease += 1;
+            } else {
+                no_decrease = 0;
+            }
+            best_slope = best_slope.min(slope);
+            if no_decrease >= max_no_decrease {
+                break;
+            }
+        }
+    }
+    Ok(zeros)
+}
+
+/// One BKZ tour on the first `rank` vectors of the basis
+///
+/// The insertions are counted in `progress.swaps`, and the index of the current block
+/// is stored in `progress.index`. The tour stops before a block reduction when
+/// `exhausted` returns `true`. Returns whether a vector was inserted, or `None` if the
+/// tour was stopped.
+fn tour<F>(
+    basis: &mut Matrix<Integer>,
+    rank: usize,
+    params: &BkzParams,
+    rand: &mut RandState,
+    progress: &mut ReductionProgress,
+    exhausted: F,
+) -> Option<bool>
+where
+    F: Fn() -> bool,
+{
+    let mut changed = false;
+    for k in 0..rank.saturating_sub(1) {
+        progress.index = k;
+        if exhausted() {
+            return None;
+        }
+        let end = rank.min(k + params.block_size);
+        let pruning = params.pruning.as_deref().filter(|p| p.len() == end - k);
+        let attempts = if pruning.is_some() {
+            params.rerandomizations + 1
+        } else {
+            1
+        };
+
+        for attempt in 0..attempts {
+            if attempt > 0 {
+                rerandomize(basis, k, end, params.delta, rand);
+            }
+            if reduce_block(basis, k, end, params.delta, pruning) {
+                progress.swaps += 1;
+                changed = true;
+                break;
+            }
+        }
+    }
+    progress.index = 0;
+    Some(changed)
+}
+
+/// Inserts at position `k` the shortest vector of the projected block `basis[k..end]`
+/// if its squared norm is below `delta * ||b*_k||²`
+///
+/// The enumeration is pruned with the coefficients `pruning`, if any. Returns whether a
+/// vector was inserted.
+pub(crate) fn reduce_block(
+    basis: &mut Matrix<Integer>,
+    k: usize,
+    end: usize,
+    delta: f64,
+    pruning: Option<&[f64]>,
+) -> bool {
+    let factor = Rational::from_f64(delta).expect("delta must be finite");
+    match shortest_in_block(basis, k, end, &factor, pruning) {
+        Some(vector) => {
+            insert(basis, k, vector, delta);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Shortest vector of the lattice whose projection orthogonally to `basis[..k]` is in the
+/// lattice of the projected block `basis[k..end]`, if its projection has a squared norm
+/// below `factor * ||b*_k||²`
+fn shortest_in_block(
+    basis: &Matrix<Integer>,
+    k: usize,
+    end: usize,
+    factor: &Rational,
+    pruning: Option<&[f64]>,
+) -> Option<BigVector> {
+    let (_, dim) = basis.dimensions();
+    let (mu, norms) = metrics::gram_schmidt(&basis.submatrix(0..end, 0..dim));
+    let bound = Rational::from(factor * &norms[k]);
+
+    let block_mu = mu.submatrix(k..end, k..end);
+    let coords = match pruning {
+        Some(pruning) => svp::enumerate_pruned(&block_mu, &norms[k..end], bound, pruning),
+        None => svp::enumerate_shortest(&block_mu, &norms[k..end], bound),
+    }?;
+    Some(
+        coords
+            .iter()
+            .enumerate()
+            .fold(BigVector::init(dim), |acc, (i, x)| {
+                acc.add(&basis[k + i].mulf(x))
+            }),
+    )
+}
+
+/// Makes `b*_{end-1}` as long as possible in the projected block `basis[start..end]`
+/// (dual SVP reduction), if it can grow by a factor larger than `1 / delta`
+///
+/// The shortest vector `w` of the dual of the projected block is found by enumeration on
+/// the reversed dual basis, whose Gram-Schmidt data is derived from the one of the block:
+/// its squared norms are the inverses `1 / ||b*_{end-1-i}||²`, and its coefficients are
+/// those of the inverse of the `mu` matrix of the block. The block is then replaced by a
+/// basis whose last vector `b` satisfies `<w, b> = 1`, and whose other vectors are
+/// orthogonal to `w`: its last Gram-Schmidt vector is `w / ||w||²`. The basis is reduced
+/// again (L² algorithm) afterwards.
+///
+/// Returns whether the block changed.
+pub(crate) fn dual_reduce_block(
+    basis: &mut Matrix<Integer>,
+    start: usize,
+    end: usize,
+    delta: f64,
+) -> bool {
+    let (_, dim) = basis.dimensions();
+    let m = end - start;
+    let (mu, norms) = metrics::gram_schmidt(&basis.submatrix(0..end, 0..dim));
+    let inverse = mu
+        .submatrix(start..end, start..end)
+        .inverse()
+        .expect("the mu matrix is unitriangular");
+
+    let mut dual_mu: Matrix<Rational> = Matrix::init(m, m);
+    for t in 0..m {
+        dual_mu[t][t] = Rational::from(1);
+        for s in 0..t {
+            dual_mu[t][s] = inverse[m - 1 - s][m - 1 - t].clone();
+        }
+    }
+    let dual_norms: Vec<Rational> = (0..m)
+        .map(|t| Rational::from(norms[end - 1 - t].recip_ref()))
+        .collect();
+    let bound = Rational::from_f64(delta).expect("delta must be finite") * &dual_norms[0];
+
+    let coords = match svp::enumerate_shortest(&dual_mu, &dual_norms, bound) {
+        Some(coords) => coords,
+        None => return false,
+    };
+
+    // Unimodular U such that U x = e_{m-1}, where x are the coordinates of w in the dual
+    // basis of the block (the HNF of the column x is e_0, x being primitive)
+    let column: Matrix<Integer> = Matrix::from_columns(
+        coords
+            .into_iter()
+            .rev()
+            .map(|x| BigVector::from_vector(vec![x]))
+            .collect(),
+    );
+    let (_, transform) = hnf::hermite_normal_form_with_transform(&column);
+    let block: Vec<BigVector> = (1..=m)
+        .map(|i| {
+            (0..m).fold(BigVector::init(dim), |acc, j| {
+                acc.add(&basis[start + j].mulf(&transform[i % m][j]))
+            })
+        })
+        .collect();
+    for (i, vector) in block.into_iter().enumerate() {
+        basis[start + i] = vector;
+    }
+    bigl2::lattice_reduce(basis, ETA, delta);
+    true
+}
+
+/// Replaces the vectors of `basis[k..end]` by random unimodular combinations of them,
+/// and reduces the basis again (L² algorithm)
+///
+/// Each vector `b_i` of the block gets `-1`, `0` or `1` times each of the next vectors of
+/// the block: the transformation is triangular with ones on the diagonal.
+pub(crate) fn rerandomize(
+    basis: &mut Matrix<Integer>,
+    k: usize,
+    end: usize,
+    delta: f64,
+    rand: &mut RandState,
+) {
+    for i in k..end {
+        for j in (i + 1)..end {
+            let x = Integer::from(Integer::from(3).random_below_ref(rand)) - 1;
+            if x != 0 {
+                let step = basis[j].mulf(&x);
+                basis[i] += &step;
+            }
+        }
+    }
+    bigl2::lattice_reduce(basis, ETA, delta);
+}
+
+/// Inserts `vector` (a lattice vector) at position `k`, and removes the linear dependency
+/// with an L² reduction
+fn insert(basis: &mut Matrix<Integer>, k: usize, vector: BigVector, delta: f64) {
+    let (n, dim) = basis.dimensions();
+    let mut rows: Vec<BigVector> = Vec::with_capacity(n + 1);
+    rows.extend((0..k).map(|i| basis[i].clone()));
+    rows.push(vector);
+    rows.extend((k..n).map(|i| basis[i].clone()));
+
+    let mut extended = Matrix::from_columns(rows);
+    bigl2::lattice_reduce(&mut extended, ETA, delta);
+    *basis = extended.submatrix(0..n, 0..dim);
+}
@@ -0,0 +1,120 @@
+//! Complex floating-point coefficients
+//!
+//! The dot product of two `VectorC`s is the Hermitian inner product, conjugating its second
+//! argument: `<u, v> = sum u_j * conj(v_j)`, so that `<v, v>` is the (real) squared norm
+//! of `v`.
+
+use crate::vector::VectorC;
+
+use std::{fmt, iter::Sum, ops};
+
+/// Complex number `re + im * i` with `f64` parts
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    /// Complex conjugate
+    pub fn conj(&self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    /// Squared magnitude `re² + im²`
+    pub fn norm(&self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+}
+
+impl From<u32> for Complex {
+    fn from(re: u32) -> Self {
+        Self::new(f64::from(re), 0.)
+    }
+}
+
+impl From<(f64, f64)> for Complex {
+    fn from((re, im): (f64, f64)) -> Self {
+        Self::new(re, im)
+    }
+}
+
+impl From<Complex> for (f64, f64) {
+    fn from(c: Complex) -> Self {
+        (c.re, c.im)
+    }
+}
+
+impl ops::Add<&Complex> for Complex {
+    type Output = Self;
+    fn add(self, other: &Self) -> Self {
+        Self::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl ops::Sub<&Complex> for Complex {
+    type Output = Self;
+    fn sub(self, other: &Self) -> Self {
+        Self::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl ops::Mul<&Complex> for Complex {
+    type Output = Self;
+    fn mul(self, other: &Self) -> Self {
+        Self::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl ops::AddAssign<&Complex> for Complex {
+    fn add_assign(&mut self, other: &Self) {
+        *self = *self + other;
+    }
+}
+
+impl ops::SubAssign<&Complex> for Complex {
+    fn sub_assign(&mut self, other: &Self) {
+        *self = *self - other;
+    }
+}
+
+impl Sum for Complex {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |acc, x| acc + &x)
+    }
+}
+
+impl fmt::Display for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.im < 0. {
+            write!(f, "{}-{}i", self.re, -self.im)
+        } else {
+            write!(f, "{}+{}i", self.re, self.im)
+        }
+    }
+}
+
+impl fmt::Debug for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl VectorC {
+    /// Create an instance from the `(re, im)` pairs of the coefficients
+    pub fn from_pairs(pairs: Vec<(f64, f64)>) -> Self {
+        pairs.into_iter().map(Complex::from).collect()
+    }
+
+    /// Squared norm `<self, self>` (real)
+    pub fn norm_sq(&self) -> f64 {
+        self.iter().map(Complex::norm).sum()
+    }
+}
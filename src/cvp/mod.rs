@@ -0,0 +1,446 @@
+//! Closest vector problem (CVP) solvers
+//!
+//! Given a lattice basis and a target vector, these functions look for the lattice
+//! vector closest to the target (for the euclidean norm).
+
+use crate::gram;
+use crate::l2::bigl2;
+use crate::matrix::Matrix;
+use crate::svp;
+use crate::vector::{BigVector, Dot, RationalVector};
+
+use rug::{Integer, Rational};
+
+/// Approximate closest vector found by one of Babai's algorithms
+#[derive(Clone, Debug, PartialEq)]
+pub struct CvpSolution {
+    /// The lattice vector close to the target
+    pub vector: BigVector,
+
+    /// Coordinates of `vector` in the basis: `vector = sum coords[i] * basis[i]`
+    pub coords: BigVector,
+
+    /// Squared distance `||target - vector||²`
+    pub distance: Integer,
+}
+
+impl CvpSolution {
+    fn new(basis: &Matrix<Integer>, target: &BigVector, coords: Vec<Integer>) -> Self {
+        let vector = combine(basis, &coords);
+        let error = target.sub(&vector);
+        Self {
+            distance: error.dot(&error),
+            vector,
+            coords: BigVector::from_vector(coords),
+        }
+    }
+}
+
+/// Exact Gram-Schmidt orthogonalisation of a basis
+struct Gso {
+    /// Orthogonalised vectors `b*_i`
+    ortho: Vec<RationalVector>,
+
+    /// Squared norms `||b*_i||²`
+    norms: Vec<Rational>,
+
+    /// Gram-Schmidt coefficients `mu_ij = <b_i, b*_j> / ||b*_j||²`
+    mu: Matrix<Rational>,
+}
+
+impl Gso {
+    fn new(basis: &Matrix<Integer>) -> Self {
+        let (d, _) = basis.dimensions();
+        let mut ortho: Vec<RationalVector> = Vec::with_capacity(d);
+        let mut norms: Vec<Rational> = Vec::with_capacity(d);
+        let mut mu: Matrix<Rational> = Matrix::init(d, d);
+
+        for i in 0..d {
+            let b_i = basis[i].to_rational();
+            let mut b_star = b_i.clone();
+            for j in 0..i {
+                mu[i][j] = b_i.dot(&ortho[j]) / &norms[j];
+                b_star = b_star.sub(&ortho[j].mulf(&mu[i][j]));
+            }
+            norms.push(b_star.dot(&b_star));
+            ortho.push(b_star);
+        }
+
+        Self { ortho, norms, mu }
+    }
+
+    /// Coordinates of the orthogonal projection of `target` on each `b*_i`
+    fn project(&self, target: &RationalVector) -> Vec<Rational> {
+        self.ortho
+            .iter()
+            .zip(&self.norms)
+            .map(|(b_star, norm)| target.dot(b_star) / norm)
+            .collect()
+    }
+
+    /// Center of the search interval for coefficient `i`, knowing coefficients `i+1..d`
+    fn center(&self, i: usize, projection: &[Rational], coords: &[Integer]) -> Rational {
+        let mut center = projection[i].clone();
+        for (j, x) in coords.iter().enumerate().skip(i + 1) {
+            center -= Rational::from(x) * &self.mu[j][i];
+        }
+        center
+    }
+}
+
+/// State of the enumeration tree search
+struct Enumeration<'a> {
+    gso: &'a Gso,
+    projection: Vec<Rational>,
+    pruning: Vec<Rational>,
+    coords: Vec<Integer>,
+    best_coords: Vec<Integer>,
+    best_dist: Rational,
+}
+
+impl<'a> Enumeration<'a> {
+    /// Explore all the coefficients for level `i` that keep the partial distance
+    /// strictly below the best distance found so far (scaled by the pruning coefficient
+    /// of the level)
+    fn search(&mut self, i: usize, partial: Rational) {
+        let center = self.gso.center(i, &self.projection, &self.coords);
+        let start: Integer = center.round_ref().into();
+
+        for step in &[1, -1] {
+            let mut x = if *step > 0 {
+                start.clone()
+            } else {
+                start.clone() - 1
+            };
+            loop {
+                let offset = Rational::from(&x) - &center;
+                let dist = partial.clone() + offset.square() * &self.gso.norms[i];
+                if dist >= Rational::from(&self.best_dist * &self.pruning[i]) {
+                    break;
+                }
+                self.coords[i] = x.clone();
+                if i == 0 {
+                    self.best_dist = dist;
+                    self.best_coords = self.coords.clone();
+                } else {
+                    self.search(i - 1, dist);
+                }
+                x += *step;
+            }
+        }
+    }
+}
+
+fn combine(basis: &Matrix<Integer>, coords: &[Integer]) -> BigVector {
+    let (_, n) = basis.dimensions();
+    coords
+        .iter()
+        .enumerate()
+        .fold(BigVector::init(n), |acc, (i, x)| acc.add(&basis[i].mulf(x)))
+}
+
+/// Babai nearest plane coefficients of `target` in `basis`
+fn nearest_plane_coords(gso: &Gso, projection: &[Rational]) -> Vec<Integer> {
+    let d = projection.len();
+    let mut coords = vec![Integer::new(); d];
+    for i in (0..d).rev() {
+        coords[i] = gso.center(i, projection, &coords).round_ref().into();
+    }
+    coords
+}
+
+/// Approximate closest vector (Babai's nearest plane algorithm)
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * target: The vector to approximate
+///
+/// The quality of the approximation depends on the quality of `basis`, this
+/// function should usually be called on a reduced basis: the distance between the
+/// solution and the orthogonal projection of `target` on the span of the basis is at
+/// most `sqrt(sum ||b*_i||²) / 2`.
+///
+/// # Panics
+/// if the vectors of `basis` are not linearly independent
+pub fn nearest_plane(basis: &Matrix<Integer>, target: &BigVector) -> CvpSolution {
+    let gso = Gso::new(basis);
+    let projection = gso.project(&target.to_rational());
+    CvpSolution::new(basis, target, nearest_plane_coords(&gso, &projection))
+}
+
+/// Approximate closest vector (Babai's rounding technique)
+///
+/// The coordinates of `target` in `basis` are computed exactly, with rational
+/// arithmetic, and rounded to the nearest integers: the solution is
+/// `round(target * B^-1) * B`. When the vectors of the basis span a subspace, the
+/// coordinates of the orthogonal projection of `target` on the subspace are rounded.
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * target: The vector to approximate
+///
+/// This is cheaper than `nearest_plane`, but its approximation is worse, unless the
+/// basis is strongly reduced (nearly orthogonal).
+///
+/// # Panics
+/// if the vectors of `basis` are not linearly independent
+pub fn babai_rounding(basis: &Matrix<Integer>, target: &BigVector) -> CvpSolution {
+    let (d, _) = basis.dimensions();
+    let mut gram_matrix: Matrix<Rational> = Matrix::init(d, d);
+    for i in 0..d {
+        for j in 0..d {
+            gram_matrix[i][j] = Rational::from(basis[i].dot(&basis[j]));
+        }
+    }
+    let inverse = gram_matrix
+        .inverse()
+        .expect("the vectors of the basis must be linearly independent");
+
+    // The coordinates x of the projection satisfy x * gram = (<target, b_j>)_j
+    let products: Vec<Rational> = (0..d)
+        .map(|j| Rational::from(target.dot(&basis[j])))
+        .collect();
+    let coords = (0..d)
+        .map(|i| {
+            let x = (0..d)
+                .map(|j| Rational::from(&products[j] * &inverse[j][i]))
+                .sum::<Rational>();
+            x.round_ref().into()
+        })
+        .collect();
+    CvpSolution::new(basis, target, coords)
+}
+
+/// Exact closest vector
+///
+/// The basis is first reduced (L² algorithm), then all the lattice vectors that
+/// are closer to `target` than Babai's approximation are enumerated.
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * target: The vector to approximate
+///
+/// # Panics
+/// if the vectors of `basis` are not linearly independent
+pub fn closest_vector_exact(basis: &Matrix<Integer>, target: &BigVector) -> BigVector {
+    let mut reduced = basis.clone();
+    bigl2::lattice_reduce(&mut reduced, 0.501, 0.998);
+
+    combine(&reduced, &enumerate_closest(&reduced, target, None))
+}
+
+/// Closest vector by enumeration, with optional pruning
+///
+/// The basis is first reduced (L² algorithm), then the lattice vectors in the sphere
+/// around `target` whose radius is the distance to Babai's approximation are enumerated,
+/// using the Gram-Schmidt orthogonalisation of the reduced basis. Without pruning, the
+/// solution is exact (see `closest_vector_exact`). With pruning coefficients (see
+/// `svp::linear_pruning`), the subtrees of level `i` whose partial squared distance
+/// exceeds `pruning[i]` times the current radius are cut: the search is much faster, but
+/// it can miss the closest vector. The solution is never farther than Babai's
+/// approximation.
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * target: The vector to approximate
+///  * pruning: pruning coefficients, one per vector of the basis (optional)
+///
+/// The coordinates of the solution are given in `basis`.
+///
+/// # Panics
+/// if the vectors of `basis` are not linearly independent  
+/// if the pruning coefficients are not sound, or if their number is not the number of
+/// vectors
+pub fn closest_vector(
+    basis: &Matrix<Integer>,
+    target: &BigVector,
+    pruning: Option<&[f64]>,
+) -> CvpSolution {
+    let (d, _) = basis.dimensions();
+    if let Some(pruning) = pruning {
+        svp::check_pruning(pruning);
+        assert_eq!(
+            d,
+            pruning.len(),
+            "one pruning coefficient per vector is needed"
+        );
+    }
+    let mut reduced = basis.clone();
+    let (_, transform) = bigl2::lattice_reduce_transform(&mut reduced, 0.501, 0.998);
+
+    // Coordinates in the reduced basis, mapped back with the transformation
+    let reduced_coords = enumerate_closest(&reduced, target, pruning);
+    let coords = (0..d)
+        .map(|j| {
+            reduced_coords
+                .iter()
+                .enumerate()
+                .map(|(i, y)| Integer::from(y * &transform[i][j]))
+                .sum()
+        })
+        .collect();
+    CvpSolution::new(basis, target, coords)
+}
+
+/// Coordinates of the closest vector in the original basis
+///
+/// Returns the integer vector `x` such that `sum x_i * basis[i]` is the lattice vector
+/// closest to `target` (see `closest_vector_exact`). The basis is reduced while keeping
+/// track of the unimodular transformation, which is used to map the coordinates found
+/// in the reduced basis back to `basis`.
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * target: The vector to approximate
+///
+/// # Panics
+/// if the vectors of `basis` are not linearly independent
+pub fn closest_coords(basis: &Matrix<Integer>, target: &BigVector) -> BigVector {
+    let (d, n) = basis.dimensions();
+    let mut gram_matrix: Matrix<Integer> = Matrix::init(d, d);
+    for i in 0..d {
+        for j in 0..d {
+            gram_matrix[i][j] = basis[i].dot(&basis[j]);
+        }
+    }
+    let transform = gram::lattice_reduce_gram(&mut gram_matrix, 0.501, 0.998);
+
+    let mut reduced: Matrix<Integer> = Matrix::init(d, n);
+    for i in 0..d {
+        reduced[i] = combine(basis, &transform[i].iter().cloned().collect::<Vec<_>>());
+    }
+
+    let coords = enumerate_closest(&reduced, target, None);
+    (0..d)
+        .map(|j| {
+            coords
+                .iter()
+                .enumerate()
+                .map(|(i, y)| Integer::from(y * &transform[i][j]))
+                .sum()
+        })
+        .collect()
+}
+
+/// Coordinates (in `reduced`) of the lattice vector closest to `target`
+///
+/// All the lattice vectors that are closer to `target` than Babai's approximation
+/// are enumerated, except the subtrees cut by the pruning coefficients (if any).
+fn enumerate_closest(
+    reduced: &Matrix<Integer>,
+    target: &BigVector,
+    pruning: Option<&[f64]>,
+) -> Vec<Integer> {
+    let gso = Gso::new(reduced);
+    let projection = gso.project(&target.to_rational());
+    let coords = nearest_plane_coords(&gso, &projection);
+    let (d, _) = reduced.dimensions();
+    if d == 0 {
+        return coords;
+    }
+
+    // Distance between `target` and the lattice point, ignoring the component
+    // of `target` that is orthogonal to the lattice
+    let best_dist = (0..d).fold(Rational::new(), |acc, i| {
+        let offset = Rational::from(&coords[i]) - gso.center(i, &projection, &coords);
+        acc + offset.square() * &gso.norms[i]
+    });
+
+    let mut enumeration = Enumeration {
+        gso: &gso,
+        projection,
+        pruning: match pruning {
+            Some(pruning) => pruning
+                .iter()
+                .map(|&coefficient| Rational::from_f64(coefficient).expect("finite pruning"))
+                .collect(),
+            None => vec![Rational::from(1); d],
+        },
+        coords: coords.clone(),
+        best_coords: coords,
+        best_dist,
+    };
+    enumeration.search(d - 1, Rational::new());
+
+    enumeration.best_coords
+}
+
+/// Kannan embedding of a closest vector problem
+///
+/// Builds the basis of `d + 1` vectors `(b_i, 0)` and `(target, factor)`, in dimension
+/// `n + 1`. If `v` is a lattice vector close to `target`, `(target - v, factor)` is a short
+/// vector of the embedding lattice: solving the shortest vector problem in the embedding
+/// solves the closest vector problem.
+///
+/// The factor is usually chosen close to the expected distance `||target - v||`, or a bit
+/// below. When it is too small, the short vectors of the lattice itself can be shorter
+/// than `(target - v, factor)`; when it is too large, the reduction does not find the
+/// embedded vector (a factor of `1` is a common choice for small distances).
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * target: The vector to approximate
+///  * factor: The embedding factor
+pub fn kannan_embedding(
+    basis: &Matrix<Integer>,
+    target: &BigVector,
+    factor: &Integer,
+) -> Matrix<Integer> {
+    let (d, _) = basis.dimensions();
+    let mut last: Vec<Integer> = target.iter().cloned().collect();
+    last.push(factor.clone());
+    basis
+        .hstack(&Matrix::init(d, 1))
+        .vstack(&Matrix::from_columns(vec![BigVector::from_vector(last)]))
+}
+
+/// Approximate closest vector by reduction of the Kannan embedding
+///
+/// The embedding lattice (see `kannan_embedding`) is reduced (L² algorithm), and the
+/// shortest reduced vector `(e, ±factor)` gives the lattice vector `target ∓ e`.
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice, of linearly independent vectors
+///  * target: The vector to approximate
+///  * factor: The embedding factor (positive)
+///
+/// Returns the solution, with its coordinates in `basis`, or `None` if no reduced vector
+/// has `±factor` as last coordinate (the factor is not suitable for this target).
+///
+/// # Panics
+/// if factor is not positive
+pub fn closest_vector_embedding(
+    basis: &Matrix<Integer>,
+    target: &BigVector,
+    factor: &Integer,
+) -> Option<CvpSolution> {
+    assert!(*factor > 0, "the embedding factor must be positive");
+    let (d, _) = basis.dimensions();
+    let mut embedding = kannan_embedding(basis, target, factor);
+    let (_, transform) = bigl2::lattice_reduce_transform(&mut embedding, 0.501, 0.998);
+
+    // Row i of the reduced basis is sum_j U[i][j] * (b_j, 0) + U[i][d] * (target, factor)
+    let i = (0..=d)
+        .filter(|&i| transform[i][d] == 1 || transform[i][d] == -1)
+        .min_by_key(|&i| embedding[i].dot(&embedding[i]))?;
+    let sign = Integer::from(-&transform[i][d]);
+    let coords = (0..d)
+        .map(|j| Integer::from(&transform[i][j] * &sign))
+        .collect();
+    Some(CvpSolution::new(basis, target, coords))
+}
+
+/// Squared distance from `target` to the lattice
+///
+/// This is the squared norm of `target - closest_vector_exact(basis, target)`.
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * target: The vector to approximate
+///
+/// # Panics
+/// if the vectors of `basis` are not linearly independent
+pub fn distance_to_lattice(basis: &Matrix<Integer>, target: &BigVector) -> Integer {
+    let error = target.sub(&closest_vector_exact(basis, target));
+    error.dot(&error)
+}
@@ -0,0 +1,25 @@
+//! Dual lattice
+//!
+//! The dual of a lattice Λ is the set of vectors `x` of its span such that
+//! `<x, v>` is an integer for every `v` in Λ.
+
+use crate::matrix::Matrix;
+
+/// Basis of the dual lattice
+///
+/// For a square basis `B` (whose vectors are `basis[i]`), the dual basis is
+/// `(B^-1)^T`: the dual vectors `d_i` satisfy `<d_i, b_j> = 1` if `i = j` and
+/// `0` otherwise.
+///
+/// Arguments:
+///  * basis: A full-rank square generating matrix for the lattice
+///
+/// # Panics
+/// if `basis` is not square or its vectors are not linearly independent
+pub fn dual_basis(basis: &Matrix<rug::Integer>) -> Matrix<rug::Rational> {
+    basis
+        .to_rational()
+        .inverse()
+        .expect("the basis must be square and full-rank")
+        .transpose()
+}
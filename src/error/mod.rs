@@ -0,0 +1,122 @@
+//! Errors of the public API
+//!
+//! The operations that panic on invalid arguments (dimension mismatches, out of bounds
+//! indices, invalid reduction factors) have a `try_` counterpart returning a
+//! `LatticeError` instead.
+
+use crate::l2::ReductionError;
+use crate::vector::{DimensionMismatch, Empty};
+
+use std::fmt;
+
+/// Errors of the fallible (`try_`) operations
+#[derive(Clone, Debug, PartialEq)]
+pub enum LatticeError {
+    /// The operands have incompatible dimensions (those of the left and right operands)
+    DimensionMismatch(usize, usize),
+
+    /// An index is not smaller than its bound
+    IndexOutOfBounds { index: usize, bound: usize },
+
+    /// A range `start..end` is reversed or exceeds its bound
+    RangeOutOfBounds {
+        start: usize,
+        end: usize,
+        bound: usize,
+    },
+
+    /// A vector or a matrix has no coefficients
+    Empty,
+
+    /// The delta factor of a reduction is not in `[1/4, 1)`
+    InvalidDelta(f64),
+
+    /// The eta factor of a reduction is not in `[1/2, sqrt(delta))`
+    InvalidEta(f64),
+
+    /// A reduction stopped before the basis was reduced
+    Reduction(ReductionError),
+}
+
+impl fmt::Display for LatticeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LatticeError::DimensionMismatch(left, right) => {
+                write!(f, "dimension mismatch: {} and {}", left, right)
+            }
+            LatticeError::IndexOutOfBounds { index, bound } => {
+                write!(f, "index {} out of bounds for {}", index, bound)
+            }
+            LatticeError::RangeOutOfBounds { start, end, bound } => {
+                write!(f, "range {}..{} out of bounds for {}", start, end, bound)
+            }
+            LatticeError::Empty => write!(f, "{}", Empty),
+            LatticeError::InvalidDelta(delta) => {
+                write!(f, "delta must be in [1/4, 1), got {}", delta)
+            }
+            LatticeError::InvalidEta(eta) => {
+                write!(f, "eta must be in [1/2, sqrt(delta)), got {}", eta)
+            }
+            LatticeError::Reduction(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for LatticeError {}
+
+impl From<DimensionMismatch> for LatticeError {
+    fn from(DimensionMismatch(left, right): DimensionMismatch) -> Self {
+        LatticeError::DimensionMismatch(left, right)
+    }
+}
+
+impl From<Empty> for LatticeError {
+    fn from(_: Empty) -> Self {
+        LatticeError::Empty
+    }
+}
+
+impl From<ReductionError> for LatticeError {
+    fn from(error: ReductionError) -> Self {
+        LatticeError::Reduction(error)
+    }
+}
+
+/// Check that `index < bound`
+pub(crate) fn check_index(index: usize, bound: usize) -> Result<(), LatticeError> {
+    if index < bound {
+        Ok(())
+    } else {
+        Err(LatticeError::IndexOutOfBounds { index, bound })
+    }
+}
+
+/// Check that `range` is not reversed and that `range.end <= bound`
+pub(crate) fn check_range(
+    range: &std::ops::Range<usize>,
+    bound: usize,
+) -> Result<(), LatticeError> {
+    if range.start <= range.end && range.end <= bound {
+        Ok(())
+    } else {
+        Err(LatticeError::RangeOutOfBounds {
+            start: range.start,
+            end: range.end,
+            bound,
+        })
+    }
+}
+
+/// Check that the range `start..start + len` does not exceed `bound`
+///
+/// When `start + len` overflows, the `end` of the error is `usize::MAX`.
+pub(crate) fn check_block(start: usize, len: usize, bound: usize) -> Result<(), LatticeError> {
+    let end = start
+        .checked_add(len)
+        .ok_or(LatticeError::RangeOutOfBounds {
+            start,
+            end: usize::MAX,
+            bound,
+        })?;
+    check_range(&(start..end), bound)
+}
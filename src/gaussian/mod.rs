@@ -0,0 +1,308 @@
+//! Lattices over the Gaussian integers `Z[i]`
+//!
+//! The dot product of two `GaussianVector`s conjugates its second argument:
+//! `<u, v> = sum u_j * conj(v_j)`, so that `<v, v>` is the (real) squared norm of `v`.
+
+use crate::matrix::Matrix;
+use crate::scalars::{BigNum, FromExt, Scalars};
+use crate::vector::{Dot, GaussianVector};
+
+use rug::{Integer, Rational};
+
+use std::{cmp, fmt, iter::Sum, ops};
+
+/// Gaussian integer `re + im * i`
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct GaussianInteger {
+    pub re: Integer,
+    pub im: Integer,
+}
+
+impl GaussianInteger {
+    pub fn new(re: Integer, im: Integer) -> Self {
+        Self { re, im }
+    }
+
+    /// Complex conjugate
+    pub fn conj(&self) -> Self {
+        Self::new(self.re.clone(), Integer::from(-&self.im))
+    }
+
+    /// Squared magnitude `re² + im²`
+    pub fn norm(&self) -> Integer {
+        Integer::from(self.re.square_ref()) + Integer::from(self.im.square_ref())
+    }
+}
+
+impl From<u32> for GaussianInteger {
+    fn from(re: u32) -> Self {
+        Self::new(Integer::from(re), Integer::new())
+    }
+}
+
+impl ops::Add<&GaussianInteger> for GaussianInteger {
+    type Output = Self;
+    fn add(self, other: &Self) -> Self {
+        Self::new(self.re + &other.re, self.im + &other.im)
+    }
+}
+
+impl ops::Sub<&GaussianInteger> for GaussianInteger {
+    type Output = Self;
+    fn sub(self, other: &Self) -> Self {
+        Self::new(self.re - &other.re, self.im - &other.im)
+    }
+}
+
+impl ops::Mul<&GaussianInteger> for GaussianInteger {
+    type Output = Self;
+    fn mul(self, other: &Self) -> Self {
+        let re = Integer::from(&self.re * &other.re) - Integer::from(&self.im * &other.im);
+        let im = Integer::from(&self.re * &other.im) + Integer::from(&self.im * &other.re);
+        Self::new(re, im)
+    }
+}
+
+impl Sum for GaussianInteger {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |acc, x| acc + &x)
+    }
+}
+
+impl fmt::Display for GaussianInteger {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.im < 0 {
+            write!(f, "{}-{}i", self.re, Integer::from(-&self.im))
+        } else {
+            write!(f, "{}+{}i", self.re, self.im)
+        }
+    }
+}
+
+impl fmt::Debug for GaussianInteger {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+/// Gaussian rational `re + im * i`, for the Gram-Schmidt orthogonalisation
+///
+/// The order is partial: only the numbers with the same imaginary part are compared (by
+/// their real parts). The reductions compare real values only.
+#[derive(Clone, Default, PartialEq)]
+pub struct GaussianRational {
+    pub re: Rational,
+    pub im: Rational,
+}
+
+impl GaussianRational {
+    pub fn new(re: Rational, im: Rational) -> Self {
+        Self { re, im }
+    }
+
+    /// Complex conjugate
+    pub fn conj(&self) -> Self {
+        Self::new(self.re.clone(), Rational::from(-&self.im))
+    }
+
+    /// Squared magnitude `re² + im²`
+    pub fn norm(&self) -> Rational {
+        Rational::from(self.re.square_ref()) + Rational::from(self.im.square_ref())
+    }
+
+    /// Nearest Gaussian integer (rounding both parts)
+    pub fn round(&self) -> GaussianInteger {
+        GaussianInteger::new(self.re.round_ref().into(), self.im.round_ref().into())
+    }
+}
+
+impl From<u32> for GaussianRational {
+    fn from(re: u32) -> Self {
+        Self::new(Rational::from(re), Rational::new())
+    }
+}
+
+impl ops::Add<&GaussianRational> for GaussianRational {
+    type Output = Self;
+    fn add(self, other: &Self) -> Self {
+        Self::new(self.re + &other.re, self.im + &other.im)
+    }
+}
+
+impl ops::Sub<&GaussianRational> for GaussianRational {
+    type Output = Self;
+    fn sub(self, other: &Self) -> Self {
+        Self::new(self.re - &other.re, self.im - &other.im)
+    }
+}
+
+impl ops::SubAssign<&GaussianRational> for GaussianRational {
+    fn sub_assign(&mut self, other: &Self) {
+        self.re -= &other.re;
+        self.im -= &other.im;
+    }
+}
+
+impl ops::Mul<&GaussianRational> for GaussianRational {
+    type Output = Self;
+    fn mul(self, other: &Self) -> Self {
+        let re = Rational::from(&self.re * &other.re) - Rational::from(&self.im * &other.im);
+        let im = Rational::from(&self.re * &other.im) + Rational::from(&self.im * &other.re);
+        Self::new(re, im)
+    }
+}
+
+impl ops::Div<&GaussianRational> for GaussianRational {
+    type Output = Self;
+    fn div(self, other: &Self) -> Self {
+        let norm = other.norm();
+        let product = self * &other.conj();
+        Self::new(product.re / &norm, product.im / &norm)
+    }
+}
+
+impl Sum for GaussianRational {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |acc, x| acc + &x)
+    }
+}
+
+impl PartialOrd for GaussianRational {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        if self.im == other.im {
+            self.re.partial_cmp(&other.re)
+        } else {
+            None
+        }
+    }
+}
+
+impl PartialEq<GaussianInteger> for GaussianRational {
+    fn eq(&self, other: &GaussianInteger) -> bool {
+        self.re == other.re && self.im == other.im
+    }
+}
+
+impl PartialOrd<GaussianInteger> for GaussianRational {
+    fn partial_cmp(&self, other: &GaussianInteger) -> Option<cmp::Ordering> {
+        if self.im == other.im {
+            self.re.partial_cmp(&other.re)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for GaussianRational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.im < 0 {
+            write!(f, "{}-{}i", self.re, Rational::from(-&self.im))
+        } else {
+            write!(f, "{}+{}i", self.re, self.im)
+        }
+    }
+}
+
+impl fmt::Debug for GaussianRational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl FromExt<&GaussianInteger> for GaussianRational {
+    fn from_ext(x: &GaussianInteger) -> Self {
+        Self::new(Rational::from(&x.re), Rational::from(&x.im))
+    }
+}
+
+impl FromExt<(GaussianInteger, GaussianInteger)> for GaussianRational {
+    fn from_ext((n, d): (GaussianInteger, GaussianInteger)) -> Self {
+        Self::from_ext(&n) / &Self::from_ext(&d)
+    }
+}
+
+impl FromExt<f64> for GaussianRational {
+    fn from_ext(f: f64) -> Self {
+        Self::new(Rational::from_ext(f), Rational::new())
+    }
+}
+
+impl FromExt<(i32, i32)> for GaussianRational {
+    fn from_ext(fraction: (i32, i32)) -> Self {
+        Self::new(Rational::from_ext(fraction), Rational::new())
+    }
+}
+
+/// `GaussianInteger` coefficients and `GaussianRational` Gram-Schmidt coefficients
+///
+/// The reductions over `Z[i]` are the generic ones with these scalars (see
+/// `reduce::lattice_reduce`). A Gram-Schmidt coefficient is size-reduced when both its
+/// real and imaginary parts are at most `eta` in absolute value, and the Lovasz condition
+/// uses the real squared magnitudes `|mu|²` and `||b*_k||²`.
+pub struct GaussianNum;
+impl Scalars for GaussianNum {
+    type Integer = GaussianInteger;
+    type Fraction = GaussianRational;
+
+    fn round(f: &Self::Fraction) -> Self::Integer {
+        f.round()
+    }
+
+    fn round_div(n: Self::Integer, d: Self::Integer) -> Self::Integer {
+        let norm = d.norm();
+        let product = n * &d.conj();
+        GaussianInteger::new(
+            BigNum::round_div(product.re, norm.clone()),
+            BigNum::round_div(product.im, norm),
+        )
+    }
+
+    /// Largest absolute value of the real and imaginary parts (a real value)
+    fn abs(f: Self::Fraction) -> Self::Fraction {
+        let (re, im) = (f.re.abs(), f.im.abs());
+        GaussianRational::new(cmp::max(re, im), Rational::new())
+    }
+
+    fn conj(f: Self::Fraction) -> Self::Fraction {
+        f.conj()
+    }
+
+    fn bit_length(n: &Self::Integer) -> u32 {
+        cmp::max(n.re.significant_bits(), n.im.significant_bits())
+    }
+
+    fn bound_denominator(f: &mut Self::Fraction, bits: u32) {
+        BigNum::bound_denominator(&mut f.re, bits);
+        BigNum::bound_denominator(&mut f.im, bits);
+    }
+}
+
+/// Lattice reduction over the Gaussian integers (complex L² algorithm)
+///
+/// Same as `reduce::lattice_reduce::<GaussianNum>`: the basis is size-reduced (both the
+/// real and imaginary parts of the Gram-Schmidt coefficients are at most eta in absolute
+/// value) and satisfies the Lovasz condition
+/// `||b*_k||² >= (delta - |mu_{k,k-1}|²) ||b*_{k-1}||²`, where the norms are the real
+/// squared magnitudes.
+///
+/// Arguments:
+///  * basis: A generating matrix for the `Z[i]`-module
+///  * eta: eta factor of the basis reduction
+///  * delta: delta factor of the basis reduction
+///
+/// The basis is reduced in-place. The zero vectors coming from linear dependencies
+/// are moved to the end of the basis, and their number is returned.
+///
+/// # Panics
+/// if delta < 1/4 or delta >= 1  
+/// if eta < 1/2 or eta >= sqrt(delta)
+pub fn lattice_reduce(basis: &mut Matrix<GaussianInteger>, eta: f64, delta: f64) -> usize {
+    crate::reduce::lattice_reduce::<GaussianNum>(basis, eta, delta)
+}
+
+impl GaussianVector {
+    /// Squared norm `<self, self>` (real)
+    pub fn norm_sq(&self) -> Integer {
+        self.dot(self).re
+    }
+}
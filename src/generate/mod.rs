@@ -0,0 +1,93 @@
+//! Generators of lattice bases for tests and benchmarks
+
+use crate::matrix::Matrix;
+
+use rug::{rand::RandState, Integer};
+
+/// Random square basis
+///
+/// The coefficients are drawn uniformly in `[0, 2^bits)` from a random generator seeded
+/// with `seed`, so that the same arguments always produce the same basis.
+///
+/// Arguments:
+///  * dimension: number of vectors (and dimension of the vectors)
+///  * bits: bit length of the coefficients
+///  * seed: seed of the random generator
+pub fn random_basis(dimension: usize, bits: usize, seed: u64) -> Matrix<Integer> {
+    let mut rand = RandState::new();
+    rand.seed(&Integer::from(seed));
+
+    let mut basis = Matrix::init(dimension, dimension);
+    for i in 0..dimension {
+        for j in 0..dimension {
+            basis[i][j] = Integer::from(Integer::random_bits(bits as u32, &mut rand));
+        }
+    }
+    basis
+}
+
+/// Lagarias-Odlyzko embedding of a subset-sum problem
+///
+/// For weights `w_0, ..., w_{n-1}` and a target `s`, the basis is made of the `n + 1` vectors
+///  * `b_i = (e_i, N * w_i)` for `i < n`
+///  * `b_n = (0, ..., 0, -N * s)`
+///
+/// where `e_i` is the i-th unit vector and the scaling factor is `N = n`. A solution
+/// `x` in `{0, 1}^n` of `sum x_i * w_i = s` gives the short lattice vector `(x, 0)`.
+///
+/// Arguments:
+///  * weights: weights of the subset-sum problem
+///  * target: target sum
+pub fn knapsack_lattice(weights: &[Integer], target: &Integer) -> Matrix<Integer> {
+    let n = weights.len();
+    let scale = Integer::from(n);
+
+    let mut basis = Matrix::init(n + 1, n + 1);
+    for (i, weight) in weights.iter().enumerate() {
+        basis[i][i] = Integer::from(1);
+        basis[i][n] = Integer::from(weight * &scale);
+    }
+    basis[n][n] = -Integer::from(target * &scale);
+    basis
+}
+
+/// Lattice of the Darmstadt SVP challenge
+///
+/// The challenge lattices are the Goldstein-Mayer lattices of a random prime: a prime `p`
+/// of `bit` bits and random integers `x_1, ..., x_{n-1}` in `[0, p)` are drawn, and the
+/// basis is made of the `n` vectors
+///  * `b_0 = (p, 0, ..., 0)`
+///  * `b_i = (x_i, e_i)` for `0 < i < n`
+///
+/// where `e_i` is the i-th unit vector of dimension `n - 1`, so that the basis is lower
+/// triangular. The challenge uses `bit = 10 * n`. The random generator is seeded with
+/// `seed`, so that the same arguments always produce the same basis, but it is not the
+/// generator of the published instances: the construction is the same, the numbers differ.
+///
+/// Arguments:
+///  * dimension: number of vectors (and dimension of the vectors)
+///  * seed: seed of the random generator
+///  * bit: bit length of the prime
+///
+/// # Panics
+/// if bit < 2
+pub fn svp_challenge(dimension: usize, seed: u64, bit: u32) -> Matrix<Integer> {
+    assert!(bit >= 2);
+
+    let mut rand = RandState::new();
+    rand.seed(&Integer::from(seed));
+
+    let mut basis = Matrix::init(dimension, dimension);
+    if dimension == 0 {
+        return basis;
+    }
+
+    let lowest = Integer::from(1) << (bit - 1);
+    let prime = (Integer::from(Integer::random_bits(bit - 1, &mut rand)) + lowest).next_prime();
+    for i in 1..dimension {
+        basis[i][0] = Integer::from(prime.random_below_ref(&mut rand));
+        basis[i][i] = Integer::from(1);
+    }
+    basis[0][0] = prime;
+    basis
+}
@@ -0,0 +1,194 @@
+//! Lattice reduction from the Gram matrix of a basis
+
+use crate::l2;
+use crate::matrix::Matrix;
+use crate::scalars::BigNum;
+
+use rug::{Integer, Rational};
+
+use std::cmp::max;
+
+/// Lattice reduction (L² algorithm) of a Gram matrix
+///
+/// Performs the same operations as `bigl2::lattice_reduce` on the basis `B` such that
+/// `gram = B B^T`, without knowing `B`: the Gram matrix is updated as `U gram U^T`,
+/// where `U` is the unimodular transformation of the reduction.
+///
+/// Arguments:
+///  * gram: the full (symmetric) Gram matrix of the lattice
+///  * eta: eta factor of the basis reduction
+///  * delta: delta factor of the basis reduction
+///
+/// The Gram matrix is reduced in-place, and the transformation `U` is returned: the
+/// reduced basis is made of the vectors `sum_j U[i][j] * b_j`.
+///
+/// The Gram matrix can be any positive semidefinite quadratic form `x -> x^T gram x`,
+/// there is no need for the basis `B` to exist with integer coefficients. The linear
+/// dependencies (for a semidefinite form) are moved to the end, as zero rows and columns.
+///
+/// # Panics
+/// if delta < 1/4 or delta >= 1  
+/// if eta < 1/2 or eta >= sqrt(delta)  
+/// if gram is not a symmetric matrix, or is not positive semidefinite
+pub fn lattice_reduce_gram(gram: &mut Matrix<Integer>, eta: f64, delta: f64) -> Matrix<Integer> {
+    l2::check_parameters(eta, delta);
+
+    let (n, cols) = gram.dimensions();
+    assert_eq!(n, cols, "the Gram matrix must be square");
+    for i in 0..n {
+        assert!(
+            gram[i][i] >= 0,
+            "the Gram matrix is not positive semidefinite"
+        );
+        for j in 0..i {
+            assert_eq!(gram[i][j], gram[j][i], "the Gram matrix must be symmetric");
+        }
+    }
+    let mut transform: Matrix<Integer> = Matrix::init(n, n);
+    for i in 0..n {
+        transform[i][i] = Integer::from(1);
+    }
+    let mut r: Matrix<Rational> = Matrix::init(n, n); // r_ij matrix
+    let mut mu: Matrix<Rational> = Matrix::init(n, n); // Gram coefficient matrix
+
+    // Moving the zero vectors to the end of the basis
+    let mut d = n;
+    let mut i = 0;
+    while i < d {
+        if gram[i][i] == 0 {
+            move_to_end(gram, &mut transform, i, d);
+            d -= 1;
+        } else {
+            i += 1;
+        }
+    }
+    if d == 0 {
+        return transform;
+    }
+
+    let eta_minus = l2::eta_bar(eta);
+    let delta_plus = l2::delta_bar(delta);
+
+    r[0][0] = Rational::from(&gram[0][0]);
+
+    let mut k = 1;
+
+    while k < d {
+        size_reduce(k, gram, &mut transform, &mut mu, &mut r, &eta_minus);
+        assert!(r[k][k] >= 0, "the Gram matrix is not positive semidefinite");
+
+        // Linear dependency: the size-reduced vector is zero
+        if gram[k][k] == 0 {
+            move_to_end(gram, &mut transform, k, d);
+            d -= 1;
+            continue;
+        }
+
+        // Lovazs condition
+        if l2::lovasz_ok(&r[k - 1][k - 1], &r[k][k], &mu[k][k - 1], &delta_plus) {
+            k += 1;
+        } else {
+            swap(gram, &mut transform, k, k - 1);
+
+            // Updating mu and r
+            for i in 0..=k {
+                update_gso_row(i, gram, &mut mu, &mut r);
+            }
+
+            k = max(1, k - 1);
+        }
+    }
+
+    transform
+}
+
+/// Gram matrix of a set of floating-point vectors
+///
+/// Returns the symmetric matrix of the dot products `<vectors[i], vectors[j]>`, computed
+/// as in the L² reduction (in parallel with the `rayon` feature).
+pub fn gram_matrix_f(vectors: &Matrix<f64>) -> Matrix<f64> {
+    let (n, _) = vectors.dimensions();
+    let mut gram = l2::gram_matrix(vectors, n);
+    for i in 0..n {
+        for j in (i + 1)..n {
+            gram[i][j] = gram[j][i];
+        }
+    }
+    gram
+}
+
+/// Recompute the row `k` of `mu` and `r` from the Gram matrix
+///
+/// For `i <= k`, sets `r[k][i] = gram[k][i] - sum_{j < i} mu[i][j] * r[k][j]` and
+/// `mu[k][i] = r[k][i] / r[i][i]` (zero when `r[i][i]` is zero). The rows `0..k` of `mu`
+/// and `r` must be up to date: calling it for `k = 0, 1, ...` computes the whole
+/// Gram-Schmidt orthogonalisation of the basis.
+pub(crate) fn update_gso_row(
+    k: usize,
+    gram: &Matrix<Integer>,
+    mu: &mut Matrix<Rational>,
+    r: &mut Matrix<Rational>,
+) {
+    l2::update_gso_from_gram::<BigNum>(k, gram, mu, r, None);
+}
+
+/// Performs the `eta`-size-reduction of the k-th vector
+fn size_reduce(
+    k: usize,
+    gram: &mut Matrix<Integer>,
+    transform: &mut Matrix<Integer>,
+    mu: &mut Matrix<Rational>,
+    r: &mut Matrix<Rational>,
+    eta: &Rational,
+) {
+    let (n, _) = gram.dimensions();
+
+    loop {
+        update_gso_row(k, gram, mu, r);
+
+        if !(0..k).any(|index| Rational::from(mu[k][index].abs_ref()) > *eta) {
+            break;
+        }
+
+        for i in (0..k).rev() {
+            let x: Integer = mu[k][i].round_ref().into();
+            transform.sub_scaled(k, i, &x);
+
+            // Updating Gram matrix: b_k <- b_k - x * b_i
+            let norm = &gram[k][k] - Integer::from(&gram[k][i] * &x) * 2u32
+                + Integer::from(&gram[i][i] * &x) * &x;
+            for j in 0..n {
+                if j != k {
+                    let minus = Integer::from(&gram[i][j] * &x);
+                    gram[k][j] -= minus;
+                    gram[j][k] = gram[k][j].clone();
+                }
+            }
+            gram[k][k] = norm;
+
+            for j in 0..i {
+                let minus = Rational::from(&x) * &mu[i][j];
+                mu[k][j] -= &minus;
+            }
+        }
+    }
+}
+
+/// Swap the vectors `i` and `j` of the basis
+fn swap(gram: &mut Matrix<Integer>, transform: &mut Matrix<Integer>, i: usize, j: usize) {
+    let (n, _) = gram.dimensions();
+    gram.swap(i, j);
+    for row in 0..n {
+        let tmp = gram[row][i].clone();
+        gram[row][i] = gram[row][j].clone();
+        gram[row][j] = tmp;
+    }
+    transform.swap(i, j);
+}
+
+/// Move the k-th vector to position `d - 1`
+fn move_to_end(gram: &mut Matrix<Integer>, transform: &mut Matrix<Integer>, k: usize, d: usize) {
+    for i in k..(d - 1) {
+        swap(gram, transform, i, i + 1);
+    }
+}
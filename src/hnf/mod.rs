@@ -0,0 +1,92 @@
+//! Hermite Normal Form of integer matrices
+
+use crate::matrix::Matrix;
+use crate::vector::BigVector;
+
+use rug::{ops::DivRounding, Integer};
+
+/// Hermite Normal Form (row-style)
+///
+/// The vectors of `matrix` are considered as the rows of an integer matrix. The output
+/// generates the same lattice and is in echelon form:
+///  * the first nonzero coefficient (the pivot) of each row is positive,
+///    and strictly to the right of the pivot of the previous row,
+///  * the coefficients above each pivot are nonnegative and smaller than the pivot.
+///
+/// Zero rows are dropped, so the output has as many rows as the rank of `matrix`.
+///
+/// Arguments:
+///  * matrix: A generating matrix for the lattice (it can be non-square and rank-deficient)
+pub fn hermite_normal_form(matrix: &Matrix<Integer>) -> Matrix<Integer> {
+    hermite_normal_form_with_transform(matrix).0
+}
+
+/// Hermite Normal Form (row-style) with the unimodular transformation
+///
+/// Returns `(H, U)` where `H` is `hermite_normal_form(matrix)`, made of `r` rows (the rank
+/// of `matrix`), and `U` is a unimodular matrix such that the first `r` rows of
+/// `U * matrix` are the rows of `H` and its other rows are zero (the last rows of `U`
+/// generate the left kernel of `matrix`).
+///
+/// Arguments:
+///  * matrix: A generating matrix for the lattice (it can be non-square and rank-deficient)
+pub fn hermite_normal_form_with_transform(
+    matrix: &Matrix<Integer>,
+) -> (Matrix<Integer>, Matrix<Integer>) {
+    let (m, n) = matrix.dimensions();
+    let mut rows: Vec<BigVector> = (0..m).map(|i| matrix[i].clone()).collect();
+    let identity: Matrix<Integer> = Matrix::identity(m);
+    let mut transform: Vec<BigVector> = (0..m).map(|i| identity[i].clone()).collect();
+    let mut rank = 0;
+
+    for col in 0..n {
+        if rank == m {
+            break;
+        }
+
+        // Euclid's algorithm on the column, until a single nonzero entry remains
+        loop {
+            let pivot = (rank..m)
+                .filter(|&k| rows[k][col] != 0)
+                .min_by(|&a, &b| rows[a][col].cmp_abs(&rows[b][col]));
+            let pivot = match pivot {
+                Some(pivot) => pivot,
+                None => break,
+            };
+            rows.swap(rank, pivot);
+            transform.swap(rank, pivot);
+
+            let mut done = true;
+            for k in (rank + 1)..m {
+                if rows[k][col] != 0 {
+                    let q = Integer::from(&rows[k][col] / &rows[rank][col]);
+                    rows[k] = rows[k].sub(&rows[rank].mulf(&q));
+                    transform[k] = transform[k].sub(&transform[rank].mulf(&q));
+                    done &= rows[k][col] == 0;
+                }
+            }
+            if done {
+                break;
+            }
+        }
+
+        if rank == m || rows[rank][col] == 0 {
+            continue;
+        }
+        if rows[rank][col] < 0 {
+            rows[rank] = rows[rank].mulf(&Integer::from(-1));
+            transform[rank] = transform[rank].mulf(&Integer::from(-1));
+        }
+
+        // Reduce the coefficients above the pivot
+        for k in 0..rank {
+            let q = Integer::from((&rows[k][col]).div_euc(&rows[rank][col]));
+            rows[k] = rows[k].sub(&rows[rank].mulf(&q));
+            transform[k] = transform[k].sub(&transform[rank].mulf(&q));
+        }
+        rank += 1;
+    }
+
+    rows.truncate(rank);
+    (Matrix::from_columns(rows), Matrix::from_columns(transform))
+}
@@ -0,0 +1,95 @@
+//! Import and export of bases
+//!
+//! The bases are written one vector per line.
+
+use crate::matrix::Matrix;
+use crate::vector::BigVector;
+
+use rug::Integer;
+
+use std::fmt;
+
+/// Errors of `parse_csv`
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseCsvError {
+    /// A field is not an integer (line and field indices start at 1)
+    InvalidInteger { line: usize, field: usize },
+
+    /// A line does not have the same number of fields as the first one
+    DimensionMismatch { line: usize },
+}
+
+impl fmt::Display for ParseCsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseCsvError::InvalidInteger { line, field } => {
+                write!(f, "invalid integer at line {}, field {}", line, field)
+            }
+            ParseCsvError::DimensionMismatch { line } => {
+                write!(f, "wrong number of fields at line {}", line)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseCsvError {}
+
+/// Format a basis as CSV: one vector per line, comma-separated integers
+pub fn to_csv(basis: &Matrix<Integer>) -> String {
+    let (d, _) = basis.dimensions();
+    (0..d)
+        .map(|i| {
+            let fields: Vec<String> = basis[i].iter().map(Integer::to_string).collect();
+            fields.join(",") + "\n"
+        })
+        .collect()
+}
+
+/// Parse a basis from CSV (the format of `to_csv`)
+///
+/// Spaces around the fields and empty lines are ignored.
+pub fn parse_csv(csv: &str) -> Result<Matrix<Integer>, ParseCsvError> {
+    let mut vectors: Vec<BigVector> = Vec::new();
+    for (index, line) in csv.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let vector = line
+            .split(',')
+            .enumerate()
+            .map(|(field, entry)| {
+                entry
+                    .trim()
+                    .parse::<Integer>()
+                    .map_err(|_| ParseCsvError::InvalidInteger {
+                        line: index + 1,
+                        field: field + 1,
+                    })
+            })
+            .collect::<Result<BigVector, _>>()?;
+        if vectors
+            .first()
+            .is_some_and(|first| first.dimension() != vector.dimension())
+        {
+            return Err(ParseCsvError::DimensionMismatch { line: index + 1 });
+        }
+        vectors.push(vector);
+    }
+
+    Ok(Matrix::from_columns(vectors))
+}
+
+/// Format a basis as a LaTeX matrix, one vector per row
+pub fn to_latex(basis: &Matrix<Integer>) -> String {
+    let (d, _) = basis.dimensions();
+    let rows: Vec<String> = (0..d)
+        .map(|i| {
+            let fields: Vec<String> = basis[i].iter().map(Integer::to_string).collect();
+            fields.join(" & ")
+        })
+        .collect();
+    format!(
+        "\\begin{{pmatrix}}\n{}\n\\end{{pmatrix}}",
+        rows.join(" \\\\\n")
+    )
+}
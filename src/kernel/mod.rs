@@ -0,0 +1,60 @@
+//! Integer kernel (nullspace) of integer matrices
+
+use crate::hnf;
+use crate::l2::bigl2;
+use crate::matrix::Matrix;
+use crate::vector::BigVector;
+
+use rug::Integer;
+
+/// Integer kernel of a matrix
+///
+/// The vectors of `matrix` are considered as the rows of an integer matrix `A` with `n`
+/// columns. This function returns a basis of the lattice of vectors `x` in `Z^n` such
+/// that `A x = 0`.
+///
+/// The kernel is obtained by reducing the lattice generated by the vectors
+/// `(e_j, K * a_j)`, where `e_j` is the j-th unit vector and `a_j` the j-th column of `A`.
+/// When the scaling factor `K` is large enough, the reduced vectors whose last coordinates
+/// vanish generate the kernel. `K` starts at `2^n` times the largest coefficient of `A`
+/// and is squared until the expected number `n - rank(A)` of kernel vectors is found.
+///
+/// Arguments:
+///  * matrix: the integer matrix
+///
+/// Returns an empty matrix (with no vectors) when the kernel is trivial.
+pub fn integer_kernel(matrix: &Matrix<Integer>) -> Matrix<Integer> {
+    let (m, n) = matrix.dimensions();
+    let rank = hnf::hermite_normal_form(matrix).dimensions().0;
+    let nullity = n - rank;
+    if nullity == 0 {
+        return Matrix::init(0, n);
+    }
+
+    let largest = (0..m)
+        .map(|i| matrix[i].norm_linf())
+        .max()
+        .unwrap_or_default();
+    let mut scale: Integer = (largest + 1) << n as u32;
+
+    loop {
+        let mut basis: Matrix<Integer> = Matrix::init(n, n + m);
+        for j in 0..n {
+            basis[j][j] = Integer::from(1);
+            for i in 0..m {
+                basis[j][n + i] = Integer::from(&matrix[i][j] * &scale);
+            }
+        }
+        bigl2::lattice_reduce(&mut basis, 0.51, 0.99);
+
+        let kernel: Vec<BigVector> = (0..n)
+            .filter(|&j| (n..n + m).all(|i| basis[j][i] == 0))
+            .map(|j| BigVector::from_vector((0..n).map(|i| basis[j][i].clone()).collect()))
+            .collect();
+        if kernel.len() == nullity {
+            return Matrix::from_columns(kernel);
+        }
+
+        scale.square_mut();
+    }
+}
@@ -1,8 +1,491 @@
-use crate::matrix::Matrix;
+use crate::error::LatticeError;
+use crate::matrix::{Matrix, RowOp};
 use crate::scalars::{Scalars, FromExt};
 use crate::vector::{Dot, Vector, Coefficient};
 
-use std::cmp::max;
+use std::{cmp::max, fmt, ops, ops::ControlFlow, time};
+
+/// Parameters of the L² reduction
+///
+/// The parameters are set with a builder:
+///
+/// ```rust
+/// use lll_rs::l2::ReductionParams;
+///
+/// let params = ReductionParams::default().eta(0.501).delta(0.998);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReductionParams {
+    /// eta factor of the basis reduction
+    eta: f64,
+
+    /// delta factor of the basis reduction
+    delta: f64,
+
+    /// Limits on the iterations of the main loop and the wall time of the reduction
+    budget: ReductionBudget,
+
+    /// Bound `2^denominator_bits` on the denominators of the Gram-Schmidt coefficients
+    denominator_bits: Option<u32>,
+
+    /// Record the log potential of the basis in the statistics
+    track_potential: bool,
+
+    /// Sort the reduced vectors by increasing norm
+    sort_by_norm: bool,
+
+    /// Maximum number of passes of a size reduction
+    max_size_reduction_passes: Option<usize>,
+}
+
+impl Default for ReductionParams {
+    /// Classic parameters `eta = 0.51` and `delta = 0.99`, without iteration limit
+    fn default() -> Self {
+        Self {
+            eta: 0.51,
+            delta: 0.99,
+            budget: ReductionBudget::default(),
+            denominator_bits: None,
+            track_potential: false,
+            sort_by_norm: false,
+            max_size_reduction_passes: None,
+        }
+    }
+}
+
+impl ReductionParams {
+    /// Set the eta factor of the basis reduction
+    pub fn eta(mut self, eta: f64) -> Self {
+        self.eta = eta;
+        self
+    }
+
+    /// Set the delta factor of the basis reduction
+    pub fn delta(mut self, delta: f64) -> Self {
+        self.delta = delta;
+        self
+    }
+
+    /// Set the maximum number of iterations of the main loop of the reduction
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.budget.max_iterations = Some(max_iterations);
+        self
+    }
+
+    /// Set the limits on the iterations of the main loop and the wall time of the reduction
+    ///
+    /// Replaces the previous limits, including a maximum number of iterations set with
+    /// `max_iterations`. The time is checked after each iteration of the main loop, so a
+    /// single iteration can overrun it.
+    pub fn budget(mut self, budget: ReductionBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Bound the denominators of the exact Gram-Schmidt coefficients
+    ///
+    /// With exact arithmetic (`bigl2`), the `mu` coefficients are always kept in canonical
+    /// form, but their denominators can still grow much larger than needed over the updates
+    /// of the reduction. With this option, each coefficient whose denominator is larger than
+    /// `2^bits` is rounded to the nearest multiple of `2^-bits`. This trades exactness for
+    /// speed: the output is still a basis of the same lattice, but the LLL conditions are
+    /// only checked up to this precision. It has no effect with floating-point arithmetic.
+    pub fn bound_denominators(mut self, bits: u32) -> Self {
+        self.denominator_bits = Some(bits);
+        self
+    }
+
+    /// Record the log potential of the basis before and after the reduction
+    ///
+    /// The potentials (see `metrics::log_potential`) are stored in the `ReductionStats` of
+    /// `bigl2::reduce_owned`. Computing them costs two exact Gram-Schmidt
+    /// orthogonalisations.
+    pub fn track_potential(mut self) -> Self {
+        self.track_potential = true;
+        self
+    }
+
+    /// Sort the vectors of the reduced basis by increasing norm
+    ///
+    /// The L² algorithm does not sort the basis: `basis[0]` is short (within the LLL
+    /// approximation factor of the shortest vector), but it is not always the shortest
+    /// vector of the reduced basis. With this option, the nonzero vectors are sorted by
+    /// increasing norm after the reduction (a stable sort, the zero vectors stay at the
+    /// end), and the transformation is permuted accordingly. The sorted basis is in general
+    /// no longer LLL-reduced. Defaults to `false`.
+    pub fn sort_by_norm(mut self, sort_by_norm: bool) -> Self {
+        self.sort_by_norm = sort_by_norm;
+        self
+    }
+
+    /// Set the maximum number of passes of the size reduction of a vector
+    ///
+    /// With floating-point arithmetic, the size reduction of a vector is repeated until its
+    /// Gram-Schmidt coefficients are small enough, which never happens when the precision
+    /// is too low. The reduction then stops with `ReductionError::Precision`.
+    pub(crate) fn max_size_reduction_passes(mut self, passes: usize) -> Self {
+        self.max_size_reduction_passes = Some(passes);
+        self
+    }
+}
+
+/// Limits on the work of a reduction
+///
+/// For the L² reduction, the iterations are the iterations of the main loop, and for the
+/// BKZ reduction they are the tours. The limits are unbounded by default.
+///
+/// ```rust
+/// use lll_rs::l2::ReductionBudget;
+/// use std::time::Duration;
+///
+/// let budget = ReductionBudget {
+///     max_duration: Some(Duration::from_secs(10)),
+///     ..ReductionBudget::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ReductionBudget {
+    /// Maximum number of iterations
+    pub max_iterations: Option<usize>,
+
+    /// Maximum wall time
+    pub max_duration: Option<time::Duration>,
+}
+
+/// Errors of the L² reduction
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReductionError {
+    /// The maximum number of iterations was reached before the basis was reduced
+    IterationLimit,
+
+    /// The maximum wall time was reached before the basis was reduced
+    TimeLimit,
+
+    /// An intermediate value overflowed the fixed-size integer types
+    Overflow,
+
+    /// The floating-point precision is too low: a Gram-Schmidt coefficient is not finite,
+    /// or the size reduction of a vector does not converge
+    Precision,
+
+    /// The progress callback stopped the reduction
+    Cancelled,
+}
+
+impl fmt::Display for ReductionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReductionError::IterationLimit => write!(f, "iteration limit reached"),
+            ReductionError::TimeLimit => write!(f, "time limit reached"),
+            ReductionError::Overflow => write!(f, "integer overflow"),
+            ReductionError::Precision => write!(f, "insufficient floating-point precision"),
+            ReductionError::Cancelled => write!(f, "reduction cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for ReductionError {}
+
+/// Statistics of a reduction
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReductionStats {
+    /// Number of iterations of the main loop
+    pub iterations: usize,
+
+    /// Number of swaps of consecutive vectors (Lovasz condition failures)
+    pub swaps: usize,
+
+    /// Number of size-reduction steps (subtractions of a nonzero multiple of a vector)
+    pub size_reductions: usize,
+
+    /// Largest bit length of the coefficients of the basis during the reduction
+    pub max_bits: u32,
+
+    /// Wall time of the reduction
+    pub duration: time::Duration,
+
+    /// Log potential of the input basis (when tracked, see `ReductionParams`)
+    pub initial_log_potential: Option<f64>,
+
+    /// Log potential of the reduced basis (when tracked, see `ReductionParams`)
+    pub final_log_potential: Option<f64>,
+}
+
+/// Progress of a reduction, passed to the progress callbacks
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReductionProgress {
+    /// Number of iterations of the main loop so far
+    pub iterations: usize,
+
+    /// Number of swaps of consecutive vectors so far
+    pub swaps: usize,
+
+    /// Index of the next vector to reduce: the vectors before it are reduced
+    pub index: usize,
+
+    /// Number of nonzero vectors (the zero vectors found so far are moved to the end)
+    pub rank: usize,
+
+    /// Wall time since the start of the reduction
+    pub elapsed: time::Duration,
+}
+
+/// Progress callback of a reduction: the reduction is cancelled when it returns
+/// `ControlFlow::Break`
+pub type ProgressCallback<'a> = dyn FnMut(&ReductionProgress) -> ControlFlow<()> + 'a;
+
+/// Output of a reduction
+#[derive(Clone, Debug)]
+pub struct ReductionResult<T> {
+    /// The reduced basis
+    pub basis: Matrix<T>,
+
+    /// The unimodular transformation `U` such that `basis = U * input`
+    pub transform: Matrix<T>,
+
+    /// Statistics of the reduction
+    pub stats: ReductionStats,
+}
+
+/// Lattice reduction (L² algorithm) with a set of parameters
+///
+/// Stops with `ReductionError::IterationLimit` when the maximum number of iterations
+/// is reached, leaving a partially reduced basis that generates the same lattice.
+fn lattice_reduce_with<S>(
+    basis: &mut Matrix<S::Integer>,
+    params: &ReductionParams,
+) -> Result<usize, ReductionError>
+where
+    S: Scalars,
+    S::Integer: Coefficient + Send + Sync,
+    S::Fraction: Coefficient,
+    Vector<S::Integer>: Dot<Output = S::Integer>,
+    Vector<S::Fraction>: Dot<Output = S::Fraction>,
+{
+    lattice_reduce_stats::<S>(basis, params, None).map(|(zeros, _)| zeros)
+}
+
+/// Lattice reduction (L² algorithm) with a set of parameters, counting iterations and swaps
+///
+/// When `transform` is given, the operations are also recorded by it (see
+/// `lattice_reduce_gso`). Returns the number of zero vectors and the statistics, or stops
+/// with `ReductionError::IterationLimit` or `ReductionError::TimeLimit` when a limit of
+/// `params` is reached.
+pub(crate) fn lattice_reduce_stats<S>(
+    basis: &mut Matrix<S::Integer>,
+    params: &ReductionParams,
+    transform: Option<&mut dyn RowTracker<S::Integer>>,
+) -> Result<(usize, ReductionStats), ReductionError>
+where
+    S: Scalars,
+    S::Integer: Coefficient + Send + Sync,
+    S::Fraction: Coefficient,
+    Vector<S::Integer>: Dot<Output = S::Integer>,
+    Vector<S::Fraction>: Dot<Output = S::Fraction>,
+{
+    lattice_reduce_progress::<S>(basis, params, transform, None).map_err(|(error, _)| error)
+}
+
+/// Lattice reduction (L² algorithm) with statistics and a progress callback
+///
+/// Same as `lattice_reduce_stats`, but `progress` is called after each iteration of the
+/// main loop that does not finish the reduction, before the limits of `params` are
+/// checked. The reduction stops with `ReductionError::Cancelled` as soon as it returns
+/// `ControlFlow::Break`, leaving a partially reduced basis that generates the same
+/// lattice. The errors come with the progress of the reduction when it stopped.
+pub(crate) fn lattice_reduce_progress<S>(
+    basis: &mut Matrix<S::Integer>,
+    params: &ReductionParams,
+    mut transform: Option<&mut dyn RowTracker<S::Integer>>,
+    mut progress: Option<&mut ProgressCallback>,
+) -> Result<(usize, ReductionStats), (ReductionError, ReductionProgress)>
+where
+    S: Scalars,
+    S::Integer: Coefficient + Send + Sync,
+    S::Fraction: Coefficient,
+    Vector<S::Integer>: Dot<Output = S::Integer>,
+    Vector<S::Fraction>: Dot<Output = S::Fraction>,
+{
+    let start = time::Instant::now();
+    let (n, _) = basis.dimensions();
+    let mut stats = ReductionStats {
+        max_bits: max_bit_length::<S>(basis, 0..n),
+        ..ReductionStats::default()
+    };
+
+    let mut state = Reduction::<S>::new(basis, params, None, transform.as_deref_mut());
+    while !state.is_done() {
+        let k = state.k;
+        if !state.step(basis, transform.as_deref_mut()) {
+            let current = state.progress(stats.iterations, start);
+            return Err((ReductionError::Precision, current));
+        }
+        stats.iterations += 1;
+        // A step only modifies the k-th vector (size reduction), and then either swaps it
+        // with the (k-1)-th vector, or moves it to the end when it is zero (the vectors
+        // after it are shifted, but unchanged). So the only new coefficients are at index
+        // k - 1 or k, and the rest of the basis never needs to be rescanned.
+        stats.max_bits = max(stats.max_bits, max_bit_length::<S>(basis, k - 1..k + 1));
+        if state.is_done() {
+            break;
+        }
+
+        let current = state.progress(stats.iterations, start);
+        if let Some(progress) = progress.as_deref_mut() {
+            if progress(&current).is_break() {
+                return Err((ReductionError::Cancelled, current));
+            }
+        }
+        let budget = &params.budget;
+        if budget
+            .max_iterations
+            .is_some_and(|max| current.iterations >= max)
+        {
+            return Err((ReductionError::IterationLimit, current));
+        }
+        if budget
+            .max_duration
+            .is_some_and(|max| current.elapsed >= max)
+        {
+            return Err((ReductionError::TimeLimit, current));
+        }
+    }
+    let zeros = state.finish();
+    stats.swaps = state.swaps;
+    stats.size_reductions = state.size_reductions;
+
+    if params.sort_by_norm {
+        sort_by_norm::<S>(basis, n - zeros, transform);
+    }
+    stats.duration = start.elapsed();
+    Ok((zeros, stats))
+}
+
+/// Lattice reduction (L² algorithm) with a `(k, d)` callback
+///
+/// Same as `lattice_reduce_progress` with the factors `eta` and `delta`, but
+/// `callback(k, d)` is called after each iteration of the main loop, including the last
+/// one (with `k == d`), where `k` is the current index and `d` the number of nonzero
+/// vectors. Stops with `ReductionError::Cancelled` as soon as it returns `false`.
+pub(crate) fn lattice_reduce_callback<S>(
+    basis: &mut Matrix<S::Integer>,
+    eta: f64,
+    delta: f64,
+    callback: &mut dyn FnMut(usize, usize) -> bool,
+) -> Result<usize, ReductionError>
+where
+    S: Scalars,
+    S::Integer: Coefficient + Send + Sync,
+    S::Fraction: Coefficient,
+    Vector<S::Integer>: Dot<Output = S::Integer>,
+    Vector<S::Fraction>: Dot<Output = S::Fraction>,
+{
+    let params = ReductionParams::default().eta(eta).delta(delta);
+    let mut progress = |progress: &ReductionProgress| {
+        if callback(progress.index, progress.rank) {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    };
+    let (n, _) = basis.dimensions();
+    let (zeros, stats) = lattice_reduce_progress::<S>(basis, &params, None, Some(&mut progress))
+        .map_err(|(error, _)| error)?;
+    // The iteration that finishes the reduction is not reported to `progress`
+    if stats.iterations > 0 && !callback(n - zeros, n - zeros) {
+        return Err(ReductionError::Cancelled);
+    }
+    Ok(zeros)
+}
+
+/// Largest bit length of the coefficients of the vectors `basis[rows]`
+fn max_bit_length<S>(basis: &Matrix<S::Integer>, rows: ops::Range<usize>) -> u32
+where
+    S: Scalars,
+{
+    rows.flat_map(|i| basis[i].iter())
+        .map(S::bit_length)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Sort the first `d` vectors of `basis` by increasing norm (stable insertion sort)
+///
+/// The swaps are also recorded by `transform`.
+fn sort_by_norm<S>(
+    basis: &mut Matrix<S::Integer>,
+    d: usize,
+    mut transform: Option<&mut dyn RowTracker<S::Integer>>,
+) where
+    S: Scalars,
+    S::Integer: Coefficient,
+    Vector<S::Integer>: Dot<Output = S::Integer>,
+{
+    let mut norms: Vec<S::Fraction> = (0..d)
+        .map(|i| S::Fraction::from_ext(&basis[i].dot(&basis[i])))
+        .collect();
+    for i in 1..d {
+        let mut j = i;
+        while j > 0 && norms[j] < norms[j - 1] {
+            norms.swap(j, j - 1);
+            basis.swap(j, j - 1);
+            if let Some(transform) = transform.as_deref_mut() {
+                transform.swap_rows(j, j - 1);
+            }
+            j -= 1;
+        }
+    }
+}
+
+/// Lattice reduction (L² algorithm) of an owned basis with a set of parameters
+///
+/// Stops with `ReductionError::IterationLimit` when the maximum number of iterations
+/// is reached.
+fn reduce_owned<S>(
+    mut basis: Matrix<S::Integer>,
+    params: &ReductionParams,
+) -> Result<ReductionResult<S::Integer>, ReductionError>
+where
+    S: Scalars,
+    S::Integer: Coefficient + Send + Sync,
+    S::Fraction: Coefficient,
+    Vector<S::Integer>: Dot<Output = S::Integer>,
+    Vector<S::Fraction>: Dot<Output = S::Fraction>,
+{
+    let (n, _) = basis.dimensions();
+    let mut transform = Matrix::identity(n);
+    let (_, stats) = lattice_reduce_stats::<S>(&mut basis, params, Some(&mut transform))?;
+
+    Ok(ReductionResult {
+        basis,
+        transform,
+        stats,
+    })
+}
+
+/// Lattice reduction (L² algorithm) returning the unimodular transformation
+///
+/// Same as `lattice_reduce`, the transformation `U` such that the reduced basis is
+/// `U * basis` is returned with the number of zero vectors.
+fn lattice_reduce_transform<S>(
+    basis: &mut Matrix<S::Integer>,
+    eta: f64,
+    delta: f64,
+) -> (usize, Matrix<S::Integer>)
+where
+    S: Scalars,
+    S::Integer: Coefficient + Send + Sync,
+    S::Fraction: Coefficient,
+    Vector<S::Integer>: Dot<Output = S::Integer>,
+    Vector<S::Fraction>: Dot<Output = S::Fraction>,
+{
+    let (n, _) = basis.dimensions();
+    let mut transform = Matrix::identity(n);
+    let params = ReductionParams::default().eta(eta).delta(delta);
+    let (zeros, _) = lattice_reduce_stats::<S>(basis, &params, Some(&mut transform))
+        .unwrap_or_else(|e| panic!("{}", e));
+    (zeros, transform)
+}
 
 /// Lattice reduction (L² algorithm)
 ///
@@ -13,181 +496,2212 @@ use std::cmp::max;
 ///  * eta: eta factor of the basis reduction
 ///  * delta: delta factor of the basis reduction
 ///
-/// The basis is reduced in-place.
+/// The basis is reduced in-place. The vectors of `basis` may be linearly dependent:
+/// the zero vectors obtained during the reduction are moved to the end of the basis,
+/// and their number is returned (MLLL).
 ///
 /// # Panics
-/// if delta <= 1/4 or delta >= 1  
-/// if eta <= 1/2 or eta > sqrt(delta)
-pub(crate) fn lattice_reduce<S>(basis: &mut Matrix<S::Integer>, eta: f64, delta: f64)
+/// if delta < 1/4 or delta >= 1  
+/// if eta < 1/2 or eta >= sqrt(delta)  
+/// if the floating-point Gram-Schmidt coefficients cannot be rounded (see
+/// `ReductionError::Precision`), which cannot happen with exact arithmetic
+pub(crate) fn lattice_reduce<S>(basis: &mut Matrix<S::Integer>, eta: f64, delta: f64) -> usize
 where
     S: Scalars,
-    S::Integer: Coefficient,
+    S::Integer: Coefficient + Send + Sync,
+    S::Fraction: Coefficient,
+    Vector<S::Integer>: Dot<Output = S::Integer>,
+    Vector<S::Fraction>: Dot<Output = S::Fraction>,
+{
+    lattice_reduce_with_hook::<S, _>(basis, eta, delta, |_, _, _| true)
+        .unwrap_or_else(|| panic!("{}", ReductionError::Precision))
+}
+
+/// Lattice reduction (L² algorithm) with a hook
+///
+/// Same as `lattice_reduce`, but `hook(basis, k, d)` is called after each iteration of
+/// the main loop, where `k` is the current index and `d` the number of nonzero vectors.
+/// The reduction stops as soon as the hook returns `false`, in which case `None` is
+/// returned and `basis` is left partially reduced (it still generates the same lattice).
+pub(crate) fn lattice_reduce_with_hook<S, F>(
+    basis: &mut Matrix<S::Integer>,
+    eta: f64,
+    delta: f64,
+    hook: F,
+) -> Option<usize>
+where
+    F: FnMut(&Matrix<S::Integer>, usize, usize) -> bool,
+    S: Scalars,
+    S::Integer: Coefficient + Send + Sync,
+    S::Fraction: Coefficient,
+    Vector<S::Integer>: Dot<Output = S::Integer>,
+    Vector<S::Fraction>: Dot<Output = S::Fraction>,
+{
+    let mut mu = Matrix::init(0, 0);
+    let mut r = Matrix::init(0, 0);
+    let params = ReductionParams::default().eta(eta).delta(delta);
+    lattice_reduce_gso::<S, F>(basis, &params, None, None, &mut mu, &mut r, hook)
+}
+
+/// Lattice reduction (L² algorithm) keeping the Gram-Schmidt orthogonalisation
+///
+/// Same as `lattice_reduce_with_hook`, the final `mu` and `r` matrices of the reduced basis
+/// are stored in `mu` and `r` (their rows for the zero vectors are zero).
+///
+/// When `initial_gram` is given, it must be the Gram matrix of `basis` (at least its lower
+/// triangular part): it is used instead of computing the Gram matrix, unless `basis`
+/// contains zero vectors.
+///
+/// When `transform` is given, the operations performed on `basis` are also recorded by
+/// `transform`: a matrix starting from the identity ends up being the unimodular
+/// transformation `U` such that the reduced basis is `U * basis`, and a `Vec<RowOp>` logs
+/// the operations.
+pub(crate) fn lattice_reduce_gso<'t, S, F>(
+    basis: &mut Matrix<S::Integer>,
+    params: &ReductionParams,
+    initial_gram: Option<&Matrix<S::Integer>>,
+    mut transform: Option<&mut (dyn RowTracker<S::Integer> + 't)>,
+    mu: &mut Matrix<S::Fraction>,
+    r: &mut Matrix<S::Fraction>,
+    mut hook: F,
+) -> Option<usize>
+where
+    F: FnMut(&Matrix<S::Integer>, usize, usize) -> bool,
+    S: Scalars,
+    S::Integer: Coefficient + Send + Sync,
+    S::Fraction: Coefficient,
+    Vector<S::Integer>: Dot<Output = S::Integer>,
+    Vector<S::Fraction>: Dot<Output = S::Fraction>,
+{
+    let mut state = Reduction::<S>::new(basis, params, initial_gram, transform.as_deref_mut());
+    let mut completed = true;
+    while !state.is_done() {
+        if !state.step(basis, transform.as_deref_mut()) || !hook(basis, state.k, state.d) {
+            completed = false;
+            break;
+        }
+    }
+
+    let zeros = state.finish();
+    *mu = state.mu;
+    *r = state.r;
+    if completed {
+        Some(zeros)
+    } else {
+        None
+    }
+}
+
+/// State of an L² reduction in progress
+///
+/// The vectors `basis[..k]` are reduced, and `basis[d..]` are the zero vectors found so
+/// far. The Gram matrix (lower triangular) and the Gram-Schmidt orthogonalisation of
+/// `basis[..k]` are kept up to date, so the reduction can be resumed at any time with
+/// `step`, as long as `basis` is not modified in between.
+pub(crate) struct Reduction<S>
+where
+    S: Scalars,
+{
+    gram: Matrix<S::Integer>,
+    mu: Matrix<S::Fraction>,
+    r: Matrix<S::Fraction>,
+    ortho: Matrix<S::Fraction>,
+    eta_minus: S::Fraction,
+    delta_plus: S::Fraction,
+    denominator_bits: Option<u32>,
+    max_size_reduction_passes: Option<usize>,
+    k: usize,
+    d: usize,
+    swaps: usize,
+    size_reductions: usize,
+}
+
+impl<S> Reduction<S>
+where
+    S: Scalars,
+    S::Integer: Coefficient + Send + Sync,
     S::Fraction: Coefficient,
     Vector<S::Integer>: Dot<Output = S::Integer>,
+    Vector<S::Fraction>: Dot<Output = S::Fraction>,
 {
-    assert!(0.25 < delta && delta < 1.);
-    assert!(0.5 < eta && eta * eta < delta);
-    // Variables
-    let (d, _) = basis.dimensions();
-    let mut gram: Matrix<S::Integer> = Matrix::init(d, d); // Gram matrix (upper triangular)
-    let mut r: Matrix<S::Fraction> = Matrix::init(d, d); // r_ij matrix
-    let mut mu: Matrix<S::Fraction> = Matrix::init(d, d); // Gram coefficient matrix
+    /// Start the reduction of `basis` (see `lattice_reduce_gso` for the arguments)
+    ///
+    /// The zero vectors are moved to the end of the basis, and the prefix of the basis that
+    /// is already reduced is skipped (the whole basis when reducing a reduced basis): the
+    /// main loop would not modify it.
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub(crate) fn new<'t>(
+        basis: &mut Matrix<S::Integer>,
+        params: &ReductionParams,
+        initial_gram: Option<&Matrix<S::Integer>>,
+        mut transform: Option<&mut (dyn RowTracker<S::Integer> + 't)>,
+    ) -> Self {
+        let (eta, delta) = (params.eta, params.delta);
+        check_parameters(eta, delta);
+        // Variables
+        let (n, _) = basis.dimensions();
+        let mut state = Self {
+            gram: Matrix::init(0, 0),
+            r: Matrix::init(n, n),  // r_ij matrix
+            mu: Matrix::init(n, n), // Gram coefficient matrix
+            ortho: Matrix::init(0, 0),
+            eta_minus: S::Fraction::from_ext(eta_bar(eta).to_f64()),
+            delta_plus: S::Fraction::from_ext(delta_bar(delta).to_f64()),
+            denominator_bits: params.denominator_bits,
+            max_size_reduction_passes: params.max_size_reduction_passes,
+            k: 1,
+            d: n,
+            swaps: 0,
+            size_reductions: 0,
+        };
+
+        // Moving the zero vectors to the end of the basis
+        let mut i = 0;
+        while i < state.d {
+            if is_zero::<S>(&basis[i].dot(&basis[i])) {
+                move_to_end(basis, i, state.d);
+                if let Some(transform) = transform.as_deref_mut() {
+                    transform.move_to_end(i, state.d);
+                }
+                state.d -= 1;
+            } else {
+                i += 1;
+            }
+        }
+        if state.d == 0 {
+            return state;
+        }
+
+        // Computing Gram matrix (lower triangular)
+        state.gram = match initial_gram {
+            Some(gram) if state.d == n => {
+                assert_eq!(gram.dimensions(), (n, n));
+                gram.clone()
+            }
+            _ => gram_matrix(basis, state.d),
+        };
+
+        if S::REORTHOGONALIZE {
+            state.ortho = Matrix::init(n, basis[0].dimension());
+        }
+        state.update_gso(0, basis);
+
+        // Skipping the prefix of the basis that is already reduced
+        while state.k < state.d {
+            let k = state.k;
+            state.update_gso(k, basis);
+            let size_reduced = !(0..k).any(|j| S::abs(state.mu[k][j].clone()) > state.eta_minus);
+            if !(size_reduced && state.lovasz_ok(k)) {
+                break;
+            }
+            state.k += 1;
+        }
+        state
+    }
+
+    /// Whether the whole basis is reduced
+    pub(crate) fn is_done(&self) -> bool {
+        self.k >= self.d
+    }
+
+    /// Index of the vector being reduced: the vectors before it are reduced
+    pub(crate) fn index(&self) -> usize {
+        self.k
+    }
+
+    /// Number of zero vectors found so far, at the end of the basis
+    pub(crate) fn zeros(&self) -> usize {
+        self.mu.dimensions().0 - self.d
+    }
+
+    /// Remove the last nonzero vector if the squared norm of its Gram-Schmidt vector
+    /// exceeds `bound`, and return whether it was removed
+    ///
+    /// The Gram-Schmidt vector is only known once the vectors before it are reduced (the
+    /// reduction reached the last vector), otherwise nothing is removed. The size reduction
+    /// does not change it, so the vector can be removed before being size-reduced. The
+    /// removed vector is left at position `d - 1`, just before the zero vectors, and counted
+    /// with them by `zeros` and `finish`.
+    pub(crate) fn remove_last(&mut self, basis: &Matrix<S::Integer>, bound: &S::Integer) -> bool {
+        if self.d == 0 || self.k < self.d - 1 {
+            return false;
+        }
+        let last = self.d - 1;
+        self.update_gso(last, basis);
+        let removed = self.r[last][last] > *bound;
+        if removed {
+            self.d -= 1;
+            self.k = self.k.min(self.d);
+        }
+        removed
+    }
+
+    /// Run one iteration of the main loop of the reduction
+    ///
+    /// Returns `false` if the size reduction did not converge within the maximum number of
+    /// passes (see `ReductionParams`) or produced Gram-Schmidt coefficients that cannot be
+    /// rounded (see `Scalars::can_round`), in which case the state must be dropped.
+    pub(crate) fn step<'t>(
+        &mut self,
+        basis: &mut Matrix<S::Integer>,
+        mut transform: Option<&mut (dyn RowTracker<S::Integer> + 't)>,
+    ) -> bool {
+        let (k, d) = (self.k, self.d);
+        let size_reduced = size_reduce::<S>(
+            k,
+            d,
+            basis,
+            transform.as_deref_mut(),
+            &mut self.gram,
+            &mut self.mu,
+            &mut self.r,
+            &mut self.ortho,
+            &self.eta_minus,
+            self.denominator_bits,
+            self.max_size_reduction_passes,
+            &mut self.size_reductions,
+        );
+        if !size_reduced {
+            return false;
+        }
+
+        // Linear dependency: the size-reduced vector is zero
+        if is_zero::<S>(&self.gram[k][k]) {
+            move_to_end(basis, k, d);
+            if let Some(transform) = transform.as_deref_mut() {
+                transform.move_to_end(k, d);
+            }
+            self.d -= 1;
+            for i in k..self.d {
+                for j in 0..=i {
+                    self.gram[i][j] = basis[i].dot(&basis[j]);
+                }
+            }
+            return true;
+        }
+
+        // Lovazs condition
+        if self.lovasz_ok(k) {
+            self.k += 1;
+        } else {
+            basis.swap(k, k - 1);
+            if let Some(transform) = transform {
+                transform.swap_rows(k, k - 1);
+            }
+            self.swaps += 1;
+
+            // Updating Gram matrix
+            for j in 0..d {
+                if j < k {
+                    self.gram[k][j] = basis[k].dot(&basis[j]);
+                    self.gram[k - 1][j] = basis[k - 1].dot(&basis[j]);
+                } else {
+                    self.gram[j][k] = basis[j].dot(&basis[k]);
+                    self.gram[j][k - 1] = basis[j].dot(&basis[k - 1]);
+                }
+            }
+
+            // Updating mu and r
+            for i in 0..=k {
+                self.update_gso(i, basis);
+            }
+
+            self.k = max(1, k - 1);
+        }
+        true
+    }
+
+    /// Clear the Gram-Schmidt rows of the zero vectors, and return their number
+    pub(crate) fn finish(&mut self) -> usize {
+        let (n, _) = self.mu.dimensions();
+        for i in self.d..n {
+            self.mu[i] = Vector::init(n);
+            self.r[i] = Vector::init(n);
+        }
+        n - self.d
+    }
+
+    /// Progress of the reduction after `iterations` iterations, started at `start`
+    fn progress(&self, iterations: usize, start: time::Instant) -> ReductionProgress {
+        ReductionProgress {
+            iterations,
+            swaps: self.swaps,
+            index: self.k,
+            rank: self.d,
+            elapsed: start.elapsed(),
+        }
+    }
+
+    /// Gram-Schmidt coefficients `mu` and `r`, up to date for the reduced vectors
+    pub(crate) fn gso(&self) -> (&Matrix<S::Fraction>, &Matrix<S::Fraction>) {
+        (&self.mu, &self.r)
+    }
+
+    fn update_gso(&mut self, k: usize, basis: &Matrix<S::Integer>) {
+        update_gso::<S>(
+            k,
+            basis,
+            &self.gram,
+            &mut self.mu,
+            &mut self.r,
+            &mut self.ortho,
+            self.denominator_bits,
+        );
+    }
+
+    fn lovasz_ok(&self, k: usize) -> bool {
+        let mu = &self.mu[k][k - 1];
+        lovasz_ok_norm(
+            &self.r[k - 1][k - 1],
+            &self.r[k][k],
+            &(S::conj(mu.clone()) * mu),
+            &self.delta_plus,
+        )
+    }
+}
+
+/// Checks the parameters of a reduction
+///
+/// The accepted ranges are the closed theoretical ones: `1/4 <= delta < 1` and
+/// `1/2 <= eta < sqrt(delta)`.
+///
+/// # Panics
+/// if delta < 1/4 or delta >= 1  
+/// if eta < 1/2 or eta >= sqrt(delta)
+pub(crate) fn check_parameters(eta: f64, delta: f64) {
+    validate_parameters(eta, delta).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Check the eta and delta factors of a reduction, returning an error if they are invalid
+pub(crate) fn validate_parameters(eta: f64, delta: f64) -> Result<(), LatticeError> {
+    if !(0.25..1.).contains(&delta) {
+        return Err(LatticeError::InvalidDelta(delta));
+    }
+    if !(0.5 <= eta && eta * eta < delta) {
+        return Err(LatticeError::InvalidEta(eta));
+    }
+    Ok(())
+}
+
+/// Margin of the size-reduction threshold above `1/2` when `eta = 1/2`
+const ETA_MARGIN: f64 = 1. / (1u64 << 20) as f64;
+
+/// Size-reduction threshold used by the reductions for a given `eta`
+///
+/// The midpoint `(eta + 1/2) / 2`, strictly between `1/2` and `eta` when
+/// `eta > 1/2 + 2^-20`. A vector is size-reduced again as soon as one of its `|mu|` exceeds
+/// this threshold, instead of `eta`: the output then satisfies the `eta` condition with
+/// some margin, which absorbs the rounding errors of the floating-point reductions and of
+/// the `f64` parameters.
+///
+/// The threshold is at least `1/2 + 2^-20`, so that the floating-point reductions still
+/// terminate near the boundary: for `eta <= 1/2 + 2^-20` (in particular `eta = 1/2`) it is
+/// `1/2 + 2^-20`, not below `eta`. The exact reductions round the `mu` exactly and return
+/// `|mu| <= 1/2` anyway.
+///
+/// The value is the exact rational of the `f64` threshold.
+pub fn eta_bar(eta: f64) -> rug::Rational {
+    let threshold = ((eta + 0.5) / 2.).max(0.5 + ETA_MARGIN);
+    rug::Rational::from_f64(threshold).expect("eta must be finite")
+}
+
+/// Lovasz threshold used by the reductions for a given `delta`
+///
+/// The midpoint `(delta + 1) / 2`, strictly between `delta` and `1`. The Lovasz condition
+/// is checked with this threshold instead of `delta`, so that the output satisfies the
+/// `delta` condition with some margin (see `eta_bar`).
+///
+/// The value is the exact rational of the `f64` midpoint.
+pub fn delta_bar(delta: f64) -> rug::Rational {
+    rug::Rational::from_f64((delta + 1.) / 2.).expect("delta must be finite")
+}
+
+/// Lovasz condition of the L² reduction
+///
+/// With `r_prev = r[k-1][k-1]`, `r_cur = r[k][k]` and `mu = mu[k][k-1]`, checks whether
+/// `delta_plus * r_prev < mu² * r_prev + r_cur`, in which case the reduction moves on to the
+/// next vector. The comparison is strict: at equality the vectors `k - 1` and `k` are
+/// swapped. All the L² reductions (exact and floating-point) use this decision rule.
+pub fn lovasz_ok<F>(r_prev: &F, r_cur: &F, mu: &F, delta_plus: &F) -> bool
+where
+    F: Clone
+        + PartialOrd
+        + for<'a> ops::Mul<&'a F, Output = F>
+        + for<'a> ops::Add<&'a F, Output = F>,
+{
+    lovasz_ok_norm(r_prev, r_cur, &(mu.clone() * mu), delta_plus)
+}
+
+/// Lovasz condition with the squared magnitude `mu_norm = |mu|²` of the coefficient
+///
+/// Same as `lovasz_ok`, for the complex coefficients.
+fn lovasz_ok_norm<F>(r_prev: &F, r_cur: &F, mu_norm: &F, delta_plus: &F) -> bool
+where
+    F: Clone
+        + PartialOrd
+        + for<'a> ops::Mul<&'a F, Output = F>
+        + for<'a> ops::Add<&'a F, Output = F>,
+{
+    delta_plus.clone() * r_prev < mu_norm.clone() * r_prev + r_cur
+}
+
+/// Lower triangular part of the Gram matrix of the first `d` vectors of `basis`
+///
+/// The rows of the Gram matrix are computed in parallel when the `rayon` feature is enabled.
+pub(crate) fn gram_matrix<T>(basis: &Matrix<T>, d: usize) -> Matrix<T>
+where
+    T: Coefficient + Send + Sync,
+    Vector<T>: Dot<Output = T>,
+{
+    #[cfg(feature = "rayon")]
+    {
+        gram_matrix_parallel(basis, d)
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        gram_matrix_sequential(basis, d)
+    }
+}
+
+#[cfg(any(test, not(feature = "rayon")))]
+pub(crate) fn gram_matrix_sequential<T>(basis: &Matrix<T>, d: usize) -> Matrix<T>
+where
+    T: Coefficient,
+    Vector<T>: Dot<Output = T>,
+{
+    let (n, _) = basis.dimensions();
+    let mut gram: Matrix<T> = Matrix::init(n, n);
+    for i in 0..d {
+        for j in 0..=i {
+            gram[i][j] = basis[i].dot(&basis[j]);
+        }
+    }
+    gram
+}
+
+#[cfg(feature = "rayon")]
+pub(crate) fn gram_matrix_parallel<T>(basis: &Matrix<T>, d: usize) -> Matrix<T>
+where
+    T: Coefficient + Send + Sync,
+    Vector<T>: Dot<Output = T>,
+{
+    use rayon::prelude::*;
+
+    let rows: Vec<Vec<T>> = (0..d)
+        .into_par_iter()
+        .map(|i| (0..=i).map(|j| basis[i].dot(&basis[j])).collect())
+        .collect();
+
+    let (n, _) = basis.dimensions();
+    let mut gram: Matrix<T> = Matrix::init(n, n);
+    for (i, row) in rows.into_iter().enumerate() {
+        for (j, entry) in row.into_iter().enumerate() {
+            gram[i][j] = entry;
+        }
+    }
+    gram
+}
+
+fn is_zero<S>(x: &S::Integer) -> bool
+where
+    S: Scalars,
+    S::Fraction: Coefficient,
+{
+    S::Fraction::from_ext(x) == S::Fraction::from(0)
+}
+
+/// Quotient `num / den`, or zero when `den` is zero (linearly dependent vectors)
+fn ratio<S>(num: &S::Fraction, den: &S::Fraction) -> S::Fraction
+where
+    S: Scalars,
+    S::Fraction: Coefficient,
+{
+    if *den == S::Fraction::from(0) {
+        S::Fraction::from(0)
+    } else {
+        num.clone() / den
+    }
+}
+
+/// Number of passes of the size reduction of a vector after which the floating-point
+/// reductions give up a precision (each pass removes about as many bits from the
+/// Gram-Schmidt coefficients as the precision)
+const SIZE_REDUCTION_PASSES: usize = 32;
+
+/// Fraction of `||b_k||²` below which `b*_k` is orthogonalised a second time
+///
+/// This is Kahan's "twice is enough" criterion `||b*_k|| < ||b_k|| / sqrt(2)`.
+const REORTHOGONALIZATION_THRESHOLD: (i32, i32) = (1, 2);
+
+/// Recompute the row `k` of `mu` and `r` from the Gram matrix
+///
+/// For `i <= k`, sets `r[k][i] = gram[k][i] - sum_{j < i} conj(mu[i][j]) * r[k][j]` and
+/// `mu[k][i] = r[k][i] / r[i][i]` (zero when `r[i][i]` is zero), bounding the denominators
+/// of `mu[k][..k]` by `2^denominator_bits` when given. The rows `0..k` of `mu` and `r` must
+/// be up to date.
+pub(crate) fn update_gso_from_gram<S>(
+    k: usize,
+    gram: &Matrix<S::Integer>,
+    mu: &mut Matrix<S::Fraction>,
+    r: &mut Matrix<S::Fraction>,
+    denominator_bits: Option<u32>,
+) where
+    S: Scalars,
+    S::Fraction: Coefficient,
+{
+    for i in 0..=k {
+        r[k][i] = S::Fraction::from_ext(&gram[k][i])
+            - &(0..i)
+                .map(|index| S::conj(mu[i][index].clone()) * &r[k][index])
+                .sum::<S::Fraction>();
+        mu[k][i] = ratio::<S>(&r[k][i], &r[i][i]);
+        if let (Some(bits), true) = (denominator_bits, i < k) {
+            S::bound_denominator(&mut mu[k][i], bits);
+        }
+    }
+}
+
+/// Recompute the row `k` of `mu` and `r`
+///
+/// With exact arithmetic, the row is computed from the Gram matrix, and the denominators
+/// of `mu` are bounded by `2^denominator_bits` when given. Otherwise
+/// (`S::REORTHOGONALIZE`), the Gram-Schmidt vector `b*_k` is computed explicitly and
+/// stored in `ortho[k]`: when its norm dropped below a fraction of `||b_k||` because of
+/// cancellations, the projection subtraction is run a second time.
+fn update_gso<S>(
+    k: usize,
+    basis: &Matrix<S::Integer>,
+    gram: &Matrix<S::Integer>,
+    mu: &mut Matrix<S::Fraction>,
+    r: &mut Matrix<S::Fraction>,
+    ortho: &mut Matrix<S::Fraction>,
+    denominator_bits: Option<u32>,
+) where
+    S: Scalars,
+    S::Fraction: Coefficient,
+    Vector<S::Fraction>: Dot<Output = S::Fraction>,
+{
+    if !S::REORTHOGONALIZE {
+        update_gso_from_gram::<S>(k, gram, mu, r, denominator_bits);
+        return;
+    }
+
+    let b_k: Vector<S::Fraction> = basis[k].iter().map(S::Fraction::from_ext).collect();
+    let mut b_star = b_k.clone();
+    for j in 0..k {
+        mu[k][j] = ratio::<S>(&b_k.dot(&ortho[j]), &r[j][j]);
+        b_star = b_star.sub(&ortho[j].mulf(&mu[k][j]));
+    }
+
+    let threshold = S::Fraction::from_ext(REORTHOGONALIZATION_THRESHOLD) * &b_k.dot(&b_k);
+    if b_star.dot(&b_star) < threshold {
+        for j in 0..k {
+            let correction = ratio::<S>(&b_star.dot(&ortho[j]), &r[j][j]);
+            b_star = b_star.sub(&ortho[j].mulf(&correction));
+            mu[k][j] = mu[k][j].clone() + &correction;
+        }
+    }
+
+    for j in 0..k {
+        r[k][j] = mu[k][j].clone() * &r[j][j];
+    }
+    r[k][k] = b_star.dot(&b_star);
+    mu[k][k] = ratio::<S>(&r[k][k], &r[k][k]);
+    ortho[k] = b_star;
+}
+
+/// Recorder of the row operations performed on a basis during a reduction
+pub(crate) trait RowTracker<T> {
+    /// Swap the rows `i` and `j`
+    fn swap_rows(&mut self, i: usize, j: usize);
+
+    /// Subtract `x` times the row `src` from the row `dst`
+    fn sub_scaled(&mut self, dst: usize, src: usize, x: &T);
+
+    /// Move the row `k` to position `d - 1`, shifting the rows `k + 1..d` down by one
+    fn move_to_end(&mut self, k: usize, d: usize) {
+        for i in k..(d - 1) {
+            self.swap_rows(i, i + 1);
+        }
+    }
+}
+
+impl<T> RowTracker<T> for Matrix<T>
+where
+    T: Coefficient,
+{
+    fn swap_rows(&mut self, i: usize, j: usize) {
+        Matrix::swap_rows(self, i, j);
+    }
+
+    fn sub_scaled(&mut self, dst: usize, src: usize, x: &T) {
+        Matrix::sub_scaled(self, dst, src, x);
+    }
+}
+
+impl RowTracker<rug::Integer> for Vec<RowOp> {
+    fn swap_rows(&mut self, i: usize, j: usize) {
+        self.push(RowOp::Swap(i, j));
+    }
+
+    fn sub_scaled(&mut self, dst: usize, src: usize, x: &rug::Integer) {
+        if *x != 0 {
+            self.push(RowOp::SubScaled {
+                dst,
+                src,
+                scalar: x.clone(),
+            });
+        }
+    }
+}
+
+/// Move `basis[k]` to position `d - 1`, shifting `basis[k + 1..d]` down by one
+fn move_to_end<T>(basis: &mut Matrix<T>, k: usize, d: usize)
+where
+    T: Coefficient,
+{
+    for i in k..(d - 1) {
+        basis.swap(i, i + 1);
+    }
+}
+
+/// Performs the `eta`-size-reduction of `basis[k]`
+///
+/// Arguments:
+/// * `k`: Index of the column to be `eta`-size-reduced
+/// * `d`: The basis dimension
+/// * `basis`: A generating matrix for the lattice
+/// * `transform`: the transformation matrix or operation log, updated as `basis` (optional)
+/// * `gram`: Gram matrix of `basis`  
+/// * `mu`: Gram coefficient matrix
+/// * `r`: the r_ij matrix
+/// * `ortho`: the Gram-Schmidt vectors (only used with `S::REORTHOGONALIZE`)
+/// * `eta`: eta factor of the basis reduction
+/// * `denominator_bits`: bound on the denominators of `mu` (see `ReductionParams`)
+/// * `max_passes`: maximum number of passes of the size reduction (see `ReductionParams`)
+/// * `steps`: incremented for each subtraction of a nonzero multiple of a vector
+///
+/// Returns `false` if the vector is still not size-reduced after `max_passes` passes, or if
+/// one of its Gram-Schmidt coefficients cannot be rounded.
+///
+/// Note: both `basis` and `gram` are updated by this operation.
+#[allow(clippy::too_many_arguments)]
+fn size_reduce<'t, S>(
+    k: usize,
+    d: usize,
+    basis: &mut Matrix<S::Integer>,
+    mut transform: Option<&mut (dyn RowTracker<S::Integer> + 't)>,
+    gram: &mut Matrix<S::Integer>,
+    mu: &mut Matrix<S::Fraction>,
+    r: &mut Matrix<S::Fraction>,
+    ortho: &mut Matrix<S::Fraction>,
+    eta: &S::Fraction,
+    denominator_bits: Option<u32>,
+    max_passes: Option<usize>,
+    steps: &mut usize,
+) -> bool
+where
+    S: Scalars,
+    S::Integer: Coefficient,
+    S::Fraction: Coefficient,
+    Vector<S::Integer>: Dot<Output = S::Integer>,
+    Vector<S::Fraction>: Dot<Output = S::Fraction>,
+{
+    let mut passes = 0;
+    loop {
+        update_gso::<S>(k, basis, gram, mu, r, ortho, denominator_bits);
+
+        if !(0..k).all(|index| S::can_round(&mu[k][index])) {
+            return false;
+        }
+        if !(0..k).any(|index| S::abs(mu[k][index].clone()) > *eta) {
+            return true;
+        }
+        if max_passes.is_some_and(|max| passes >= max) {
+            return false;
+        }
+        passes += 1;
+
+        for i in (0..k).rev() {
+            let x = S::round(&mu[k][i]);
+            if !is_zero::<S>(&x) {
+                *steps += 1;
+            }
+            basis.sub_scaled(k, i, &x);
+            if let Some(transform) = transform.as_deref_mut() {
+                transform.sub_scaled(k, i, &x);
+            }
+
+            // Updating Gram matrix
+            for j in 0..d {
+                if j < k {
+                    gram[k][j] = basis[k].dot(&basis[j]);
+                } else {
+                    gram[j][k] = basis[j].dot(&basis[k]);
+                }
+            }
+
+            for j in 0..i {
+                let minus = S::Fraction::from_ext(&x) * &mu[i][j];
+                mu[k][j] -= &minus;
+                if let Some(bits) = denominator_bits {
+                    S::bound_denominator(&mut mu[k][j], bits);
+                }
+            }
+        }
+    }
+}
+
+pub mod bigl2 {
+    use super::{
+        ProgressCallback, ReductionBudget, ReductionError, ReductionParams, ReductionProgress,
+        ReductionResult, ReductionStats,
+    };
+    use crate::error::LatticeError;
+    use crate::matrix::{Matrix, RowOp};
+    use crate::metrics;
+    use crate::scalars::{BigFloat, BigNum};
+    use crate::vector::Dot;
+
+    use rug::Rational;
+
+    /// Lattice reduction (L² algorithm)
+    ///
+    /// This implementation uses `rug::Integers` and `rug::Rationnal` for the underlying arithmetic operations.
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * eta: eta factor of the basis reduction
+    ///  * delta: delta factor of the basis reduction
+    ///
+    /// The basis is reduced in-place. The zero vectors coming from linear dependencies
+    /// are moved to the end of the basis, and their number is returned.
+    ///
+    /// The vectors may have more coordinates than there are vectors in the basis (a
+    /// sublattice of a higher-dimensional space): the reduced basis generates the same
+    /// lattice.
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn lattice_reduce(basis: &mut Matrix<rug::Integer>, eta: f64, delta: f64) -> usize {
+        super::lattice_reduce::<BigNum>(basis, eta, delta)
+    }
+
+    /// Lattice reduction (L² algorithm), or an error if the parameters are invalid
+    ///
+    /// Same as `lattice_reduce`, returning `LatticeError::InvalidDelta` or
+    /// `LatticeError::InvalidEta` instead of panicking (the basis is then unchanged).
+    pub fn try_lattice_reduce(
+        basis: &mut Matrix<rug::Integer>,
+        eta: f64,
+        delta: f64,
+    ) -> Result<usize, LatticeError> {
+        super::validate_parameters(eta, delta)?;
+        Ok(super::lattice_reduce::<BigNum>(basis, eta, delta))
+    }
+
+    /// Lattice reduction (L² algorithm) with an automatic choice of the arithmetic
+    ///
+    /// The reduction first runs with double floating-point Gram-Schmidt coefficients, then
+    /// with multiple-precision floating-point numbers of `floatl2::precision_bound` bits
+    /// (with the `mpfr` feature), and finally with exact rational numbers. The basis itself
+    /// is always kept exact, so each step goes on from the basis left by the previous one.
+    /// A step fails when it runs for more iterations than the bound of the exact
+    /// algorithm, when the size reduction of a vector does not converge, or when its output
+    /// is not reduced (checked with exact arithmetic).
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * eta: eta factor of the basis reduction
+    ///  * delta: delta factor of the basis reduction
+    ///
+    /// The basis is reduced in-place. The zero vectors coming from linear dependencies
+    /// are moved to the end of the basis, and their number is returned.
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn reduce_auto(basis: &mut Matrix<rug::Integer>, eta: f64, delta: f64) -> usize {
+        super::check_parameters(eta, delta);
+        let params = ReductionParams::default()
+            .eta(eta)
+            .delta(delta)
+            .max_iterations(iteration_bound(basis, delta))
+            .max_size_reduction_passes(super::SIZE_REDUCTION_PASSES);
+        // Coefficients computed by the reduction, up to the rounding of the comparisons
+        let eta_check = super::eta_bar(eta).to_f64().max(eta);
+        let is_reduced =
+            |basis: &Matrix<rug::Integer>| crate::verify::is_lll_reduced(basis, delta, eta_check);
+
+        let (n, _) = basis.dimensions();
+        let max_bits = (0..n)
+            .flat_map(|i| basis[i].iter())
+            .map(rug::Integer::significant_bits)
+            .max()
+            .unwrap_or(0);
+        if max_bits <= F64_MAX_BITS {
+            if let Ok(zeros) = super::lattice_reduce_with::<BigFloat>(basis, &params) {
+                if is_reduced(basis) {
+                    return zeros;
+                }
+            }
+        }
+
+        #[cfg(feature = "mpfr")]
+        {
+            let precision = super::floatl2::precision_bound(n, eta, delta);
+            if let Ok(zeros) = super::floatl2::lattice_reduce_with(basis, &params, precision) {
+                if is_reduced(basis) {
+                    return zeros;
+                }
+            }
+        }
+
+        lattice_reduce(basis, eta, delta)
+    }
+
+    /// Largest bit length of the coefficients for which `reduce_auto` tries double
+    /// floating-point numbers (the squared norms must not overflow)
+    const F64_MAX_BITS: u32 = 500;
+
+    /// Bound on the number of iterations of the L² reduction of `basis` with exact arithmetic
+    ///
+    /// Each swap decreases the potential `prod_i ||b*_i||^(2(d - i))`, which is at most
+    /// `B^(d(d + 1)/2)` with `B` the largest squared norm of the vectors, by a factor
+    /// `delta_bar` (see `delta_bar`), and each iteration either swaps two vectors or
+    /// increases `k`.
+    fn iteration_bound(basis: &Matrix<rug::Integer>, delta: f64) -> usize {
+        let (n, _) = basis.dimensions();
+        let log_norm = (0..n)
+            .map(|i| basis[i].dot(&basis[i]).significant_bits())
+            .max()
+            .unwrap_or(0) as f64;
+        let d = n as f64;
+        let swaps = d * (d + 1.) / 2. * log_norm / -super::delta_bar(delta).to_f64().log2();
+        (d + 2. * swaps).ceil() as usize + n
+    }
+
+    /// Reduced basis of the lattice generated by a set of vectors (MLLL)
+    ///
+    /// The generating set may contain linearly dependent vectors, and more vectors than
+    /// the rank of the lattice. The zero vectors appearing during the reduction are
+    /// eliminated, and the remaining vectors form a reduced basis of the lattice.
+    ///
+    /// Arguments:
+    ///  * generators: A generating set of the lattice
+    ///  * eta: eta factor of the basis reduction
+    ///  * delta: delta factor of the basis reduction
+    ///
+    /// Returns the reduced basis, made of as many vectors as the rank of the lattice (no
+    /// vector for the lattice `{0}`).
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn reduce_generating_set(
+        generators: &Matrix<rug::Integer>,
+        eta: f64,
+        delta: f64,
+    ) -> Matrix<rug::Integer> {
+        let mut basis = generators.clone();
+        let zeros = lattice_reduce(&mut basis, eta, delta);
+        let (n, dim) = basis.dimensions();
+        basis.submatrix(0..n - zeros, 0..dim)
+    }
+
+    /// Lattice reduction (L² algorithm) with early termination
+    ///
+    /// The reduction stops as soon as the squared norm of `basis[0]` is at most `target_norm_sq`.
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * eta: eta factor of the basis reduction
+    ///  * delta: delta factor of the basis reduction
+    ///  * target_norm_sq: target for the squared norm of the first vector
+    ///
+    /// The basis is reduced in-place. Returns `true` if the target was reached, and `false`
+    /// if the basis was fully reduced without reaching it.
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn lattice_reduce_until(
+        basis: &mut Matrix<rug::Integer>,
+        eta: f64,
+        delta: f64,
+        target_norm_sq: &rug::Integer,
+    ) -> bool {
+        reduce_until(basis, eta, delta, target_norm_sq).0
+    }
+
+    /// Same as `lattice_reduce_until`, also returning the number of swaps
+    pub(crate) fn reduce_until(
+        basis: &mut Matrix<rug::Integer>,
+        eta: f64,
+        delta: f64,
+        target_norm_sq: &rug::Integer,
+    ) -> (bool, usize) {
+        let reached = |basis: &Matrix<rug::Integer>| basis[0].dot(&basis[0]) <= *target_norm_sq;
+
+        let params = ReductionParams::default().eta(eta).delta(delta);
+        let mut state = super::Reduction::<BigNum>::new(basis, &params, None, None);
+        while !state.is_done() {
+            let converged = state.step(basis, None);
+            assert!(converged, "the exact size reduction always converges");
+            if reached(basis) {
+                return (true, state.swaps);
+            }
+        }
+        (reached(basis), state.swaps)
+    }
+
+    /// Lattice reduction (L² algorithm) with a progress callback
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * eta: eta factor of the basis reduction
+    ///  * delta: delta factor of the basis reduction
+    ///  * callback: called as `callback(k, d)` after each iteration, where `k` is the index
+    ///    of the vector being reduced and `d` the dimension of the lattice; the reduction is
+    ///    aborted when it returns `false`
+    ///
+    /// The basis is reduced in-place. Returns the number of zero vectors, or
+    /// `ReductionError::Cancelled` if the reduction was aborted (the basis then still
+    /// generates the same lattice).
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn lattice_reduce_with_callback(
+        basis: &mut Matrix<rug::Integer>,
+        eta: f64,
+        delta: f64,
+        callback: &mut dyn FnMut(usize, usize) -> bool,
+    ) -> Result<usize, ReductionError> {
+        super::lattice_reduce_callback::<BigNum>(basis, eta, delta, callback)
+    }
+
+    /// Lattice reduction (L² algorithm) with a set of parameters
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * params: parameters of the basis reduction
+    ///
+    /// The basis is reduced in-place. Returns the number of zero vectors, or
+    /// `ReductionError::IterationLimit` if the maximum number of iterations was reached
+    /// (the basis then still generates the same lattice).
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn lattice_reduce_with(
+        basis: &mut Matrix<rug::Integer>,
+        params: &ReductionParams,
+    ) -> Result<usize, ReductionError> {
+        super::lattice_reduce_with::<BigNum>(basis, params)
+    }
+
+    /// Lattice reduction (L² algorithm) with custom parameters, returning statistics
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * params: parameters of the basis reduction
+    ///
+    /// The basis is reduced in-place. Returns the number of zero vectors and the
+    /// statistics of the reduction (see `ReductionStats`), or
+    /// `ReductionError::IterationLimit` if the maximum number of iterations was reached.
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn lattice_reduce_with_stats(
+        basis: &mut Matrix<rug::Integer>,
+        params: &ReductionParams,
+    ) -> Result<(usize, ReductionStats), ReductionError> {
+        super::lattice_reduce_stats::<BigNum>(basis, params, None)
+    }
+
+    /// Lattice reduction (L² algorithm) with a cancellable progress callback
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * params: parameters of the basis reduction
+    ///  * progress: called after each iteration of the main loop with the current
+    ///    `ReductionProgress`; the reduction is cancelled when it returns `ControlFlow::Break`
+    ///
+    /// The basis is reduced in-place. Returns the number of zero vectors, or
+    /// `ReductionError::Cancelled` if the callback stopped the reduction (the partially
+    /// reduced basis is left in `basis`, and still generates the same lattice).
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn lattice_reduce_with_progress(
+        basis: &mut Matrix<rug::Integer>,
+        params: &ReductionParams,
+        progress: &mut ProgressCallback,
+    ) -> Result<usize, ReductionError> {
+        super::lattice_reduce_progress::<BigNum>(basis, params, None, Some(progress))
+            .map(|(zeros, _)| zeros)
+            .map_err(|(error, _)| error)
+    }
+
+    /// Lattice reduction (L² algorithm) within a budget
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * params: parameters of the basis reduction
+    ///  * budget: limits on the iterations and the wall time, replacing those of `params`
+    ///
+    /// The basis is reduced in-place. Returns the number of zero vectors or, when the
+    /// reduction stops early, the reason and the progress of the reduction at that point
+    /// (the partially reduced basis is left in `basis`, and still generates the same
+    /// lattice).
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn lattice_reduce_with_budget(
+        basis: &mut Matrix<rug::Integer>,
+        params: &ReductionParams,
+        budget: ReductionBudget,
+    ) -> Result<usize, (ReductionError, ReductionProgress)> {
+        let params = params.clone().budget(budget);
+        super::lattice_reduce_progress::<BigNum>(basis, &params, None, None).map(|(zeros, _)| zeros)
+    }
+
+    /// Lattice reduction (L² algorithm) returning the transformation
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * eta: eta factor of the basis reduction
+    ///  * delta: delta factor of the basis reduction
+    ///
+    /// The basis is reduced in-place. Returns the number of zero vectors, and the
+    /// unimodular transformation `U` of the reduction: the reduced basis is `U * basis`,
+    /// where `basis` is the input basis.
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn lattice_reduce_transform(
+        basis: &mut Matrix<rug::Integer>,
+        eta: f64,
+        delta: f64,
+    ) -> (usize, Matrix<rug::Integer>) {
+        super::lattice_reduce_transform::<BigNum>(basis, eta, delta)
+    }
+
+    /// Lattice reduction (L² algorithm) of an owned basis
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * params: parameters of the basis reduction
+    ///
+    /// Takes ownership of `basis` and returns the reduced basis, together with the
+    /// unimodular transformation of the reduction and statistics. Returns
+    /// `ReductionError::IterationLimit` if the maximum number of iterations was reached.
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn reduce_owned(
+        basis: Matrix<rug::Integer>,
+        params: &ReductionParams,
+    ) -> Result<ReductionResult<rug::Integer>, ReductionError> {
+        let initial = if params.track_potential {
+            Some(metrics::log_potential(&metrics::gram_schmidt_r(&basis)))
+        } else {
+            None
+        };
+        let mut result = super::reduce_owned::<BigNum>(basis, params)?;
+        if params.track_potential {
+            result.stats.initial_log_potential = initial;
+            result.stats.final_log_potential = Some(metrics::log_potential(
+                &metrics::gram_schmidt_r(&result.basis),
+            ));
+        }
+        Ok(result)
+    }
+
+    /// Lattice reduction (L² algorithm) logging the row operations
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * eta: eta factor of the basis reduction
+    ///  * delta: delta factor of the basis reduction
+    ///
+    /// The basis is reduced in-place. Returns the sequence of row operations performed on
+    /// `basis`: replaying them on another matrix with `Matrix::apply_ops` performs the same
+    /// transformation (on the identity matrix, this gives the unimodular transformation
+    /// of the reduction).
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn lattice_reduce_logged(
+        basis: &mut Matrix<rug::Integer>,
+        eta: f64,
+        delta: f64,
+    ) -> Vec<RowOp> {
+        let mut log = Vec::new();
+        let mut mu = Matrix::init(0, 0);
+        let mut r = Matrix::init(0, 0);
+        super::lattice_reduce_gso::<BigNum, _>(
+            basis,
+            &ReductionParams::default().eta(eta).delta(delta),
+            None,
+            Some(&mut log),
+            &mut mu,
+            &mut r,
+            |_, _, _| true,
+        )
+        .expect("the reduction cannot be interrupted");
+        log
+    }
+
+    /// Lattice reduction (L² algorithm) returning the Gram-Schmidt orthogonalisation
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * eta: eta factor of the basis reduction
+    ///  * delta: delta factor of the basis reduction
+    ///
+    /// The basis is reduced in-place. Returns the matrices `(mu, r)` of the reduced basis,
+    /// where `r[i][j] = <b_i, b*_j>` and `mu[i][j] = r[i][j] / r[j][j]` for `j <= i`
+    /// (`b*_j` being the Gram-Schmidt orthogonalisation of the basis). The rows of the
+    /// zero vectors moved at the end of the basis are zero.
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn lattice_reduce_full(
+        basis: &mut Matrix<rug::Integer>,
+        eta: f64,
+        delta: f64,
+    ) -> (Matrix<Rational>, Matrix<Rational>) {
+        let mut mu = Matrix::init(0, 0);
+        let mut r = Matrix::init(0, 0);
+        super::lattice_reduce_gso::<BigNum, _>(
+            basis,
+            &ReductionParams::default().eta(eta).delta(delta),
+            None,
+            None,
+            &mut mu,
+            &mut r,
+            |_, _, _| true,
+        )
+        .expect("the reduction cannot be interrupted");
+        (mu, r)
+    }
+
+    /// Lattice reduction (L² algorithm) with removal of the long trailing vectors
+    ///
+    /// During the reduction, as soon as the vectors before the last one are reduced, the
+    /// last vector is removed if the squared norm `||b*_i||²` of its Gram-Schmidt vector
+    /// exceeds `bound` (as fplll's `lll_reduction_with_removals`), and the zero vectors
+    /// coming from linear dependencies are removed too. The removed vectors are not reduced
+    /// any further, and the final basis is reduced: its Gram-Schmidt vectors do not depend
+    /// on the vectors that follow. In Coppersmith's method, the vectors of a reduced basis
+    /// whose Gram-Schmidt norm is below the bound of Howgrave-Graham's lemma are the useful
+    /// ones.
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * eta: eta factor of the basis reduction
+    ///  * delta: delta factor of the basis reduction
+    ///  * bound: bound on the squared norms of the last Gram-Schmidt vectors
+    ///
+    /// The basis is reduced in-place, and the removed vectors are dropped from it. Returns
+    /// the number of removed vectors.
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn lattice_reduce_with_removals(
+        basis: &mut Matrix<rug::Integer>,
+        eta: f64,
+        delta: f64,
+        bound: &rug::Integer,
+    ) -> usize {
+        let params = ReductionParams::default().eta(eta).delta(delta);
+        let mut state = super::Reduction::<BigNum>::new(basis, &params, None, None);
+        loop {
+            while state.remove_last(basis, bound) {}
+            if state.is_done() {
+                break;
+            }
+            let converged = state.step(basis, None);
+            assert!(converged, "the exact size reduction always converges");
+        }
+        let removed = state.finish();
+        let (n, dim) = basis.dimensions();
+        *basis = basis.submatrix(0..(n - removed), 0..dim);
+        removed
+    }
+
+    /// Lattice reduction (L² algorithm) reusing a cached Gram matrix
+    ///
+    /// Useful to reduce the same basis several times (e.g. with different parameters):
+    /// the Gram matrix of `basis` is computed into `gram` on first use (when it is `None`),
+    /// and reused by the following calls. The cache is not modified by the reduction, it
+    /// always holds the Gram matrix of the input basis.
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * eta: eta factor of the basis reduction
+    ///  * delta: delta factor of the basis reduction
+    ///  * gram: cache for the Gram matrix of `basis` (see `compute_gram`)
+    ///
+    /// The basis is reduced in-place. The zero vectors coming from linear dependencies
+    /// are moved to the end of the basis, and their number is returned.
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)  
+    /// if the cached Gram matrix does not have the dimensions of the Gram matrix of `basis`
+    pub fn lattice_reduce_cached(
+        basis: &mut Matrix<rug::Integer>,
+        eta: f64,
+        delta: f64,
+        gram: &mut Option<Matrix<rug::Integer>>,
+    ) -> usize {
+        let gram = gram.get_or_insert_with(|| compute_gram(basis));
+        let mut mu = Matrix::init(0, 0);
+        let mut r = Matrix::init(0, 0);
+        super::lattice_reduce_gso::<BigNum, _>(
+            basis,
+            &ReductionParams::default().eta(eta).delta(delta),
+            Some(gram),
+            None,
+            &mut mu,
+            &mut r,
+            |_, _, _| true,
+        )
+        .expect("the reduction cannot be interrupted")
+    }
+
+    /// Gram matrix of a basis
+    ///
+    /// Returns the (symmetric) matrix of the dot products `<basis[i], basis[j]>`.
+    pub fn compute_gram(basis: &Matrix<rug::Integer>) -> Matrix<rug::Integer> {
+        let (n, _) = basis.dimensions();
+        let mut gram = super::gram_matrix(basis, n);
+        for i in 0..n {
+            for j in (i + 1)..n {
+                gram[i][j] = gram[j][i].clone();
+            }
+        }
+        gram
+    }
+
+    /// Lattice reduction (L² algorithm) of a range of vectors of the basis
+    ///
+    /// Only the vectors `basis[start..end]` are modified: their projections orthogonally
+    /// to `basis[..start]` are reduced, then they are size-reduced with respect to all the
+    /// previous vectors. The vectors outside of the range are a fixed context, and the
+    /// Lovasz condition is not checked between `basis[start - 1]` and `basis[start]`.
+    /// Useful to re-reduce a few vectors of a reduced basis after modifying them.
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice, of linearly independent vectors
+    ///  * start: first index of the range
+    ///  * end: end of the range (excluded)
+    ///  * eta: eta factor of the basis reduction
+    ///  * delta: delta factor of the basis reduction
+    ///
+    /// The basis is reduced in-place. A range that is already reduced is left unchanged.
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)  
+    /// if start > end or end is larger than the number of vectors
+    pub fn lattice_reduce_range(
+        basis: &mut Matrix<rug::Integer>,
+        start: usize,
+        end: usize,
+        eta: f64,
+        delta: f64,
+    ) {
+        let (n, dim) = basis.dimensions();
+        assert!(start <= end && end <= n);
+        if start == end {
+            return;
+        }
+        crate::segment::reduce_window(basis, start, end, eta, delta);
+
+        // Size reduction of the range with respect to all the previous vectors
+        let prefix = basis.submatrix(0..end, 0..dim);
+        let r = metrics::gram_schmidt_r(&prefix);
+        let eta = Rational::from_f64(eta).unwrap();
+        let mut mu: Matrix<Rational> = Matrix::init(end, end);
+        for i in 0..end {
+            for j in 0..i {
+                if r[j][j] != 0 {
+                    mu[i][j] = Rational::from(&r[i][j] / &r[j][j]);
+                }
+            }
+        }
+        for k in start..end {
+            if !(0..k).any(|j| Rational::from(mu[k][j].abs_ref()) > eta) {
+                continue;
+            }
+            for i in (0..k).rev() {
+                let x: rug::Integer = mu[k][i].round_ref().into();
+                if x == 0 {
+                    continue;
+                }
+                let step = basis[i].mulf(&x);
+                basis[k] -= &step;
+                for j in 0..i {
+                    let minus = Rational::from(&x) * &mu[i][j];
+                    mu[k][j] -= minus;
+                }
+                mu[k][i] -= &x;
+            }
+        }
+    }
+
+    /// Resumable lattice reduction (L² algorithm)
+    ///
+    /// The reducer owns the basis and the state of the reduction (the Gram matrix and the
+    /// Gram-Schmidt orthogonalisation), so the reduction can be run in several calls: for a
+    /// number of iterations, or until a prefix of the basis is reduced. Each call resumes
+    /// the reduction where the previous one stopped, and the final basis is the same as
+    /// with `lattice_reduce`.
+    ///
+    /// ```rust
+    /// use lll_rs::l2::bigl2::Reducer;
+    /// use lll_rs::matrix::Matrix;
+    /// use lll_rs::vector::BigVector;
+    ///
+    /// let basis = Matrix::from_columns(vec![
+    ///     BigVector::from_vector(vec![1.into(), 0.into(), 0.into(), 1345.into()]),
+    ///     BigVector::from_vector(vec![0.into(), 1.into(), 0.into(), 35.into()]),
+    ///     BigVector::from_vector(vec![0.into(), 0.into(), 1.into(), 154.into()]),
+    /// ]);
+    ///
+    /// let mut reducer = Reducer::new(basis, 0.51, 0.99);
+    /// reducer.reduce_until(2);
+    /// assert!(reducer.index() >= 2);
+    /// reducer.run(10);
+    /// reducer.reduce();
+    /// assert!(reducer.is_reduced());
+    /// let reduced = reducer.into_basis();
+    /// ```
+    pub struct Reducer {
+        basis: Matrix<rug::Integer>,
+        state: super::Reduction<BigNum>,
+    }
+
+    impl Reducer {
+        /// Start the reduction of `basis`
+        ///
+        /// The zero vectors are moved to the end of the basis, and the prefix of the basis
+        /// that is already reduced is skipped.
+        ///
+        /// # Panics
+        /// if delta < 1/4 or delta >= 1  
+        /// if eta < 1/2 or eta >= sqrt(delta)
+        pub fn new(mut basis: Matrix<rug::Integer>, eta: f64, delta: f64) -> Self {
+            let params = ReductionParams::default().eta(eta).delta(delta);
+            let state = super::Reduction::new(&mut basis, &params, None, None);
+            Self { basis, state }
+        }
+
+        /// Run at most `iterations` iterations of the reduction
+        ///
+        /// Returns `true` if the basis is reduced.
+        pub fn run(&mut self, iterations: usize) -> bool {
+            for _ in 0..iterations {
+                if self.state.is_done() {
+                    break;
+                }
+                self.step();
+            }
+            self.state.is_done()
+        }
+
+        /// Run the reduction until the vectors `basis[..index]` are reduced
+        ///
+        /// Stops as soon as the index of the vector being reduced reaches `index` (or when
+        /// the whole basis is reduced): the following iterations may still modify the
+        /// prefix.
+        pub fn reduce_until(&mut self, index: usize) {
+            while !self.state.is_done() && self.state.index() < index {
+                self.step();
+            }
+        }
+
+        /// Run the reduction to completion
+        ///
+        /// Returns the number of zero vectors, at the end of the basis.
+        pub fn reduce(&mut self) -> usize {
+            while !self.state.is_done() {
+                self.step();
+            }
+            self.state.zeros()
+        }
+
+        /// Whether the basis is reduced
+        pub fn is_reduced(&self) -> bool {
+            self.state.is_done()
+        }
+
+        /// Index of the vector being reduced: the vectors before it are reduced
+        pub fn index(&self) -> usize {
+            self.state.index()
+        }
+
+        /// Number of zero vectors found so far, at the end of the basis
+        pub fn zeros(&self) -> usize {
+            self.state.zeros()
+        }
+
+        /// The current basis
+        pub fn basis(&self) -> &Matrix<rug::Integer> {
+            &self.basis
+        }
+
+        /// Gram-Schmidt coefficients `mu` and `r_ij` of the current basis
+        ///
+        /// Only the rows of the reduced vectors (before `index`) are up to date.
+        pub fn gso(&self) -> (&Matrix<Rational>, &Matrix<Rational>) {
+            self.state.gso()
+        }
+
+        /// The current basis, consuming the reducer
+        pub fn into_basis(self) -> Matrix<rug::Integer> {
+            self.basis
+        }
+
+        fn step(&mut self) {
+            let converged = self.state.step(&mut self.basis, None);
+            assert!(converged, "the exact size reduction always converges");
+        }
+    }
+}
+
+pub mod l2f {
+    use super::{ProgressCallback, ReductionError, ReductionParams, ReductionStats};
+    use crate::error::LatticeError;
+    use crate::matrix::Matrix;
+    use crate::scalars::Float;
+
+    /// Lattice reduction (L² algorithm)
+    ///
+    /// This implementation uses platform double floating-point numbers (IEEE 754)
+    /// for the underlying arithmetic operations.
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * eta: eta factor of the basis reduction
+    ///  * delta: delta factor of the basis reduction
+    ///
+    /// The basis is reduced in-place. The zero vectors coming from linear dependencies
+    /// are moved to the end of the basis, and their number is returned.
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)  
+    /// if the Gram-Schmidt coefficients overflow to an infinity or a NaN (ill-conditioned
+    /// basis, see `try_lattice_reduce`)
+    pub fn lattice_reduce(basis: &mut Matrix<f64>, eta: f64, delta: f64) -> usize {
+        super::lattice_reduce::<Float>(basis, eta, delta)
+    }
+
+    /// Lattice reduction (L² algorithm), or an error if the parameters are invalid or the
+    /// precision is insufficient
+    ///
+    /// Same as `lattice_reduce`, returning `LatticeError::InvalidDelta` or
+    /// `LatticeError::InvalidEta` instead of panicking (the basis is then unchanged), and
+    /// `LatticeError::Reduction(ReductionError::Precision)` when the Gram-Schmidt
+    /// coefficients overflow (the partially reduced basis then still generates the same
+    /// lattice).
+    pub fn try_lattice_reduce(
+        basis: &mut Matrix<f64>,
+        eta: f64,
+        delta: f64,
+    ) -> Result<usize, LatticeError> {
+        super::validate_parameters(eta, delta)?;
+        let params = ReductionParams::default().eta(eta).delta(delta);
+        Ok(super::lattice_reduce_with::<Float>(basis, &params)?)
+    }
+
+    /// Lattice reduction (L² algorithm) with a progress callback
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * eta: eta factor of the basis reduction
+    ///  * delta: delta factor of the basis reduction
+    ///  * callback: called as `callback(k, d)` after each iteration, where `k` is the index
+    ///    of the vector being reduced and `d` the dimension of the lattice; the reduction is
+    ///    aborted when it returns `false`
+    ///
+    /// The basis is reduced in-place. Returns the number of zero vectors, or
+    /// `ReductionError::Cancelled` if the reduction was aborted and
+    /// `ReductionError::Precision` if the Gram-Schmidt coefficients overflowed (the basis
+    /// then still generates the same lattice).
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn lattice_reduce_with_callback(
+        basis: &mut Matrix<f64>,
+        eta: f64,
+        delta: f64,
+        callback: &mut dyn FnMut(usize, usize) -> bool,
+    ) -> Result<usize, ReductionError> {
+        super::lattice_reduce_callback::<Float>(basis, eta, delta, callback)
+    }
+
+    /// Lattice reduction (L² algorithm) with a set of parameters
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * params: parameters of the basis reduction
+    ///
+    /// The basis is reduced in-place. Returns the number of zero vectors, or
+    /// `ReductionError::IterationLimit` if the maximum number of iterations was reached
+    /// (the basis then still generates the same lattice).
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn lattice_reduce_with(
+        basis: &mut Matrix<f64>,
+        params: &ReductionParams,
+    ) -> Result<usize, ReductionError> {
+        super::lattice_reduce_with::<Float>(basis, params)
+    }
 
-    // Computing Gram matrix
-    for i in 0..d {
-        for j in 0..=i {
-            gram[i][j] = basis[i].dot(&basis[j]);
+    /// Lattice reduction (L² algorithm) with custom parameters, returning statistics
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * params: parameters of the basis reduction
+    ///
+    /// The basis is reduced in-place. Returns the number of zero vectors and the
+    /// statistics of the reduction (see `ReductionStats`), or
+    /// `ReductionError::IterationLimit` if the maximum number of iterations was reached.
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn lattice_reduce_with_stats(
+        basis: &mut Matrix<f64>,
+        params: &ReductionParams,
+    ) -> Result<(usize, ReductionStats), ReductionError> {
+        super::lattice_reduce_stats::<Float>(basis, params, None)
+    }
+
+    /// Lattice reduction (L² algorithm) with a cancellable progress callback
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * params: parameters of the basis reduction
+    ///  * progress: called after each iteration of the main loop with the current
+    ///    `ReductionProgress`; the reduction is cancelled when it returns `ControlFlow::Break`
+    ///
+    /// The basis is reduced in-place. Returns the number of zero vectors, or
+    /// `ReductionError::Cancelled` if the callback stopped the reduction (the partially
+    /// reduced basis is left in `basis`, and still generates the same lattice).
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn lattice_reduce_with_progress(
+        basis: &mut Matrix<f64>,
+        params: &ReductionParams,
+        progress: &mut ProgressCallback,
+    ) -> Result<usize, ReductionError> {
+        super::lattice_reduce_progress::<Float>(basis, params, None, Some(progress))
+            .map(|(zeros, _)| zeros)
+            .map_err(|(error, _)| error)
+    }
+
+    /// Lattice reduction (L² algorithm) returning the transformation
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * eta: eta factor of the basis reduction
+    ///  * delta: delta factor of the basis reduction
+    ///
+    /// The basis is reduced in-place. Returns the number of zero vectors, and the
+    /// unimodular transformation `U` of the reduction: the reduced basis is `U * basis`,
+    /// where `basis` is the input basis.
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)  
+    /// if the Gram-Schmidt coefficients overflow to an infinity or a NaN (ill-conditioned
+    /// basis)
+    pub fn lattice_reduce_transform(
+        basis: &mut Matrix<f64>,
+        eta: f64,
+        delta: f64,
+    ) -> (usize, Matrix<f64>) {
+        super::lattice_reduce_transform::<Float>(basis, eta, delta)
+    }
+}
+
+#[cfg(feature = "mpfr")]
+pub mod floatl2 {
+    use crate::matrix::Matrix;
+    use crate::scalars::{BigNum, FromExt, Scalars};
+    use crate::vector::{Dot, Vector};
+
+    use rug::{Integer, Rational};
+
+    use std::{cell::Cell, cmp::Ordering, iter::Sum, ops};
+
+    thread_local! {
+        /// Precision (in bits) of the floating-point numbers of the current reduction
+        static PRECISION: Cell<u32> = const { Cell::new(53) };
+    }
+
+    fn precision() -> u32 {
+        PRECISION.with(Cell::get)
+    }
+
+    /// Sets the precision of the current reduction, and restores the previous one when
+    /// dropped (also when the reduction panics)
+    struct PrecisionGuard {
+        previous: u32,
+    }
+
+    impl PrecisionGuard {
+        fn new(precision_bits: u32) -> Self {
+            let previous = PRECISION.with(|precision| precision.replace(precision_bits));
+            Self { previous }
         }
     }
 
-    let eta_minus = S::Fraction::from_ext((eta + 0.5) / 2.);
-    let delta_plus = S::Fraction::from_ext((delta + 1.) / 2.);
+    impl Drop for PrecisionGuard {
+        fn drop(&mut self) {
+            PRECISION.with(|precision| precision.set(self.previous));
+        }
+    }
 
-    r[0][0] = S::Fraction::from_ext(&gram[0][0]);
+    /// Multiple-precision floating-point number, with the precision of the current reduction
+    #[derive(Clone)]
+    struct MpFloat(rug::Float);
 
-    let mut k = 1;
+    impl MpFloat {
+        fn new<T>(val: T) -> Self
+        where
+            rug::Float: rug::Assign<T>,
+        {
+            Self(rug::Float::with_val(precision(), val))
+        }
+    }
 
-    while k < d {
-        size_reduce::<S>(k, d, basis, &mut gram, &mut mu, &mut r, &eta_minus);
+    impl From<u32> for MpFloat {
+        fn from(x: u32) -> Self {
+            Self::new(x)
+        }
+    }
 
-        let delta_criterion = delta_plus.clone() * &r[k - 1][k - 1];
-        let scalar_criterion =
-            (mu[k][k - 1].clone() * &mu[k][k - 1] * &r[k - 1][k - 1]) + &r[k][k];
+    impl Default for MpFloat {
+        fn default() -> Self {
+            Self::new(0)
+        }
+    }
 
-        // Lovazs condition
-        if delta_criterion < scalar_criterion {
-            k += 1;
-        } else {
-            basis.swap(k, k - 1);
+    impl ops::Add<&MpFloat> for MpFloat {
+        type Output = Self;
+        fn add(self, other: &Self) -> Self {
+            Self(self.0 + &other.0)
+        }
+    }
 
-            // Updating Gram matrix
-            for j in 0..d {
-                if j < k {
-                    gram[k][j] = basis[k].dot(&basis[j]);
-                    gram[k - 1][j] = basis[k - 1].dot(&basis[j]);
-                } else {
-                    gram[j][k] = basis[k].dot(&basis[j]);
-                    gram[j][k - 1] = basis[k - 1].dot(&basis[j]);
-                }
-            }
+    impl ops::Sub<&MpFloat> for MpFloat {
+        type Output = Self;
+        fn sub(self, other: &Self) -> Self {
+            Self(self.0 - &other.0)
+        }
+    }
 
-            // Updating mu and r
-            for i in 0..=k {
-                for j in 0..=i {
-                    r[i][j] = S::Fraction::from_ext(&gram[i][j])
-                        - &(0..j)
-                            .map(|index| mu[j][index].clone() * &r[i][index])
-                            .sum::<S::Fraction>();
-                    mu[i][j] = r[i][j].clone() / &r[j][j];
-                }
-            }
+    impl ops::Mul<&MpFloat> for MpFloat {
+        type Output = Self;
+        fn mul(self, other: &Self) -> Self {
+            Self(self.0 * &other.0)
+        }
+    }
 
-            k = max(1, k - 1);
+    impl ops::Div<&MpFloat> for MpFloat {
+        type Output = Self;
+        fn div(self, other: &Self) -> Self {
+            Self(self.0 / &other.0)
         }
     }
-}
 
-/// Performs the `eta`-size-reduction of `basis[k]`
-///
-/// Arguments:
-/// * `k`: Index of the column to be `eta`-size-reduced
-/// * `d`: The basis dimension
-/// * `basis`: A generating matrix for the lattice
-/// * `gram`: Gram matrix of `basis`  
-/// * `mu`: Gram coefficient matrix
-/// * `r`: the r_ij matrix
-/// * `eta`: eta factor of the basis reduction
-///
-/// Note: both `basis` and `gram` are updated by this operation.
-fn size_reduce<S>(
-    k: usize,
-    d: usize,
-    basis: &mut Matrix<S::Integer>,
-    gram: &mut Matrix<S::Integer>,
-    mu: &mut Matrix<S::Fraction>,
-    r: &mut Matrix<S::Fraction>,
-    eta: &S::Fraction,
-) where
-    S: Scalars,
-    S::Integer: Coefficient,
-    S::Fraction: Coefficient,
-    Vector<S::Integer>: Dot<Output = S::Integer>,
-{
-    // Update mu and r
-    for i in 0..=k {
-        r[k][i] = S::Fraction::from_ext(&gram[k][i])
-            - &(0..i)
-                .map(|index| mu[i][index].clone() * &r[k][index])
-                .sum::<S::Fraction>();
-        mu[k][i] = r[k][i].clone() / &r[i][i];
+    impl ops::SubAssign<&MpFloat> for MpFloat {
+        fn sub_assign(&mut self, other: &Self) {
+            self.0 -= &other.0;
+        }
     }
 
-    if (0..k).any(|index| S::abs(mu[k][index].clone()) > *eta) {
-        for i in (0..k).rev() {
-            let x = S::round(&mu[k][i]);
-            basis[k] = basis[k].sub(&basis[i].mulf(&x));
+    impl Sum for MpFloat {
+        fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+            iter.fold(Self::default(), |acc, x| acc + &x)
+        }
+    }
 
-            // Updating Gram matrix
-            for j in 0..d {
-                if j < k {
-                    gram[k][j] = basis[k].dot(&basis[j]);
-                } else {
-                    gram[j][k] = basis[k].dot(&basis[j]);
-                }
-            }
+    impl PartialEq for MpFloat {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
 
-            for j in 0..i {
-                let minus = S::Fraction::from_ext(&x) * &mu[i][j];
-                mu[k][j] -= &minus;
-            }
+    impl PartialOrd for MpFloat {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            self.0.partial_cmp(&other.0)
         }
-        size_reduce::<S>(k, d, basis, gram, mu, r, eta);
     }
-}
 
-pub mod bigl2 {
-    use crate::matrix::Matrix;
-    use crate::scalars::BigNum;
+    impl PartialEq<Integer> for MpFloat {
+        fn eq(&self, other: &Integer) -> bool {
+            self.0 == *other
+        }
+    }
 
-    /// Lattice reduction (L² algorithm)
+    impl PartialOrd<Integer> for MpFloat {
+        fn partial_cmp(&self, other: &Integer) -> Option<Ordering> {
+            self.0.partial_cmp(other)
+        }
+    }
+
+    impl FromExt<&Integer> for MpFloat {
+        fn from_ext(x: &Integer) -> Self {
+            Self::new(x)
+        }
+    }
+
+    impl FromExt<f64> for MpFloat {
+        fn from_ext(x: f64) -> Self {
+            Self::new(x)
+        }
+    }
+
+    impl FromExt<(Integer, Integer)> for MpFloat {
+        fn from_ext((n, d): (Integer, Integer)) -> Self {
+            Self::new(Rational::from((n, d)))
+        }
+    }
+
+    impl FromExt<(i32, i32)> for MpFloat {
+        fn from_ext((n, d): (i32, i32)) -> Self {
+            Self::new(Rational::from((n, d)))
+        }
+    }
+
+    impl Dot for Vector<MpFloat> {
+        type Output = MpFloat;
+        fn dot(&self, other: &Self) -> Self::Output {
+            self.iter()
+                .zip(other)
+                .map(|(coeff_r, coeff_l)| coeff_r.clone() * coeff_l)
+                .sum()
+        }
+    }
+
+    struct MpNum;
+    impl Scalars for MpNum {
+        type Integer = Integer;
+        type Fraction = MpFloat;
+
+        const REORTHOGONALIZE: bool = true;
+
+        fn round(f: &Self::Fraction) -> Self::Integer {
+            f.0.to_integer()
+                .expect("cannot round a non-finite coefficient")
+        }
+
+        fn can_round(f: &Self::Fraction) -> bool {
+            f.0.is_finite()
+        }
+
+        fn round_div(n: Self::Integer, d: Self::Integer) -> Self::Integer {
+            BigNum::round_div(n, d)
+        }
+
+        fn abs(f: Self::Fraction) -> Self::Fraction {
+            MpFloat(f.0.abs())
+        }
+
+        fn bit_length(n: &Self::Integer) -> u32 {
+            n.significant_bits()
+        }
+    }
+
+    /// Precision sufficient for the L² reduction of `dimension` vectors
     ///
-    /// This implementation uses `rug::Integers` and `rug::Rationnal` for the underlying arithmetic operations.
+    /// Nguyen and Stehlé [NS09] prove that the L² algorithm is correct with floating-point
+    /// numbers of `d * log2((1 + eta)² / (delta - eta²)) + o(d)` bits for `d` vectors (about
+    /// `1.6 * d` bits for `eta` close to `1/2` and `delta` close to `1`). This returns that
+    /// bound, with `log2(d) + 16` bits for the `o(d)` term, and at least 53 bits.
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn precision_bound(dimension: usize, eta: f64, delta: f64) -> u32 {
+        super::check_parameters(eta, delta);
+        let rho = (1. + eta) * (1. + eta) / (delta - eta * eta);
+        let d = dimension.max(1) as f64;
+        let bits = d * rho.log2() + d.log2() + 16.;
+        (bits.ceil() as u32).max(53)
+    }
+
+    /// Lattice reduction (L² algorithm) with multiple-precision floating-point numbers
+    ///
+    /// Same as `lattice_reduce_prec`, with the precision given by `precision_bound` for the
+    /// number of vectors of `basis`.
     ///
     /// Arguments:
     ///  * basis: A generating matrix for the lattice
     ///  * eta: eta factor of the basis reduction
     ///  * delta: delta factor of the basis reduction
     ///
-    /// The basis is reduced in-place.
+    /// The basis is reduced in-place. The zero vectors coming from linear dependencies
+    /// are moved to the end of the basis, and their number is returned.
     ///
     /// # Panics
-    /// if delta <= 1/4 or delta >= 1  
-    /// if eta <= 1/2 or eta > sqrt(delta)
-    pub fn lattice_reduce(basis: &mut Matrix<rug::Integer>, eta: f64, delta: f64) {
-        super::lattice_reduce::<BigNum>(basis, eta, delta)
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)  
+    /// if the precision given by `precision_bound` turns out to be insufficient (see
+    /// `ReductionError::Precision`)
+    pub fn lattice_reduce(basis: &mut Matrix<Integer>, eta: f64, delta: f64) -> usize {
+        let (n, _) = basis.dimensions();
+        lattice_reduce_prec(basis, eta, delta, precision_bound(n, eta, delta))
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Lattice reduction (L² algorithm) with multiple-precision floating-point numbers
+    ///
+    /// The basis is kept exact (`rug::Integer`), and the Gram-Schmidt orthogonalisation is
+    /// computed with `rug::Float`s of `precision_bits` bits. This is much faster than the
+    /// exact rational arithmetic of `bigl2`, and unlike `l2f` it can handle coefficients
+    /// above `2^53` when the precision is large enough.
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice
+    ///  * eta: eta factor of the basis reduction
+    ///  * delta: delta factor of the basis reduction
+    ///  * precision_bits: precision of the floating-point numbers
+    ///
+    /// The basis is reduced in-place. The zero vectors coming from linear dependencies
+    /// are moved to the end of the basis, and their number is returned. When the precision
+    /// is too low, the reduction stops with `ReductionError::Precision`, as soon as the
+    /// Gram-Schmidt coefficients are not finite or the size reduction of a vector does not
+    /// converge (the partially reduced basis then still generates the same lattice).
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)  
+    /// if precision_bits is not a valid MPFR precision
+    pub fn lattice_reduce_prec(
+        basis: &mut Matrix<Integer>,
+        eta: f64,
+        delta: f64,
+        precision_bits: u32,
+    ) -> Result<usize, super::ReductionError> {
+        let params = super::ReductionParams::default()
+            .eta(eta)
+            .delta(delta)
+            .max_size_reduction_passes(super::SIZE_REDUCTION_PASSES);
+        lattice_reduce_with(basis, &params, precision_bits)
+    }
+
+    /// Lattice reduction (L² algorithm) with a set of parameters and a given precision
+    ///
+    /// Stops with `ReductionError::IterationLimit` when the maximum number of iterations
+    /// of `params` is reached.
+    pub(crate) fn lattice_reduce_with(
+        basis: &mut Matrix<Integer>,
+        params: &super::ReductionParams,
+        precision_bits: u32,
+    ) -> Result<usize, super::ReductionError> {
+        assert!(
+            rug::float::prec_min() <= precision_bits && precision_bits <= rug::float::prec_max()
+        );
+        let _guard = PrecisionGuard::new(precision_bits);
+        super::lattice_reduce_with::<MpNum>(basis, params)
     }
 }
 
-pub mod l2f {
+pub mod l2i64 {
+    use super::{move_to_end, ReductionError};
     use crate::matrix::Matrix;
-    use crate::scalars::Float;
+    use crate::vector::{CheckedDot, VectorI64};
 
-    /// Lattice reduction (L² algorithm)
+    use std::cmp::max;
+
+    /// Lattice reduction (L² algorithm) with 64-bit integers
     ///
-    /// This implementation uses platform double floating-point numbers (IEEE 754)
-    /// for the underlying arithmetic operations.
+    /// This implementation stores the basis in `i64`, the Gram matrix in `i128` and uses
+    /// platform double floating-point numbers for the Gram-Schmidt coefficients. It is much
+    /// faster than `bigl2::lattice_reduce` on small lattices, and every integer operation is
+    /// checked: the reduction stops with `ReductionError::Overflow` as soon as an intermediate
+    /// value does not fit. This allows to start with the fast path, and escalate to exact
+    /// arithmetic only when needed:
+    ///
+    /// ```rust
+    /// use lll_rs::l2::{bigl2, l2i64};
+    /// use lll_rs::matrix::Matrix;
+    /// use lll_rs::vector::VectorI64;
+    ///
+    /// let mut basis: Matrix<i64> = Matrix::init(2, 2);
+    /// basis[0] = VectorI64::from_vector(vec![i64::MAX, i64::MAX]);
+    /// basis[1] = VectorI64::from_vector(vec![i64::MAX, 1]);
+    ///
+    /// if l2i64::lattice_reduce(&mut basis, 0.501, 0.998).is_err() {
+    ///     let mut big: Matrix<rug::Integer> = Matrix::init(2, 2);
+    ///     for i in 0..2 {
+    ///         big[i] = basis[i].to_big();
+    ///     }
+    ///     bigl2::lattice_reduce(&mut big, 0.501, 0.998);
+    /// }
+    /// ```
     ///
     /// Arguments:
     ///  * basis: A generating matrix for the lattice
     ///  * eta: eta factor of the basis reduction
     ///  * delta: delta factor of the basis reduction
     ///
+    /// The basis is reduced in-place. The zero vectors coming from linear dependencies
+    /// are moved to the end of the basis, and their number is returned. The reduction
+    /// stops with `ReductionError::Precision` when the size reduction of a vector does not
+    /// converge with the `f64` Gram-Schmidt coefficients. On overflow or loss of
+    /// precision, the basis is left partially reduced (it still generates the same
+    /// lattice).
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if eta < 1/2 or eta >= sqrt(delta)
+    pub fn lattice_reduce(
+        basis: &mut Matrix<i64>,
+        eta: f64,
+        delta: f64,
+    ) -> Result<usize, ReductionError> {
+        super::check_parameters(eta, delta);
+        // Variables
+        let (n, _) = basis.dimensions();
+        let mut r: Matrix<f64> = Matrix::init(n, n);
+        let mut mu: Matrix<f64> = Matrix::init(n, n);
+
+        // Moving the zero vectors to the end of the basis
+        let mut d = n;
+        let mut i = 0;
+        while i < d {
+            if basis[i].iter().all(|&c| c == 0) {
+                move_to_end(basis, i, d);
+                d -= 1;
+            } else {
+                i += 1;
+            }
+        }
+        if d == 0 {
+            return Ok(n);
+        }
+
+        // Computing Gram matrix (lower triangular)
+        let mut gram: Matrix<i128> = Matrix::init(n, n);
+        for i in 0..d {
+            for j in 0..=i {
+                gram[i][j] = checked_dot(&basis[i], &basis[j])?;
+            }
+        }
+
+        let eta_minus = super::eta_bar(eta).to_f64();
+        let delta_plus = super::delta_bar(delta).to_f64();
+
+        r[0][0] = gram[0][0] as f64;
+
+        let mut k = 1;
+
+        while k < d {
+            size_reduce(k, d, basis, &mut gram, &mut mu, &mut r, eta_minus)?;
+
+            // Linear dependency: the size-reduced vector is zero
+            if gram[k][k] == 0 {
+                move_to_end(basis, k, d);
+                d -= 1;
+                for i in k..d {
+                    for j in 0..=i {
+                        gram[i][j] = checked_dot(&basis[i], &basis[j])?;
+                    }
+                }
+                continue;
+            }
+
+            // Lovazs condition
+            if super::lovasz_ok(&r[k - 1][k - 1], &r[k][k], &mu[k][k - 1], &delta_plus) {
+                k += 1;
+            } else {
+                basis.swap(k, k - 1);
+
+                // Updating Gram matrix
+                for j in 0..d {
+                    if j < k {
+                        gram[k][j] = checked_dot(&basis[k], &basis[j])?;
+                        gram[k - 1][j] = checked_dot(&basis[k - 1], &basis[j])?;
+                    } else {
+                        gram[j][k] = checked_dot(&basis[k], &basis[j])?;
+                        gram[j][k - 1] = checked_dot(&basis[k - 1], &basis[j])?;
+                    }
+                }
+
+                // Updating mu and r
+                for i in 0..=k {
+                    update_gso(i, &gram, &mut mu, &mut r);
+                }
+
+                k = max(1, k - 1);
+            }
+        }
+
+        Ok(n - d)
+    }
+
+    fn checked_dot(u: &VectorI64, v: &VectorI64) -> Result<i128, ReductionError> {
+        u.checked_dot(v).ok_or(ReductionError::Overflow)
+    }
+
+    /// Recompute the row `i` of `mu` and `r` from the Gram matrix
+    fn update_gso(i: usize, gram: &Matrix<i128>, mu: &mut Matrix<f64>, r: &mut Matrix<f64>) {
+        for j in 0..=i {
+            r[i][j] =
+                gram[i][j] as f64 - (0..j).map(|index| mu[j][index] * r[i][index]).sum::<f64>();
+            mu[i][j] = if r[j][j] == 0. { 0. } else { r[i][j] / r[j][j] };
+        }
+    }
+
+    /// Performs the `eta`-size-reduction of `basis[k]`, with checked integer operations
+    ///
+    /// `basis` and `gram` are only updated when no overflow occurs. Fails with
+    /// `ReductionError::Precision` if the vector is still not size-reduced after
+    /// `SIZE_REDUCTION_PASSES` passes.
+    fn size_reduce(
+        k: usize,
+        d: usize,
+        basis: &mut Matrix<i64>,
+        gram: &mut Matrix<i128>,
+        mu: &mut Matrix<f64>,
+        r: &mut Matrix<f64>,
+        eta: f64,
+    ) -> Result<(), ReductionError> {
+        let mut passes = 0;
+        loop {
+            update_gso(k, gram, mu, r);
+
+            if !(0..k).any(|index| mu[k][index].abs() > eta) {
+                return Ok(());
+            }
+            if passes >= super::SIZE_REDUCTION_PASSES {
+                return Err(ReductionError::Precision);
+            }
+            passes += 1;
+
+            for i in (0..k).rev() {
+                let x = mu[k][i].round();
+                if x < i64::MIN as f64 || x >= i64::MAX as f64 {
+                    return Err(ReductionError::Overflow);
+                }
+                let x = x as i64;
+                let reduced = basis[k]
+                    .iter()
+                    .zip(&basis[i])
+                    .map(|(&b_k, &b_i)| b_i.checked_mul(x).and_then(|p| b_k.checked_sub(p)))
+                    .collect::<Option<VectorI64>>()
+                    .ok_or(ReductionError::Overflow)?;
+
+                // Updating Gram matrix
+                let products = (0..d)
+                    .map(|j| {
+                        if j == k {
+                            checked_dot(&reduced, &reduced)
+                        } else {
+                            checked_dot(&reduced, &basis[j])
+                        }
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                basis[k] = reduced;
+                for (j, product) in products.into_iter().enumerate() {
+                    if j < k {
+                        gram[k][j] = product;
+                    } else {
+                        gram[j][k] = product;
+                    }
+                }
+
+                for j in 0..i {
+                    let minus = x as f64 * mu[i][j];
+                    mu[k][j] -= minus;
+                }
+            }
+        }
+    }
+}
+
+pub mod gauss {
+    use crate::matrix::Matrix;
+    use crate::scalars::{BigNum, Scalars};
+    use crate::vector::{BigVector, Dot};
+
+    /// Lattice reduction of a 2-dimensional lattice (Gauss-Lagrange algorithm)
+    ///
+    /// The output basis is Gauss-reduced: `basis[0]` is a shortest nonzero vector of
+    /// the lattice, `||basis[0]|| <= ||basis[1]||` and `|<basis[0], basis[1]>| <= ||basis[0]||² / 2`.
+    ///
+    /// Arguments:
+    ///  * basis: A generating matrix for the lattice, made of exactly 2 vectors
+    ///
     /// The basis is reduced in-place.
     ///
     /// # Panics
-    /// if delta <= 1/4 or delta >= 1  
-    /// if eta <= 1/2 or eta > sqrt(delta)
-    pub fn lattice_reduce(basis: &mut Matrix<f64>, eta: f64, delta: f64) {
-        super::lattice_reduce::<Float>(basis, eta, delta)
+    /// if `basis` does not contain exactly 2 linearly independent vectors
+    pub fn reduce_2d(basis: &mut Matrix<rug::Integer>) {
+        assert_eq!(basis.dimensions().0, 2);
+
+        let (b_0, b_1) = gauss_reduce(basis[0].clone(), basis[1].clone());
+        basis[0] = b_0;
+        basis[1] = b_1;
+    }
+
+    /// Gauss-Lagrange reduction of the lattice generated by two vectors
+    ///
+    /// Returns a Gauss-reduced basis `(c_0, c_1)` of the lattice generated by `b_0` and
+    /// `b_1` (see `reduce_2d`): `c_0` is a shortest nonzero vector of the lattice, and
+    /// `c_1` is a shortest vector among those linearly independent of `c_0`. This is much
+    /// cheaper than the L² algorithm for 2 vectors, whatever their dimension.
+    ///
+    /// # Panics
+    /// if `b_0` and `b_1` are linearly dependent
+    pub fn gauss_reduce(b_0: BigVector, b_1: BigVector) -> (BigVector, BigVector) {
+        let (mut b_0, mut b_1) = (b_0, b_1);
+        let mut norm_0 = b_0.dot(&b_0);
+        let mut norm_1 = b_1.dot(&b_1);
+        if norm_0 > norm_1 {
+            std::mem::swap(&mut b_0, &mut b_1);
+            std::mem::swap(&mut norm_0, &mut norm_1);
+        }
+
+        loop {
+            assert!(norm_0 != 0, "the vectors must be linearly independent");
+            let x = BigNum::round_div(b_0.dot(&b_1), norm_0.clone());
+            b_1 = b_1.sub(&b_0.mulf(&x));
+            norm_1 = b_1.dot(&b_1);
+
+            if norm_1 >= norm_0 {
+                return (b_0, b_1);
+            }
+            std::mem::swap(&mut b_0, &mut b_1);
+            std::mem::swap(&mut norm_0, &mut norm_1);
+        }
     }
 }
@@ -52,19 +52,109 @@
 //!
 extern crate rug;
 
+pub mod bkz;
+pub mod complex;
+pub mod cvp;
+pub mod dual;
+pub mod error;
+pub mod gaussian;
+pub mod generate;
+pub mod gram;
+pub mod hnf;
+pub mod io;
+pub mod kernel;
 pub mod l2;
 pub mod lll;
 pub mod matrix;
+pub mod metrics;
+pub mod reduce;
+pub mod relation;
+pub mod segment;
+pub mod sieve;
+pub mod slide;
+pub mod solve;
+pub mod svp;
 pub mod vector;
+pub mod verify;
 mod scalars;
 
+use rug::Integer;
+
+/// Lattice reduction (L² algorithm) of a basis given as nested vectors
+///
+/// The simplest entry point of the library: each inner vector is a vector of the basis.
+///
+/// ```rust
+/// use rug::Integer;
+///
+/// let rows = vec![
+///     vec![Integer::from(1), Integer::from(1345)],
+///     vec![Integer::from(0), Integer::from(35)],
+/// ];
+/// let reduced = lll_rs::reduce_int(rows, 0.51, 0.99);
+/// assert_eq!(reduced[0], vec![Integer::from(-2), Integer::from(5)]);
+/// ```
+///
+/// Arguments:
+///  * rows: the vectors of the basis
+///  * eta: eta factor of the basis reduction
+///  * delta: delta factor of the basis reduction
+///
+/// Returns the reduced basis. The zero vectors coming from linear dependencies are at the
+/// end.
+///
+/// # Panics
+/// if the vectors do not all have the same length  
+/// if delta < 1/4 or delta >= 1  
+/// if eta < 1/2 or eta >= sqrt(delta)
+pub fn reduce_int(rows: Vec<Vec<Integer>>, eta: f64, delta: f64) -> Vec<Vec<Integer>> {
+    try_reduce_int(rows, eta, delta).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Lattice reduction (L² algorithm) of a basis given as nested vectors, or an error
+///
+/// Same as `reduce_int`, returning `LatticeError::DimensionMismatch` (with the lengths of
+/// the first vector and of the first vector of a different length) if the vectors do not
+/// all have the same length, and `LatticeError::InvalidDelta` or `LatticeError::InvalidEta`
+/// if the factors are invalid, instead of panicking.
+pub fn try_reduce_int(
+    rows: Vec<Vec<Integer>>,
+    eta: f64,
+    delta: f64,
+) -> Result<Vec<Vec<Integer>>, error::LatticeError> {
+    let dimension = rows.first().map_or(0, Vec::len);
+    if let Some(row) = rows.iter().find(|row| row.len() != dimension) {
+        return Err(error::LatticeError::DimensionMismatch(dimension, row.len()));
+    }
+
+    let mut basis = matrix::Matrix::from_columns(
+        rows.into_iter()
+            .map(vector::BigVector::from_vector)
+            .collect(),
+    );
+    l2::bigl2::try_lattice_reduce(&mut basis, eta, delta)?;
+
+    let (n, _) = basis.dimensions();
+    Ok((0..n).map(|i| basis[i].iter().cloned().collect()).collect())
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
-        l2::{bigl2, l2f},
-        lll::{biglll, lllf},
+        bkz::{self, BkzParams},
+        complex::Complex,
+        cvp, dual,
+        error::LatticeError,
+        generate, gram, hnf, io, kernel,
+        l2::{self, bigl2, gauss, l2f, l2i64, ReductionBudget, ReductionError, ReductionParams},
+        lll::{biglll, intlll, lllf},
         matrix::Matrix,
-        vector::{BigVector, VectorF},
+        metrics, reduce, relation, segment, sieve, slide, solve, svp,
+        vector::{
+            BigVector, CheckedDot, DimensionMismatch, Dot, Empty, InnerProduct, RationalVector,
+            Scale, VectorC, VectorF, VectorI64,
+        },
+        verify,
     };
 
     use rug::{Assign, Integer};
@@ -242,4 +332,2579 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_distance_to_lattice() {
+        type I = Integer;
+
+        let mut basis: Matrix<I> = Matrix::init(3, 4);
+        basis[0] = BigVector::from_vector(vec![I::from(1), I::from(0), I::from(0), I::from(1345)]);
+        basis[1] = BigVector::from_vector(vec![I::from(0), I::from(1), I::from(0), I::from(35)]);
+        basis[2] = BigVector::from_vector(vec![I::from(0), I::from(0), I::from(1), I::from(154)]);
+
+        // 2 * b_0 - b_1 + 3 * b_2
+        let point =
+            BigVector::from_vector(vec![I::from(2), I::from(-1), I::from(3), I::from(3117)]);
+        assert_eq!(cvp::distance_to_lattice(&basis, &point), 0);
+
+        // Skewed basis of 2Z x 3Z
+        let mut basis: Matrix<I> = Matrix::init(2, 2);
+        basis[0] = BigVector::from_vector(vec![I::from(2), I::from(0)]);
+        basis[1] = BigVector::from_vector(vec![I::from(2), I::from(3)]);
+
+        let target = BigVector::from_vector(vec![I::from(5), I::from(1)]);
+        assert_eq!(cvp::distance_to_lattice(&basis, &target), 2);
+
+        let target = BigVector::from_vector(vec![I::from(-3), I::from(5)]);
+        assert_eq!(cvp::distance_to_lattice(&basis, &target), 2);
+    }
+
+    #[test]
+    fn test_conversions() {
+        type I = Integer;
+
+        let v = BigVector::from_vector(vec![I::from(-3), I::from(0), I::from(1) << 40]);
+        let f = v.to_float();
+        assert_eq!(f[2], (1u64 << 40) as f64);
+        let w = f.round_to_big();
+        for i in 0..v.dimension() {
+            assert_eq!(v[i], w[i]);
+        }
+
+        let r = v.to_rational();
+        assert_eq!(r[0], rug::Rational::from(-3));
+
+        let f = VectorF::from_vector(vec![0.5, 1.5, -2.5, 2.4]);
+        let w = f.round_to_big();
+        assert_eq!(w[0], 0);
+        assert_eq!(w[1], 2);
+        assert_eq!(w[2], -2);
+        assert_eq!(w[3], 2);
+
+        let mut basis: Matrix<I> = Matrix::init(2, 2);
+        basis[0] = BigVector::from_vector(vec![I::from(1), I::from(2)]);
+        basis[1] = BigVector::from_vector(vec![I::from(3), I::from(-4)]);
+        let back = basis.to_float().round_to_big();
+        for i in 0..2 {
+            for j in 0..2 {
+                assert_eq!(basis[i][j], back[i][j]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        type I = Integer;
+
+        let mut basis: Matrix<I> = Matrix::init(3, 4);
+        basis[0] = BigVector::from_vector(vec![I::from(1), I::from(0), I::from(0), I::from(1345)]);
+        basis[1] = BigVector::from_vector(vec![I::from(0), I::from(-1), I::from(0), I::from(35)]);
+        basis[2] = BigVector::from_vector(vec![I::from(0), I::from(0), I::from(1), I::from(154)]);
+        assert_eq!(
+            basis.to_string(),
+            "[1  0 0 1345]\n[0 -1 0   35]\n[0  0 1  154]"
+        );
+
+        let basis = Matrix::<f64>::from_columns(vec![
+            VectorF::from_vector(vec![1.5, 0.]),
+            VectorF::from_vector(vec![-2., 10.25]),
+        ]);
+        assert_eq!(basis.to_string(), "[1.5     0]\n[ -2 10.25]");
+    }
+
+    #[test]
+    fn test_norms() {
+        let v = VectorF::from_vector(vec![3., -4., 1.]);
+        assert_eq!(v.norm_l1(), 8.);
+        assert_eq!(v.norm_linf(), 4.);
+
+        let v = BigVector::from_vector(vec![Integer::from(3), Integer::from(-4), Integer::from(1)]);
+        assert_eq!(v.norm_l1(), 8);
+        assert_eq!(v.norm_linf(), 4);
+    }
+
+    #[test]
+    fn test_gauss_reduce_2d() {
+        type I = Integer;
+
+        // Skewed basis of a lattice of determinant 4
+        let mut basis: Matrix<I> = Matrix::init(2, 2);
+        basis[0] = BigVector::from_vector(vec![I::from(1), (I::from(1) << 20) + 3]);
+        basis[1] = BigVector::from_vector(vec![I::from(3), (I::from(3) << 20) + 5]);
+        let mut lll_basis = basis.clone();
+
+        gauss::reduce_2d(&mut basis);
+        biglll::lattice_reduce(&mut lll_basis);
+
+        let norm_0 = basis[0].dot(&basis[0]);
+        let norm_1 = basis[1].dot(&basis[1]);
+        let scalar = basis[0].dot(&basis[1]);
+        assert!(norm_0 <= norm_1);
+        assert!(I::from(scalar.abs_ref()) * 2 <= norm_0);
+        assert!(norm_0 <= lll_basis[0].dot(&lll_basis[0]));
+
+        let det = I::from(&basis[0][0] * &basis[1][1]) - I::from(&basis[0][1] * &basis[1][0]);
+        assert_eq!(det.abs(), 4);
+    }
+
+    #[test]
+    fn test_dual_basis() {
+        type I = Integer;
+
+        let mut basis: Matrix<I> = Matrix::init(3, 3);
+        basis[0] = BigVector::from_vector(vec![I::from(2), I::from(1), I::from(0)]);
+        basis[1] = BigVector::from_vector(vec![I::from(1), I::from(3), I::from(1)]);
+        basis[2] = BigVector::from_vector(vec![I::from(0), I::from(-1), I::from(4)]);
+
+        let dual = dual::dual_basis(&basis);
+        let primal = basis.to_rational();
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { 1 } else { 0 };
+                assert_eq!(dual[i].dot(&primal[j]), expected);
+            }
+        }
+
+        let singular = Matrix::<rug::Rational>::from_columns(vec![
+            primal[0].clone(),
+            primal[0].clone(),
+            primal[1].clone(),
+        ]);
+        assert!(singular.inverse().is_none());
+    }
+
+    #[test]
+    fn test_hermite_normal_form() {
+        type I = Integer;
+
+        let mut matrix: Matrix<I> = Matrix::init(3, 4);
+        matrix[0] = BigVector::from_vector(vec![I::from(2), I::from(4), I::from(6), I::from(8)]);
+        matrix[1] = BigVector::from_vector(vec![I::from(3), I::from(1), I::from(-5), I::from(2)]);
+        // Rank-deficient: matrix[2] = matrix[0] + 2 * matrix[1]
+        matrix[2] = BigVector::from_vector(vec![I::from(8), I::from(6), I::from(-4), I::from(12)]);
+
+        let hnf = hnf::hermite_normal_form(&matrix);
+        assert_eq!(hnf.dimensions(), (2, 4));
+        assert_eq!(hnf.to_string(), "[1  7 17 14]\n[0 10 28 20]");
+
+        // Unimodular row operations
+        let mut other = matrix.clone();
+        other.swap(0, 2);
+        other[1] = other[1].add(&other[0].mulf(&I::from(-3)));
+        other[2] = other[2].sub(&other[1].mulf(&I::from(5)));
+        other[0] = other[0].mulf(&I::from(-1));
+
+        let other_hnf = hnf::hermite_normal_form(&other);
+        assert_eq!(other_hnf.to_string(), hnf.to_string());
+    }
+
+    #[test]
+    fn test_bigl2_dependent() {
+        type I = Integer;
+
+        let mut basis: Matrix<I> = Matrix::init(3, 4);
+        basis[0] = BigVector::from_vector(vec![I::from(1), I::from(0), I::from(0), I::from(1345)]);
+        basis[1] = BigVector::from_vector(vec![I::from(0), I::from(1), I::from(0), I::from(35)]);
+        basis[2] = basis[0].add(&basis[1]);
+
+        let zeros = bigl2::lattice_reduce(&mut basis, 0.501, 0.998);
+        assert_eq!(zeros, 1);
+        assert!(basis[0].dot(&basis[0]) > 0);
+        assert!(basis[1].dot(&basis[1]) > 0);
+        assert_eq!(basis[2].dot(&basis[2]), 0);
+
+        let mut basis: Matrix<f64> = Matrix::init(3, 2);
+        basis[0] = VectorF::from_vector(vec![0., 0.]);
+        basis[1] = VectorF::from_vector(vec![3., 5.]);
+        basis[2] = VectorF::from_vector(vec![6., 10.]);
+
+        let zeros = l2f::lattice_reduce(&mut basis, 0.501, 0.998);
+        assert_eq!(zeros, 2);
+        assert_eq!(basis[0][0].abs(), 3.);
+        assert_eq!(basis[0][1].abs(), 5.);
+    }
+
+    #[test]
+    fn test_bigl2_small_stack() {
+        type I = Integer;
+
+        // Size-reducing the last vector takes several sweeps
+        let reduced = std::thread::Builder::new()
+            .stack_size(128 * 1024)
+            .spawn(|| {
+                let mut basis: Matrix<I> = Matrix::init(3, 3);
+                basis[0] = BigVector::from_vector(vec![I::from(1), I::from(0), I::from(0)]);
+                basis[1] = BigVector::from_vector(vec![I::from(1) << 200, I::from(1), I::from(0)]);
+                basis[2] =
+                    BigVector::from_vector(vec![I::from(3) << 400, I::from(5) << 200, I::from(1)]);
+                bigl2::lattice_reduce(&mut basis, 0.501, 0.998);
+                basis
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+
+        for i in 0..3 {
+            assert_eq!(reduced[i].norm_linf(), 1);
+        }
+    }
+
+    #[test]
+    fn test_bigl2_until() {
+        type I = Integer;
+
+        let mut basis: Matrix<I> = Matrix::init(3, 4);
+        basis[0] = BigVector::from_vector(vec![I::from(1), I::from(0), I::from(0), I::from(1345)]);
+        basis[1] = BigVector::from_vector(vec![I::from(0), I::from(1), I::from(0), I::from(35)]);
+        basis[2] = BigVector::from_vector(vec![I::from(0), I::from(0), I::from(1), I::from(154)]);
+
+        let mut reduced = basis.clone();
+        let params = ReductionParams::default().eta(0.501).delta(0.998);
+        let (_, stats) = bigl2::lattice_reduce_with_stats(&mut reduced, &params).unwrap();
+        let shortest = reduced[0].dot(&reduced[0]);
+
+        // Easy target: the reduction stops before the basis is fully reduced
+        let mut early = basis.clone();
+        let target = I::from(1000);
+        assert!(bigl2::lattice_reduce_until(
+            &mut early, 0.501, 0.998, &target
+        ));
+        assert!(early[0].dot(&early[0]) <= target);
+        assert!(early.to_string() != reduced.to_string());
+        let (reached, swaps) = bigl2::reduce_until(&mut basis.clone(), 0.501, 0.998, &target);
+        assert!(reached);
+        assert!(swaps < stats.swaps);
+
+        // Unreachable target: the basis is fully reduced
+        let mut full = basis.clone();
+        let target = shortest - 1;
+        assert!(!bigl2::lattice_reduce_until(
+            &mut full, 0.501, 0.998, &target
+        ));
+        assert_eq!(full.to_string(), reduced.to_string());
+        let (reached, swaps) = bigl2::reduce_until(&mut basis.clone(), 0.501, 0.998, &target);
+        assert!(!reached);
+        assert_eq!(stats.swaps, swaps);
+    }
+
+    #[test]
+    fn test_l2_callback() {
+        let mut basis: Matrix<f64> = Matrix::init(3, 4);
+        basis[0] = VectorF::from_vector(vec![1., 0., 0., 1345.]);
+        basis[1] = VectorF::from_vector(vec![0., 1., 0., 35.]);
+        basis[2] = VectorF::from_vector(vec![0., 0., 1., 154.]);
+        let original = basis.clone();
+
+        let mut calls = Vec::new();
+        let zeros = l2f::lattice_reduce_with_callback(&mut basis, 0.501, 0.998, &mut |k, d| {
+            calls.push((k, d));
+            true
+        });
+        assert_eq!(zeros, Ok(0));
+        assert!(!calls.is_empty());
+        assert!(calls.iter().all(|&(k, d)| 1 <= k && k <= d && d == 3));
+        assert_eq!(calls.last(), Some(&(3, 3)));
+        // Each iteration moves to the next vector, or back to the previous one after a swap
+        assert!(matches!(calls[0].0, 1 | 2));
+        for pair in calls.windows(2) {
+            let ((k, _), (next, _)) = (pair[0], pair[1]);
+            assert!(next == k + 1 || next == (k - 1).max(1), "{:?}", calls);
+        }
+
+        // Aborting at the first call
+        let mut aborted = original.clone();
+        let mut count = 0;
+        let result = l2f::lattice_reduce_with_callback(&mut aborted, 0.501, 0.998, &mut |_, _| {
+            count += 1;
+            false
+        });
+        assert_eq!(result, Err(ReductionError::Cancelled));
+        assert_eq!(count, 1);
+        assert!(aborted.to_string() != basis.to_string());
+    }
+
+    #[test]
+    fn test_generate() {
+        let basis = generate::random_basis(5, 64, 42);
+        assert_eq!(basis.dimensions(), (5, 5));
+        assert_eq!(
+            basis.to_string(),
+            generate::random_basis(5, 64, 42).to_string()
+        );
+        assert!(basis.to_string() != generate::random_basis(5, 64, 43).to_string());
+        for i in 0..5 {
+            for j in 0..5 {
+                assert!(basis[i][j].significant_bits() <= 64);
+            }
+        }
+
+        let weights = [Integer::from(3), Integer::from(5), Integer::from(7)];
+        let basis = generate::knapsack_lattice(&weights, &Integer::from(10));
+        assert_eq!(
+            basis.to_string(),
+            "[1 0 0   9]\n[0 1 0  15]\n[0 0 1  21]\n[0 0 0 -30]"
+        );
+    }
+
+    #[test]
+    fn test_subset_sum() {
+        let weights: Vec<Integer> = [
+            1_040_772_936_760u64,
+            434_439_589_175,
+            54_335_349_840,
+            902_254_243_635,
+            105_380_810_795,
+            641_520_749_048,
+            996_681_516_149,
+            234_107_653_877,
+            94_650_323_160,
+            461_423_994_714,
+        ]
+        .iter()
+        .map(|&w| Integer::from(w))
+        .collect();
+        let expected = vec![
+            true, false, true, true, false, false, true, false, true, false,
+        ];
+
+        let target = Integer::from(3_088_694_369_544u64);
+        assert_eq!(solve::subset_sum(&weights, &target), Some(expected));
+        assert_eq!(solve::subset_sum(&weights, &Integer::from(1)), None);
+    }
+
+    #[test]
+    fn test_integer_relation() {
+        let a = std::f64::consts::PI;
+        let b = std::f64::consts::SQRT_2;
+        let c = 2. * a + 3. * b;
+
+        let relation = relation::find_integer_relation(&[a, b, c], 1_000_000_000_000).unwrap();
+        let sign = if relation[2] < 0 { 1 } else { -1 };
+        assert_eq!(relation[0].clone() * sign, 2);
+        assert_eq!(relation[1].clone() * sign, 3);
+        assert_eq!(relation[2].clone() * sign, -1);
+
+        assert_eq!(
+            relation::find_integer_relation(&[a, b], 1_000_000_000_000),
+            None
+        );
+    }
+
+    #[test]
+    fn test_integer_kernel() {
+        type I = Integer;
+
+        let mut matrix: Matrix<I> = Matrix::init(2, 3);
+        matrix[0] = BigVector::from_vector(vec![I::from(1), I::from(2), I::from(3)]);
+        matrix[1] = BigVector::from_vector(vec![I::from(4), I::from(5), I::from(6)]);
+
+        let kernel = kernel::integer_kernel(&matrix);
+        assert_eq!(kernel.dimensions(), (1, 3));
+        let sign = if kernel[0][0] < 0 { -1 } else { 1 };
+        assert_eq!(
+            format!("{:?}", kernel[0].mulf(&I::from(sign))),
+            "[1, -2, 1]"
+        );
+
+        let mut matrix: Matrix<I> = Matrix::init(2, 2);
+        matrix[0] = BigVector::from_vector(vec![I::from(1), I::from(2)]);
+        matrix[1] = BigVector::from_vector(vec![I::from(3), I::from(4)]);
+        assert_eq!(kernel::integer_kernel(&matrix).dimensions(), (0, 2));
+    }
+
+    #[test]
+    fn test_lattice_reduce_gram() {
+        let basis = generate::random_basis(4, 20, 7);
+        let mut gram_matrix: Matrix<Integer> = Matrix::init(4, 4);
+        for i in 0..4 {
+            for j in 0..4 {
+                gram_matrix[i][j] = basis[i].dot(&basis[j]);
+            }
+        }
+
+        let transform = gram::lattice_reduce_gram(&mut gram_matrix, 0.501, 0.998);
+        let mut reduced = basis.clone();
+        bigl2::lattice_reduce(&mut reduced, 0.501, 0.998);
+
+        for i in 0..4 {
+            let mut combination = BigVector::init(4);
+            for j in 0..4 {
+                combination = combination.add(&basis[j].mulf(&transform[i][j]));
+            }
+            assert_eq!(format!("{:?}", combination), format!("{:?}", reduced[i]));
+            for j in 0..4 {
+                assert_eq!(gram_matrix[i][j], reduced[i].dot(&reduced[j]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_l2_params() {
+        let params = ReductionParams::default();
+        assert_eq!(params, ReductionParams::default().eta(0.51).delta(0.99));
+
+        let basis = generate::random_basis(4, 30, 1);
+        let mut expected = basis.clone();
+        bigl2::lattice_reduce(&mut expected, 0.501, 0.998);
+
+        let mut reduced = basis.clone();
+        let params = ReductionParams::default().eta(0.501).delta(0.998);
+        assert_eq!(bigl2::lattice_reduce_with(&mut reduced, &params), Ok(0));
+        assert_eq!(reduced.to_string(), expected.to_string());
+
+        let mut basis: Matrix<f64> = Matrix::init(3, 4);
+        basis[0] = VectorF::from_vector(vec![1., 0., 0., 1345.]);
+        basis[1] = VectorF::from_vector(vec![0., 1., 0., 35.]);
+        basis[2] = VectorF::from_vector(vec![0., 0., 1., 154.]);
+        let mut expected = basis.clone();
+        l2f::lattice_reduce(&mut expected, 0.51, 0.99);
+        assert_eq!(
+            l2f::lattice_reduce_with(&mut basis, &ReductionParams::default()),
+            Ok(0)
+        );
+        assert_eq!(basis.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn test_l2_max_iterations() {
+        let basis = generate::random_basis(5, 40, 3);
+        let mut expected = basis.clone();
+        bigl2::lattice_reduce(&mut expected, 0.51, 0.99);
+
+        let mut limited = basis.clone();
+        let params = ReductionParams::default().max_iterations(2);
+        assert_eq!(
+            bigl2::lattice_reduce_with(&mut limited, &params),
+            Err(ReductionError::IterationLimit)
+        );
+        // The lattice is preserved: resuming the reduction gives the same result
+        assert_eq!(
+            bigl2::lattice_reduce_with(&mut limited, &ReductionParams::default()),
+            Ok(0)
+        );
+        assert_eq!(limited.to_string(), expected.to_string());
+
+        let mut reduced = basis.clone();
+        let params = ReductionParams::default().max_iterations(100_000);
+        assert_eq!(bigl2::lattice_reduce_with(&mut reduced, &params), Ok(0));
+        assert_eq!(reduced.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn test_vector_iterators() {
+        let v: BigVector = (1..=3).map(Integer::from).collect();
+        assert_eq!(v.dimension(), 3);
+        assert_eq!(v.iter().sum::<Integer>(), 6);
+        let mut total = Integer::new();
+        for c in &v {
+            total += c;
+        }
+        assert_eq!(total, 6);
+        let coefficients: Vec<Integer> = v.into_iter().collect();
+        assert_eq!(coefficients, vec![1, 2, 3]);
+
+        let v: VectorF = vec![1.5, -2.].into_iter().collect();
+        assert_eq!(v.iter().copied().collect::<Vec<f64>>(), vec![1.5, -2.]);
+        assert_eq!(v.into_iter().map(f64::abs).sum::<f64>(), 3.5);
+    }
+
+    #[test]
+    fn test_projection() {
+        let u = VectorF::from_vector(vec![3., 1., -2.]);
+        let v = VectorF::from_vector(vec![1., 1., 1.]);
+        let projection = u.project_onto(&v);
+        assert!((projection[0] - 2. / 3.).abs() < 1e-12);
+        assert!(u.reject_from(&v).dot(&v).abs() < 1e-12);
+
+        let u = BigVector::from_vector(vec![Integer::from(3), Integer::from(1), Integer::from(-2)]);
+        let v = BigVector::from_vector(vec![Integer::from(1), Integer::from(1), Integer::from(1)]);
+        assert_eq!(u.project_onto(&v)[0], rug::Rational::from((2, 3)));
+        assert_eq!(u.reject_from(&v).dot(&v.to_rational()), 0);
+    }
+
+    #[test]
+    fn test_angle() {
+        let u = VectorF::from_vector(vec![1., 2., 0.]);
+        let v = VectorF::from_vector(vec![-2., 1., 5.]);
+        assert!((u.angle(&v) - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert!(u.is_orthogonal(&v, 1e-12));
+
+        let w = u.mulf(&3.);
+        assert_eq!(u.angle(&w), 0.);
+        assert!(!u.is_orthogonal(&w, 1e-12));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_gram_matrix_parallel() {
+        let basis = generate::random_basis(12, 100, 5);
+        let sequential = crate::l2::gram_matrix_sequential(&basis, 12);
+        let parallel = crate::l2::gram_matrix_parallel(&basis, 12);
+        assert_eq!(parallel.to_string(), sequential.to_string());
+    }
+
+    #[test]
+    fn test_bigl2_full() {
+        let mut basis = generate::random_basis(5, 30, 11);
+        let (mu, r) = bigl2::lattice_reduce_full(&mut basis, 0.51, 0.99);
+
+        let eta = rug::Rational::from((51, 100));
+        for i in 0..5 {
+            assert!(r[i][i] > 0);
+            for j in 0..i {
+                assert!(rug::Rational::from(mu[i][j].abs_ref()) <= eta);
+                assert_eq!(r[i][j], rug::Rational::from(&mu[i][j] * &r[j][j]));
+            }
+        }
+        assert_eq!(r[0][0], basis[0].dot(&basis[0]));
+    }
+
+    #[test]
+    fn test_submatrix() {
+        let mut matrix = generate::random_basis(4, 16, 2);
+        let original = matrix.clone();
+
+        let block = matrix.submatrix(1..3, 2..4);
+        assert_eq!(block.dimensions(), (2, 2));
+        assert_eq!(block[1][0], matrix[2][2]);
+
+        matrix.assign_submatrix(1, 2, &block);
+        assert_eq!(matrix.to_string(), original.to_string());
+
+        matrix.assign_submatrix(0, 0, &block);
+        assert_eq!(matrix[1][1], original[2][3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_submatrix_out_of_bounds() {
+        generate::random_basis(4, 16, 2).submatrix(3..5, 0..1);
+    }
+
+    #[test]
+    fn test_stack() {
+        type I = Integer;
+
+        let mut basis: Matrix<I> = Matrix::init(2, 2);
+        basis[0] = BigVector::from_vector(vec![I::from(2), I::from(0)]);
+        basis[1] = BigVector::from_vector(vec![I::from(1), I::from(3)]);
+
+        // Kannan embedding of the target (5, 1)
+        let embedding = basis
+            .hstack(&Matrix::init(2, 1))
+            .vstack(&Matrix::from_columns(vec![BigVector::from_vector(vec![
+                I::from(5),
+                I::from(1),
+                I::from(1),
+            ])]));
+        assert_eq!(embedding.dimensions(), (3, 3));
+        assert_eq!(embedding.to_string(), "[2 0 0]\n[1 3 0]\n[5 1 1]");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_stack_mismatch() {
+        let basis: Matrix<Integer> = Matrix::init(2, 2);
+        basis.hstack(&Matrix::init(3, 1));
+    }
+
+    #[test]
+    fn test_vector_i64() {
+        let x = 1i64 << 40;
+        let u = VectorI64::checked_from_vector(vec![x, x, 3]).unwrap();
+        let v = VectorI64::checked_from_vector(vec![x, x + 1, 5]).unwrap();
+        let expected = u.to_big().dot(&v.to_big());
+        assert_eq!(expected, u.dot(&v));
+        assert!(expected.to_i64().is_none());
+
+        assert!(VectorI64::checked_from_vector(vec![i64::MAX, i64::MAX, 0]).is_none());
+        assert!(VectorI64::checked_from_vector(vec![i64::MIN]).is_some());
+    }
+
+    #[test]
+    fn test_l2i64() {
+        let mut basis: Matrix<i64> = Matrix::init(3, 4);
+        basis[0] = VectorI64::from_vector(vec![1, 0, 0, 1345]);
+        basis[1] = VectorI64::from_vector(vec![0, 1, 0, 35]);
+        basis[2] = VectorI64::from_vector(vec![0, 0, 1, 154]);
+        let mut big: Matrix<Integer> = Matrix::init(3, 4);
+        for i in 0..3 {
+            big[i] = basis[i].to_big();
+        }
+
+        assert_eq!(Ok(0), l2i64::lattice_reduce(&mut basis, 0.501, 0.998));
+        bigl2::lattice_reduce(&mut big, 0.501, 0.998);
+        for i in 0..3 {
+            assert_eq!(format!("{:?}", big[i]), format!("{:?}", basis[i].to_big()));
+        }
+    }
+
+    #[test]
+    fn test_l2i64_overflow() {
+        let u = VectorI64::from_vector(vec![i64::MAX, i64::MAX, i64::MAX]);
+        assert_eq!(None, u.checked_dot(&u));
+        let v = VectorI64::from_vector(vec![1, 1]);
+        assert_eq!(Some(2), v.checked_dot(&v));
+
+        let mut basis: Matrix<i64> = Matrix::init(2, 3);
+        basis[0] = u;
+        basis[1] = VectorI64::from_vector(vec![1, 0, 0]);
+        assert_eq!(
+            Err(ReductionError::Overflow),
+            l2i64::lattice_reduce(&mut basis, 0.501, 0.998)
+        );
+    }
+
+    #[test]
+    fn test_l2f_reorthogonalization() {
+        // Double floating-point numbers, with the Gram-Schmidt coefficients computed from
+        // the Gram matrix in a single pass
+        struct SinglePass;
+        impl crate::scalars::Scalars for SinglePass {
+            type Integer = f64;
+            type Fraction = f64;
+
+            fn round(f: &f64) -> f64 {
+                f.round()
+            }
+
+            fn round_div(n: f64, d: f64) -> f64 {
+                (n / d).round()
+            }
+
+            fn abs(f: f64) -> f64 {
+                f.abs()
+            }
+
+            fn bit_length(n: &f64) -> u32 {
+                <crate::scalars::Float as crate::scalars::Scalars>::bit_length(n)
+            }
+        }
+
+        // Nearly parallel vectors: the Gram-Schmidt projections cancel out
+        let n = 6;
+        let mut basis: Matrix<f64> = Matrix::init(n, n + 1);
+        for i in 0..n {
+            for j in 0..=n {
+                basis[i][j] = (1u64 << 35) as f64 * (j + 1) as f64 + ((i * 7 + j * 3) % 11) as f64;
+            }
+        }
+
+        assert_eq!(0, l2f::lattice_reduce(&mut basis, 0.51, 0.99));
+        assert!(verify::is_lll_reduced(&basis.round_to_big(), 0.98, 0.52));
+
+        // Worse cancellations: the size reduction does not converge in a single pass
+        let n = 7;
+        let mut basis: Matrix<f64> = Matrix::init(n, n + 1);
+        for i in 0..n {
+            for j in 0..=n {
+                basis[i][j] = (1u64 << 50) as f64 * (j + 1) as f64 + ((i * 7 + j * 3) % 11) as f64;
+            }
+        }
+        let params = ReductionParams::default()
+            .eta(0.51)
+            .delta(0.99)
+            .max_size_reduction_passes(64);
+        assert_eq!(
+            Err(ReductionError::Precision),
+            l2::lattice_reduce_stats::<SinglePass>(&mut basis.clone(), &params, None)
+        );
+        assert_eq!(0, l2f::lattice_reduce(&mut basis, 0.51, 0.99));
+        assert!(verify::is_lll_reduced(&basis.round_to_big(), 0.98, 0.52));
+    }
+
+    #[test]
+    fn test_divf() {
+        let v = VectorF::from_vector(vec![1.5, -2., 0.1]);
+        let back = v.mulf(&3.7).divf(3.7);
+        for i in 0..3 {
+            assert!((back[i] - v[i]).abs() < 1e-12);
+        }
+
+        let k = rug::Rational::from((-3, 7));
+        let v = BigVector::from_vector(vec![Integer::from(4), Integer::from(-9)]).to_rational();
+        let back = v.mulf(&k).divf(&k);
+        assert_eq!(format!("{:?}", v), format!("{:?}", back));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_divf_zero() {
+        VectorF::from_vector(vec![1.]).divf(0.);
+    }
+
+    #[test]
+    fn test_successive_minima_estimates() {
+        type I = Integer;
+
+        // Lattice generated by (3, 0, 0), (0, 5, 0), (0, 0, 7): λ_1 = 3
+        let mut basis: Matrix<I> = Matrix::init(3, 3);
+        basis[0] = BigVector::from_vector(vec![I::from(3), I::from(5), I::from(0)]);
+        basis[1] = BigVector::from_vector(vec![I::from(6), I::from(15), I::from(7)]);
+        basis[2] = BigVector::from_vector(vec![I::from(9), I::from(20), I::from(14)]);
+
+        let estimates = metrics::successive_minima_estimates(&basis);
+        let upper = &estimates.upper_bounds;
+        assert_eq!(3, upper.len());
+        assert!(upper.windows(2).all(|w| w[0] <= w[1]));
+        assert!(estimates.first_lower_bound <= 3. && 3. <= upper[0]);
+    }
+
+    #[test]
+    fn test_closest_coords() {
+        type I = Integer;
+
+        let mut basis: Matrix<I> = Matrix::init(3, 4);
+        basis[0] = BigVector::from_vector(vec![I::from(1), I::from(0), I::from(0), I::from(1345)]);
+        basis[1] = BigVector::from_vector(vec![I::from(0), I::from(1), I::from(0), I::from(35)]);
+        basis[2] = BigVector::from_vector(vec![I::from(0), I::from(0), I::from(1), I::from(154)]);
+
+        let target =
+            BigVector::from_vector(vec![I::from(7), I::from(-4), I::from(2), I::from(9000)]);
+        let coords = cvp::closest_coords(&basis, &target);
+        assert_eq!(3, coords.dimension());
+
+        let point = (0..3).fold(BigVector::init(4), |acc, i| {
+            acc.add(&basis[i].mulf(&coords[i]))
+        });
+        let closest = cvp::closest_vector_exact(&basis, &target);
+        assert_eq!(format!("{:?}", closest), format!("{:?}", point));
+    }
+
+    #[test]
+    fn test_io() {
+        type I = Integer;
+
+        let mut basis: Matrix<I> = Matrix::init(2, 3);
+        basis[0] = BigVector::from_vector(vec![I::from(1), I::from(0), I::from(-1345)]);
+        basis[1] = BigVector::from_vector(vec![I::from(0), I::from(12), I::from(35)]);
+
+        let csv = io::to_csv(&basis);
+        assert_eq!("1,0,-1345\n0,12,35\n", csv);
+        assert_eq!(
+            "\\begin{pmatrix}\n1 & 0 & -1345 \\\\\n0 & 12 & 35\n\\end{pmatrix}",
+            io::to_latex(&basis)
+        );
+
+        let parsed = io::parse_csv(&csv).unwrap();
+        assert_eq!(basis.to_string(), parsed.to_string());
+
+        assert_eq!(
+            Err(io::ParseCsvError::InvalidInteger { line: 2, field: 2 }),
+            io::parse_csv("1, 2\n3, x\n").map(|m| m.dimensions())
+        );
+        assert_eq!(
+            Err(io::ParseCsvError::DimensionMismatch { line: 2 }),
+            io::parse_csv("1, 2\n3\n").map(|m| m.dimensions())
+        );
+    }
+
+    #[test]
+    fn test_bigl2_cached() {
+        type I = Integer;
+
+        let mut basis: Matrix<I> = Matrix::init(3, 4);
+        basis[0] = BigVector::from_vector(vec![I::from(1), I::from(0), I::from(0), I::from(1345)]);
+        basis[1] = BigVector::from_vector(vec![I::from(0), I::from(1), I::from(0), I::from(35)]);
+        basis[2] = BigVector::from_vector(vec![I::from(0), I::from(0), I::from(1), I::from(154)]);
+
+        let gram = bigl2::compute_gram(&basis);
+        assert_eq!(gram[0][1], 1345 * 35);
+        assert_eq!(gram[1][0], 1345 * 35);
+
+        for &(eta, delta) in &[(0.501, 0.998), (0.51, 0.75)] {
+            let mut expected = basis.clone();
+            bigl2::lattice_reduce(&mut expected, eta, delta);
+
+            let mut prefilled = Some(gram.clone());
+            let mut reduced = basis.clone();
+            assert_eq!(
+                0,
+                bigl2::lattice_reduce_cached(&mut reduced, eta, delta, &mut prefilled)
+            );
+            assert_eq!(expected.to_string(), reduced.to_string());
+
+            let mut empty = None;
+            let mut reduced = basis.clone();
+            bigl2::lattice_reduce_cached(&mut reduced, eta, delta, &mut empty);
+            assert_eq!(expected.to_string(), reduced.to_string());
+            assert_eq!(gram.to_string(), empty.unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn test_bigl2_rectangular() {
+        type I = Integer;
+
+        // 2-dimensional sublattice of Z^4
+        let mut basis: Matrix<I> = Matrix::init(2, 4);
+        basis[0] = BigVector::from_vector(vec![I::from(3), I::from(17), I::from(-5), I::from(40)]);
+        basis[1] = BigVector::from_vector(vec![I::from(4), I::from(22), I::from(-7), I::from(51)]);
+
+        let mut reduced = basis.clone();
+        assert_eq!(0, bigl2::lattice_reduce(&mut reduced, 0.501, 0.998));
+        assert_eq!((2, 4), reduced.dimensions());
+        assert!(verify::is_lll_reduced(&reduced, 0.998, 0.501));
+
+        // Same lattice: the reduced vectors are integer combinations of the original
+        // ones and vice versa
+        assert_eq!(
+            hnf::hermite_normal_form(&basis).to_string(),
+            hnf::hermite_normal_form(&reduced).to_string()
+        );
+        let norm = |m: &Matrix<I>, i: usize| m[i].dot(&m[i]);
+        assert!(norm(&reduced, 0) < norm(&basis, 0));
+    }
+
+    #[test]
+    fn test_scale() {
+        fn triple<V>(v: &V, three: &V::Scalar) -> V
+        where
+            V: Scale + Clone,
+        {
+            v.scale(three)
+        }
+
+        let v = VectorF::from_vector(vec![1.5, -2.]);
+        assert_eq!("[4.5, -6.0]", format!("{:?}", triple(&v, &3.)));
+
+        let v = BigVector::from_vector(vec![Integer::from(7), Integer::from(-2)]);
+        assert_eq!("[21, -6]", format!("{:?}", triple(&v, &Integer::from(3))));
+
+        let v =
+            RationalVector::from_vector(vec![rug::Rational::from((1, 3)), rug::Rational::from(2)]);
+        assert_eq!(
+            "[1, 6]",
+            format!("{:?}", triple(&v, &rug::Rational::from(3)))
+        );
+    }
+
+    #[test]
+    fn test_generic_reduce() {
+        type I = Integer;
+
+        let mut basis: Matrix<I> = Matrix::init(3, 4);
+        basis[0] = BigVector::from_vector(vec![I::from(1), I::from(0), I::from(0), I::from(1345)]);
+        basis[1] = BigVector::from_vector(vec![I::from(0), I::from(1), I::from(0), I::from(35)]);
+        basis[2] = BigVector::from_vector(vec![I::from(0), I::from(0), I::from(1), I::from(154)]);
+
+        let mut expected = basis.clone();
+        bigl2::lattice_reduce(&mut expected, 0.501, 0.998);
+        let mut reduced = basis.clone();
+        assert_eq!(
+            0,
+            reduce::lattice_reduce::<reduce::BigNum>(&mut reduced, 0.501, 0.998)
+        );
+        assert_eq!(expected.to_string(), reduced.to_string());
+
+        let mut expected = basis.to_float();
+        l2f::lattice_reduce(&mut expected, 0.501, 0.998);
+        let mut reduced = basis.to_float();
+        reduce::lattice_reduce::<reduce::Float>(&mut reduced, 0.501, 0.998);
+        assert_eq!(expected.to_string(), reduced.to_string());
+    }
+
+    #[test]
+    fn test_gaussian_reduce() {
+        use crate::gaussian::{self, GaussianInteger, GaussianNum};
+        use crate::vector::GaussianVector;
+
+        let g = |re: i32, im: i32| GaussianInteger::new(Integer::from(re), Integer::from(im));
+
+        // Z-lattice generated by the vectors v and i*v of the module
+        let embedding = |basis: &Matrix<GaussianInteger>| {
+            let (d, n) = basis.dimensions();
+            let mut real: Matrix<Integer> = Matrix::init(2 * d, 2 * n);
+            for i in 0..d {
+                for j in 0..n {
+                    real[2 * i][2 * j] = basis[i][j].re.clone();
+                    real[2 * i][2 * j + 1] = basis[i][j].im.clone();
+                    real[2 * i + 1][2 * j] = Integer::from(-&basis[i][j].im);
+                    real[2 * i + 1][2 * j + 1] = basis[i][j].re.clone();
+                }
+            }
+            hnf::hermite_normal_form(&real).to_string()
+        };
+
+        let mut basis: Matrix<GaussianInteger> = Matrix::init(3, 3);
+        basis[0] = GaussianVector::from_vector(vec![g(1, 0), g(0, 0), g(137, 41)]);
+        basis[1] = GaussianVector::from_vector(vec![g(0, 0), g(1, 0), g(52, -77)]);
+        basis[2] = GaussianVector::from_vector(vec![g(0, 0), g(0, 0), g(211, 0)]);
+
+        let mut reduced = basis.clone();
+        assert_eq!(gaussian::lattice_reduce(&mut reduced, 0.51, 0.99), 0);
+
+        assert_eq!(embedding(&basis), embedding(&reduced));
+        assert!(reduced[0].norm_sq() < basis[0].norm_sq());
+
+        // The generic reduction with the Gaussian scalars
+        let mut generic = basis.clone();
+        reduce::lattice_reduce::<GaussianNum>(&mut generic, 0.51, 0.99);
+        assert_eq!(reduced, generic);
+
+        // Linear dependency over Z[i]: (1 + i) * b_0 + b_1
+        let mut dependent: Matrix<GaussianInteger> = Matrix::init(4, 3);
+        for i in 0..3 {
+            dependent[i] = basis[i].clone();
+        }
+        dependent[3] = basis[0].mulf(&g(1, 1)).add(&basis[1]);
+        assert_eq!(gaussian::lattice_reduce(&mut dependent, 0.51, 0.99), 1);
+        assert_eq!(dependent[3], GaussianVector::init(3));
+
+        let mut nonzero: Matrix<GaussianInteger> = Matrix::init(3, 3);
+        for i in 0..3 {
+            nonzero[i] = dependent[i].clone();
+        }
+        assert_eq!(embedding(&basis), embedding(&nonzero));
+    }
+
+    #[test]
+    fn test_row_operations() {
+        type I = Integer;
+
+        let mut matrix: Matrix<I> = Matrix::init(3, 3);
+        matrix[0] = BigVector::from_vector(vec![I::from(1), I::from(2), I::from(3)]);
+        matrix[1] = BigVector::from_vector(vec![I::from(4), I::from(5), I::from(6)]);
+        matrix[2] = BigVector::from_vector(vec![I::from(7), I::from(8), I::from(9)]);
+
+        matrix.swap_columns(0, 2);
+        assert_eq!("[3 2 1]\n[6 5 4]\n[9 8 7]", matrix.to_string());
+
+        matrix.scale_row(1, &I::from(-2));
+        assert_eq!(
+            "[  3   2  1]\n[-12 -10 -8]\n[  9   8  7]",
+            matrix.to_string()
+        );
+
+        matrix.add_scaled_row(2, 0, &I::from(-3));
+        assert_eq!(
+            "[  3   2  1]\n[-12 -10 -8]\n[  0   2  4]",
+            matrix.to_string()
+        );
+    }
+
+    #[test]
+    fn test_identity() {
+        type I = Integer;
+
+        let mut matrix: Matrix<I> = Matrix::init(3, 3);
+        matrix[0] = BigVector::from_vector(vec![I::from(1), I::from(-2), I::from(3)]);
+        matrix[1] = BigVector::from_vector(vec![I::from(4), I::from(5), I::from(-6)]);
+        matrix[2] = BigVector::from_vector(vec![I::from(7), I::from(8), I::from(9)]);
+
+        let identity: Matrix<I> = Matrix::identity(3);
+        let columns = matrix.transpose();
+        let mut product: Matrix<I> = Matrix::zeros(3, 3);
+        for i in 0..3 {
+            for j in 0..3 {
+                product[i][j] = identity[i].dot(&columns[j]);
+            }
+        }
+        assert_eq!(matrix.to_string(), product.to_string());
+
+        let identity: Matrix<f64> = Matrix::identity(2);
+        assert_eq!("[1 0]\n[0 1]", identity.to_string());
+        assert_eq!((2, 4), Matrix::<f64>::zeros(2, 4).dimensions());
+    }
+
+    #[test]
+    fn test_hash() {
+        use std::collections::HashSet;
+        type I = Integer;
+
+        let mut basis: Matrix<I> = Matrix::init(2, 2);
+        basis[0] = BigVector::from_vector(vec![I::from(1), I::from(1345)]);
+        basis[1] = BigVector::from_vector(vec![I::from(0), I::from(35)]);
+
+        let mut set = HashSet::new();
+        set.insert(basis.clone());
+        set.insert(basis.clone());
+        assert_eq!(1, set.len());
+
+        let mut other = basis.clone();
+        other.swap(0, 1);
+        assert!(other != basis);
+        set.insert(other);
+        assert_eq!(2, set.len());
+
+        // Same coefficients, different dimensions
+        assert!(Matrix::<I>::zeros(2, 3) != Matrix::<I>::zeros(3, 2));
+
+        let vectors: HashSet<_> = vec![basis[0].to_rational(), basis[0].to_rational()]
+            .into_iter()
+            .collect();
+        assert_eq!(1, vectors.len());
+    }
+
+    #[test]
+    fn test_segment_lll() {
+        let weights: Vec<Integer> = [
+            366_181_829u64,
+            881_724_311,
+            493_103_097,
+            725_190_237,
+            193_561_871,
+            982_342_907,
+            640_882_413,
+            277_415_629,
+            819_044_551,
+            458_332_907,
+            104_853_637,
+        ]
+        .iter()
+        .map(|&w| Integer::from(w))
+        .collect();
+        let basis = generate::knapsack_lattice(&weights, &Integer::from(3_000_000_000u64));
+
+        let mut reduced = basis.clone();
+        assert_eq!(0, segment::lattice_reduce(&mut reduced, 0.51, 0.99, 3));
+        assert!(verify::is_lll_reduced(&reduced, 0.99, 0.51));
+        assert_eq!(
+            hnf::hermite_normal_form(&basis),
+            hnf::hermite_normal_form(&reduced)
+        );
+
+        // Small dimension: ordinary L²
+        let small = generate::random_basis(4, 20, 7);
+        let mut expected = small.clone();
+        bigl2::lattice_reduce(&mut expected, 0.51, 0.99);
+        let mut reduced = small.clone();
+        segment::lattice_reduce(&mut reduced, 0.51, 0.99, 2);
+        assert_eq!(expected, reduced);
+    }
+
+    #[cfg(feature = "mpfr")]
+    #[test]
+    fn test_floatl2_precision() {
+        use crate::l2::floatl2;
+
+        let mut rand = rug::rand::RandState::new();
+        rand.seed(&Integer::from(3));
+        let weights: Vec<Integer> = (0..8)
+            .map(|_| Integer::from(Integer::random_bits(120, &mut rand)))
+            .collect();
+        let target = weights[1].clone() + &weights[4] + &weights[6];
+        let basis = generate::knapsack_lattice(&weights, &target);
+
+        let mut reduced = basis.clone();
+        assert_eq!(
+            Ok(0),
+            floatl2::lattice_reduce_prec(&mut reduced, 0.51, 0.99, 256)
+        );
+        assert!(verify::is_lll_reduced(&reduced, 0.99, 0.51));
+        assert_eq!(
+            hnf::hermite_normal_form(&basis),
+            hnf::hermite_normal_form(&reduced)
+        );
+
+        // The precision is too low for the size reduction to converge
+        let mut partial = basis.clone();
+        assert_eq!(
+            Err(ReductionError::Precision),
+            floatl2::lattice_reduce_prec(&mut partial, 0.51, 0.99, 8)
+        );
+        assert_eq!(
+            hnf::hermite_normal_form(&basis),
+            hnf::hermite_normal_form(&partial)
+        );
+
+        // The coefficients do not fit in a f64
+        let mut float = basis.to_float();
+        l2f::lattice_reduce(&mut float, 0.51, 0.99);
+        let float = float.round_to_big();
+        assert!(
+            !verify::is_lll_reduced(&float, 0.99, 0.51)
+                || hnf::hermite_normal_form(&basis) != hnf::hermite_normal_form(&float)
+        );
+    }
+
+    #[test]
+    fn test_reduce_owned() {
+        let basis = generate::random_basis(5, 30, 11);
+        let mut expected = basis.clone();
+        bigl2::lattice_reduce(&mut expected, 0.51, 0.99);
+
+        let result = bigl2::reduce_owned(basis.clone(), &ReductionParams::default()).unwrap();
+        assert_eq!(expected, result.basis);
+        assert!(verify::is_lll_reduced(&result.basis, 0.99, 0.51));
+        assert!(result.stats.swaps > 0);
+        assert!(result.stats.iterations >= result.stats.swaps);
+
+        // basis = U * input, with U unimodular
+        let columns = basis.transpose();
+        for i in 0..5 {
+            for j in 0..5 {
+                assert_eq!(result.transform[i].dot(&columns[j]), result.basis[i][j]);
+            }
+        }
+        let inverse = result.transform.to_rational().inverse().unwrap();
+        for i in 0..5 {
+            assert!(inverse[i].iter().all(|x| *x.denom() == 1));
+        }
+    }
+
+    #[test]
+    fn test_bound_denominators() {
+        let largest_denominator = |basis: &Matrix<Integer>, params: &ReductionParams| {
+            let mut reduced = basis.clone();
+            let mut mu = Matrix::init(0, 0);
+            let mut r = Matrix::init(0, 0);
+            crate::l2::lattice_reduce_gso::<reduce::BigNum, _>(
+                &mut reduced,
+                params,
+                None,
+                None,
+                &mut mu,
+                &mut r,
+                |_, _, _| true,
+            );
+            let (n, _) = mu.dimensions();
+            let bits = (0..n)
+                .flat_map(|i| (0..i).map(move |j| (i, j)))
+                .map(|(i, j)| mu[i][j].denom().significant_bits())
+                .max()
+                .unwrap_or(0);
+            (reduced, bits)
+        };
+
+        let exact = ReductionParams::default();
+        let bounded = ReductionParams::default().bound_denominators(64);
+
+        // Small input: same output
+        let basis = generate::random_basis(4, 16, 5);
+        assert_eq!(
+            largest_denominator(&basis, &exact).0,
+            largest_denominator(&basis, &bounded).0
+        );
+
+        // Large coefficients: the exact denominators are much larger
+        let basis = generate::random_basis(10, 200, 5);
+        let (reduced, exact_bits) = largest_denominator(&basis, &exact);
+        let (bounded_reduced, bounded_bits) = largest_denominator(&basis, &bounded);
+        assert!(bounded_bits <= 65 && exact_bits > 65);
+        assert_eq!(
+            hnf::hermite_normal_form(&reduced),
+            hnf::hermite_normal_form(&bounded_reduced)
+        );
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_nalgebra() {
+        let original = nalgebra::DMatrix::from_row_slice(2, 3, &[1., 0., 1345., 0., 1., 35.]);
+        let matrix = Matrix::<f64>::from(&original);
+        assert_eq!((2, 3), matrix.dimensions());
+        assert_eq!("[1 0 1345]\n[0 1   35]", matrix.to_string());
+        assert_eq!(original, nalgebra::DMatrix::from(&matrix));
+    }
+
+    #[test]
+    fn test_sample_short() {
+        let basis = generate::random_basis(6, 20, 7);
+        let mut reduced = basis.clone();
+        bigl2::lattice_reduce(&mut reduced, 0.51, 0.99);
+
+        let short = svp::sample_short(&basis, 30, 1);
+        let norm = short.dot(&short);
+        assert!(norm > 0);
+        assert!(norm <= reduced[0].dot(&reduced[0]));
+    }
+
+    #[test]
+    fn test_dimension_mismatch() {
+        let a = VectorF::from_vector(vec![1., 2.]);
+        let b = VectorF::from_vector(vec![1., 2., 3.]);
+        assert_eq!(Err(DimensionMismatch(2, 3)), a.try_add(&b));
+        assert_eq!(Err(DimensionMismatch(2, 3)), a.try_sub(&b));
+        assert_eq!(Err(DimensionMismatch(2, 3)), a.try_dot(&b));
+        assert_eq!(Ok(5.), a.try_dot(&a));
+
+        let c = BigVector::from_vector(vec![Integer::from(1), Integer::from(2)]);
+        let d = BigVector::from_vector(vec![Integer::from(3); 3]);
+        assert_eq!(Err(DimensionMismatch(2, 3)), c.try_add(&d));
+        assert_eq!(Err(DimensionMismatch(3, 2)), d.try_sub(&c));
+        assert_eq!(Err(DimensionMismatch(2, 3)), c.try_dot(&d));
+        assert_eq!(Ok(c.add(&c)), c.try_add(&c));
+    }
+
+    #[test]
+    fn test_add_assign() {
+        let a = BigVector::from_vector(vec![Integer::from(1), Integer::from(-4)]);
+        let b = BigVector::from_vector(vec![Integer::from(7), Integer::from(2)]);
+        let mut c = a.clone();
+        c.add_assign(&b);
+        assert_eq!(a.add(&b), c);
+        c -= &b;
+        assert_eq!(a, c);
+        c.sub_assign(&b);
+        assert_eq!(a.sub(&b), c);
+        c.sub_mulf_assign(&b, &Integer::from(-3));
+        assert_eq!(a.sub(&b).add(&b.mulf(&Integer::from(3))), c);
+
+        let x = VectorF::from_vector(vec![1.5, -2.]);
+        let mut y = VectorF::from_vector(vec![0.25, 3.]);
+        y += &x;
+        assert_eq!(VectorF::from_vector(vec![1.75, 1.]), y);
+    }
+
+    #[test]
+    fn test_svp_challenge() {
+        let basis = generate::svp_challenge(5, 42, 50);
+        assert_eq!(basis, generate::svp_challenge(5, 42, 50));
+        assert_ne!(basis, generate::svp_challenge(5, 43, 50));
+        assert_eq!((5, 5), basis.dimensions());
+
+        let prime = &basis[0][0];
+        assert_ne!(rug::integer::IsPrime::No, prime.is_probably_prime(30));
+        assert!(prime.significant_bits() >= 50);
+        for i in 0..5 {
+            for j in 1..5 {
+                let expected = if i == j { 1 } else { 0 };
+                assert_eq!(expected, basis[i][j]);
+            }
+            if i > 0 {
+                assert!(basis[i][0] >= 0 && basis[i][0] < *prime);
+            }
+        }
+    }
+
+    #[test]
+    fn test_log_potential() {
+        let basis = generate::random_basis(8, 30, 11);
+        let params = ReductionParams::default().track_potential();
+        let result = bigl2::reduce_owned(basis.clone(), &params).unwrap();
+        let initial = result.stats.initial_log_potential.unwrap();
+        let last = result.stats.final_log_potential.unwrap();
+        assert!(last < initial);
+
+        let mut reduced = basis;
+        let (_, r) = bigl2::lattice_reduce_full(&mut reduced, 0.51, 0.99);
+        assert!((metrics::log_potential(&r) - last).abs() < 1e-9 * last.abs());
+
+        let untracked = bigl2::reduce_owned(reduced, &ReductionParams::default()).unwrap();
+        assert_eq!(None, untracked.stats.initial_log_potential);
+    }
+
+    #[test]
+    fn test_staged() {
+        let weights: Vec<Integer> = (0..12)
+            .map(|i| Integer::from(Integer::u_pow_u(3, 20 + i)) + i)
+            .collect();
+        let basis = generate::knapsack_lattice(&weights, &Integer::from(Integer::u_pow_u(3, 28)));
+
+        let direct = bigl2::reduce_owned(basis.clone(), &ReductionParams::default()).unwrap();
+        assert!(verify::is_lll_reduced(&direct.basis, 0.99, 0.51));
+
+        let mut staged = basis;
+        let stats = reduce::staged(&mut staged, &[(0.51, 0.75), (0.51, 0.99)]);
+        assert!(verify::is_lll_reduced(&staged, 0.99, 0.51));
+        assert!(stats.swaps <= direct.stats.swaps);
+    }
+
+    #[test]
+    fn test_lattice_reduce_range() {
+        let mut basis = generate::random_basis(7, 30, 5);
+        bigl2::lattice_reduce(&mut basis, 0.51, 0.99);
+        let reduced = basis.clone();
+        bigl2::lattice_reduce_range(&mut basis, 2, 6, 0.51, 0.99);
+        assert_eq!(reduced, basis);
+
+        // Perturbing a vector of the range
+        let step = basis[3].mulf(&Integer::from(1000));
+        basis[4] += &step;
+        bigl2::lattice_reduce_range(&mut basis, 2, 6, 0.51, 0.99);
+        for i in (0..2).chain(6..7) {
+            assert_eq!(reduced[i], basis[i]);
+        }
+        bigl2::lattice_reduce_range(&mut basis, 0, 7, 0.51, 0.99);
+        assert!(verify::is_lll_reduced(&basis, 0.99, 0.51));
+    }
+
+    #[test]
+    fn test_abs_signum() {
+        let v: BigVector = vec![-3, 2, -1, 0].into_iter().map(Integer::from).collect();
+        let abs: BigVector = vec![3, 2, 1, 0].into_iter().map(Integer::from).collect();
+        assert_eq!(abs, v.abs());
+        assert_eq!(vec![-1, 1, -1, 0], v.signum_coefficients());
+        assert!(!v.is_zero());
+        assert!(BigVector::init(3).is_zero());
+        assert!(BigVector::init(0).is_zero());
+    }
+
+    #[test]
+    fn test_pairwise_preprocess() {
+        let weights: Vec<Integer> = (0..8)
+            .map(|i| Integer::from(Integer::u_pow_u(7, 15 + i)) + i)
+            .collect();
+        let basis = generate::knapsack_lattice(&weights, &Integer::from(Integer::u_pow_u(7, 18)));
+        let largest = |basis: &Matrix<Integer>| {
+            (0..basis.dimensions().0)
+                .map(|i| basis[i].norm_linf())
+                .max()
+                .unwrap()
+        };
+
+        let mut preprocessed = basis.clone();
+        reduce::pairwise_preprocess(&mut preprocessed);
+        assert_eq!(
+            hnf::hermite_normal_form(&basis),
+            hnf::hermite_normal_form(&preprocessed)
+        );
+        assert!(largest(&preprocessed) < largest(&basis));
+    }
+
+    #[test]
+    fn test_lovasz_ok() {
+        let q = |n: i32, d: i32| rug::Rational::from((n, d));
+
+        // delta_plus * r_prev == mu² * r_prev + r_cur: the vectors are swapped
+        assert!(!l2::lovasz_ok(&q(1, 1), &q(3, 4), &q(1, 2), &q(1, 1)));
+        assert!(l2::lovasz_ok(&q(1, 1), &q(4, 5), &q(1, 2), &q(1, 1)));
+        assert!(!l2::lovasz_ok(&q(1, 1), &q(1, 2), &q(1, 2), &q(1, 1)));
+
+        // Same decision with floating-point numbers
+        assert!(!l2::lovasz_ok(&1., &0.75, &0.5, &1.));
+        assert!(l2::lovasz_ok(&1., &0.8, &0.5, &1.));
+    }
+
+    #[test]
+    fn test_best_of_random() {
+        let basis = generate::random_basis(8, 40, 21);
+        let mut plain = basis.clone();
+        bigl2::lattice_reduce(&mut plain, 0.51, 0.99);
+        assert_eq!(plain, reduce::best_of_random(&basis, 0.51, 0.99, 1, 3));
+
+        let best = reduce::best_of_random(&basis, 0.51, 0.99, 10, 3);
+        assert!(best[0].dot(&best[0]) <= plain[0].dot(&plain[0]));
+        assert_eq!(
+            hnf::hermite_normal_form(&basis),
+            hnf::hermite_normal_form(&best)
+        );
+    }
+
+    #[test]
+    fn test_matrix_accessors() {
+        let mut matrix: Matrix<Integer> = Matrix::init(3, 2);
+        assert_eq!((3, 2), (matrix.rows(), matrix.cols()));
+        matrix.set(0, 1, Integer::from(5));
+        matrix.set(2, 0, Integer::from(-7));
+        assert_eq!(Some(&Integer::from(5)), matrix.get(0, 1));
+        assert_eq!(Some(&Integer::from(0)), matrix.get(1, 1));
+        assert_eq!(None, matrix.get(3, 0));
+        assert_eq!(None, matrix.get(0, 2));
+
+        matrix.swap_rows(0, 2);
+        assert_eq!("[-7 0]\n[ 0 0]\n[ 0 5]", matrix.to_string());
+        matrix.swap_columns(0, 1);
+        assert_eq!("[0 -7]\n[0  0]\n[5  0]", matrix.to_string());
+        matrix.swap(1, 2);
+        assert_eq!(Some(&Integer::from(5)), matrix.get(1, 0));
+    }
+
+    #[test]
+    fn test_reduce_int() {
+        let rows: Vec<Vec<Integer>> =
+            vec![vec![1, 0, 0, 1345], vec![0, 1, 0, 35], vec![0, 0, 1, 154]]
+                .into_iter()
+                .map(|row| row.into_iter().map(Integer::from).collect())
+                .collect();
+        let reduced = crate::reduce_int(rows, 0.501, 0.998);
+        assert_eq!(
+            vec![vec![1, 1, -9, -6], vec![0, 9, -2, 7], vec![1, -3, -8, 8]],
+            reduced
+                .iter()
+                .map(|row| row.iter().map(|c| c.to_i32().unwrap()).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_reduced_short_circuit() {
+        let basis = generate::random_basis(10, 40, 8);
+        let params = ReductionParams::default();
+        let first = bigl2::reduce_owned(basis, &params).unwrap();
+        assert!(first.stats.swaps > 0);
+
+        let second = bigl2::reduce_owned(first.basis.clone(), &params).unwrap();
+        assert_eq!(0, second.stats.swaps);
+        assert_eq!(0, second.stats.iterations);
+        assert_eq!(first.basis, second.basis);
+        assert_eq!(Matrix::identity(10), second.transform);
+
+        let mut float = first.basis.to_float();
+        l2f::lattice_reduce(&mut float, 0.51, 0.99);
+        assert_eq!(first.basis.to_float(), float);
+    }
+
+    #[test]
+    fn test_hermitian_dot() {
+        let u = VectorC::from_pairs(vec![(1., 2.), (0., 1.)]);
+        let v = VectorC::from_pairs(vec![(3., -1.), (2., 2.)]);
+
+        // (1+2i)(3+i) + i(2-2i) = (1+7i) + (2+2i)
+        assert_eq!(Complex::new(3., 9.), u.dot(&v));
+        assert_eq!(Complex::new(3., -9.), v.dot(&u));
+        assert_eq!(Complex::new(6., 0.), u.dot(&u));
+        assert_eq!(6., u.norm_sq());
+
+        let scaled = u.mulf(&Complex::new(0., 1.));
+        assert_eq!(VectorC::from_pairs(vec![(-2., 1.), (-1., 0.)]), scaled);
+        let pair: (f64, f64) = scaled[0].into();
+        assert_eq!((-2., 1.), pair);
+    }
+
+    #[test]
+    fn test_diophantine() {
+        let big = |v: Vec<i32>| -> BigVector { v.into_iter().map(Integer::from).collect() };
+        let a = Matrix::from_columns(vec![big(vec![2, 4, 6]), big(vec![1, -3, 5])]);
+
+        let b = big(vec![10, 7]);
+        let x = solve::diophantine(&a, &b).unwrap();
+        let product: BigVector = (0..2).map(|i| a[i].dot(&x)).collect();
+        assert_eq!(b, product);
+
+        // 2x + 4y + 6z is always even
+        assert_eq!(None, solve::diophantine(&a, &big(vec![7, 1])));
+
+        // Inconsistent system: the two equations have the same left-hand side
+        let a = Matrix::from_columns(vec![big(vec![1, 2, 3]), big(vec![1, 2, 3])]);
+        assert_eq!(None, solve::diophantine(&a, &big(vec![1, 2])));
+        assert!(solve::diophantine(&a, &big(vec![5, 5])).is_some());
+    }
+
+    #[test]
+    fn test_lattice_reduce_logged() {
+        let basis = generate::random_basis(6, 30, 4);
+        let result = bigl2::reduce_owned(basis.clone(), &ReductionParams::default()).unwrap();
+
+        let mut reduced = basis.clone();
+        let ops = bigl2::lattice_reduce_logged(&mut reduced, 0.51, 0.99);
+        assert_eq!(result.basis, reduced);
+
+        let mut transform = Matrix::identity(6);
+        transform.apply_ops(&ops);
+        assert_eq!(result.transform, transform);
+
+        let mut replayed = basis;
+        replayed.apply_ops(&ops);
+        assert_eq!(reduced, replayed);
+    }
+
+    #[test]
+    fn test_sort_by_norm() {
+        let basis = generate::random_basis(8, 30, 6);
+        let params = ReductionParams::default().sort_by_norm(true);
+        let result = bigl2::reduce_owned(basis.clone(), &params).unwrap();
+        let norms: Vec<Integer> = (0..8)
+            .map(|i| result.basis[i].dot(&result.basis[i]))
+            .collect();
+        assert!(norms.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        // The transformation still maps the input to the sorted basis
+        for i in 0..8 {
+            let row = (0..8).fold(BigVector::init(8), |acc, j| {
+                acc.add(&basis[j].mulf(&result.transform[i][j]))
+            });
+            assert_eq!(result.basis[i], row);
+        }
+
+        let mut sorted = basis;
+        bigl2::lattice_reduce_with(&mut sorted, &params).unwrap();
+        assert_eq!(result.basis, sorted);
+    }
+
+    #[test]
+    fn test_gram_matrix_f() {
+        let sqrt = 0.5f64.sqrt();
+        let vectors = Matrix::from_columns(vec![
+            VectorF::from_vector(vec![sqrt, sqrt, 0.]),
+            VectorF::from_vector(vec![-sqrt, sqrt, 0.]),
+        ]);
+        let gram = gram::gram_matrix_f(&vectors);
+        assert_eq!((2, 2), gram.dimensions());
+        for i in 0..2 {
+            for j in 0..2 {
+                let expected = if i == j { 1. } else { 0. };
+                assert!((gram[i][j] - expected).abs() < 1e-15);
+            }
+        }
+
+        let other = Matrix::from_columns(vec![
+            VectorF::from_vector(vec![1., 2.]),
+            VectorF::from_vector(vec![3., -1.]),
+        ]);
+        let expected = Matrix::from_columns(vec![
+            VectorF::from_vector(vec![5., 1.]),
+            VectorF::from_vector(vec![1., 10.]),
+        ]);
+        assert_eq!(expected, gram::gram_matrix_f(&other));
+    }
+
+    #[test]
+    fn test_from_cholesky() {
+        let l = Matrix::from_columns(vec![
+            VectorF::from_vector(vec![1., 0.]),
+            VectorF::from_vector(vec![0.9, 0.5]),
+        ]);
+        let (transform, reduced) = reduce::from_cholesky(&l, 0.51, 0.99);
+
+        // The shortest vector is b_1 - b_0 = (-0.1, 0.5), then comes b_0
+        let det = Integer::from(&transform[0][0] * &transform[1][1])
+            - Integer::from(&transform[0][1] * &transform[1][0]);
+        assert_eq!(1, det.abs());
+        assert!((reduced[0].dot(&reduced[0]) - 0.26).abs() < 1e-12);
+        assert!((reduced[1].dot(&reduced[1]) - 1.).abs() < 1e-12);
+        assert_eq!(transform[0][0], -transform[0][1].clone());
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize() {
+        use zeroize::Zeroize;
+
+        let mut basis = generate::random_basis(4, 200, 1);
+        let mut vector = basis[0].clone();
+        vector.zeroize();
+        assert!(vector.is_zero());
+        assert_eq!(4, vector.dimension());
+
+        basis.zeroize();
+        assert_eq!(Matrix::init(4, 4), basis);
+
+        let wrapped = zeroize::Zeroizing::new(generate::random_basis(2, 64, 2));
+        assert_eq!((2, 2), wrapped.dimensions());
+    }
+
+    #[test]
+    fn test_reduce_all() {
+        let bases: Vec<Matrix<Integer>> = (0..4)
+            .map(|seed| generate::random_basis(5, 20, seed))
+            .collect();
+        let params = ReductionParams::default();
+
+        let generated = std::cell::Cell::new(0);
+        let mut reduced = reduce::reduce_all(
+            bases.iter().map(|basis| {
+                generated.set(generated.get() + 1);
+                basis.clone()
+            }),
+            &params,
+        );
+        assert_eq!(0, generated.get());
+        let first = reduced.next().unwrap();
+        assert_eq!(1, generated.get());
+
+        let mut expected = bases[0].clone();
+        bigl2::lattice_reduce(&mut expected, 0.51, 0.99);
+        assert_eq!(expected, first);
+        for (i, basis) in reduced.enumerate() {
+            let mut expected = bases[i + 1].clone();
+            bigl2::lattice_reduce(&mut expected, 0.51, 0.99);
+            assert_eq!(expected, basis);
+        }
+
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+
+            let parallel: Vec<Matrix<Integer>> =
+                reduce::par_reduce_all(bases.clone(), &params).collect();
+            let sequential: Vec<Matrix<Integer>> = reduce::reduce_all(bases, &params).collect();
+            assert_eq!(sequential, parallel);
+        }
+    }
+
+    #[test]
+    fn test_eta_delta_bar() {
+        assert_eq!(rug::Rational::from((3, 4)), l2::eta_bar(1.));
+        assert_eq!(rug::Rational::from((7, 8)), l2::delta_bar(0.75));
+
+        let eta_bar = l2::eta_bar(0.51);
+        assert!(eta_bar > 0.5 && eta_bar < 0.51);
+        assert_eq!(rug::Rational::from_f64(0.505).unwrap(), eta_bar);
+        let delta_bar = l2::delta_bar(0.99);
+        assert!(delta_bar > 0.99 && delta_bar < 1);
+        assert_eq!(rug::Rational::from_f64(0.995).unwrap(), delta_bar);
+    }
+
+    #[test]
+    fn test_matrix_reset() {
+        let basis = generate::random_basis(5, 30, 9);
+        let mut expected = basis.clone();
+        bigl2::lattice_reduce(&mut expected, 0.51, 0.99);
+
+        let mut buffer = generate::random_basis(5, 50, 10);
+        bigl2::lattice_reduce(&mut buffer, 0.51, 0.99);
+        buffer.reset();
+        assert_eq!(Matrix::init(5, 5), buffer);
+
+        for i in 0..5 {
+            buffer[i] += &basis[i];
+        }
+        bigl2::lattice_reduce(&mut buffer, 0.51, 0.99);
+        assert_eq!(expected, buffer);
+    }
+
+    #[test]
+    fn test_shortest_reduced() {
+        let mut basis = generate::random_basis(8, 30, 12);
+        let shortest = svp::shortest_reduced(&mut basis, 0.51, 0.99);
+        let norm = shortest.dot(&shortest);
+        assert!(norm > 0);
+        assert!((0..8).any(|i| basis[i] == shortest));
+        assert!((0..8).all(|i| basis[i].dot(&basis[i]) >= norm));
+    }
+
+    #[test]
+    fn test_inner_product() {
+        fn norm_sq<V: InnerProduct>(v: &V) -> V::Scalar {
+            v.dot(v)
+        }
+        fn sum_norms<V: Dot>(vectors: &[V]) -> Vec<V::Output> {
+            vectors.iter().map(|v| v.dot(v)).collect()
+        }
+
+        let f = VectorF::from_vector(vec![3., 4.]);
+        assert_eq!(25., norm_sq(&f));
+        let b = BigVector::from_vector(vec![Integer::from(-2), Integer::from(5)]);
+        assert_eq!(29, norm_sq(&b));
+
+        let references = vec![&b, &b];
+        assert_eq!(vec![Integer::from(29); 2], sum_norms(&references));
+    }
+
+    #[test]
+    fn test_closed_parameter_bounds() {
+        let rows = [[1, 0, 0, 1345], [0, 1, 0, 35], [0, 0, 1, 154]];
+        let mut basis: Matrix<Integer> = Matrix::from_columns(
+            rows.iter()
+                .map(|row| BigVector::from_vector(row.iter().map(|&x| Integer::from(x)).collect()))
+                .collect(),
+        );
+        bigl2::lattice_reduce(&mut basis, 0.5, 0.75);
+        assert!(verify::is_lll_reduced(&basis, 0.75, 0.5));
+
+        let mut float_basis = Matrix::from_columns(vec![
+            VectorF::from_vector(vec![1., 0., 0., 1345.]),
+            VectorF::from_vector(vec![0., 1., 0., 35.]),
+            VectorF::from_vector(vec![0., 0., 1., 154.]),
+        ]);
+        l2f::lattice_reduce(&mut float_basis, 0.5, 0.25 + 0.3);
+        assert!(verify::is_lll_reduced_f(&float_basis, 0.25 + 0.3, 0.5));
+
+        assert!(l2::eta_bar(0.5) > 0.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_eta_below_one_half() {
+        let mut basis = Matrix::from_columns(vec![
+            VectorF::from_vector(vec![1., 0.]),
+            VectorF::from_vector(vec![0., 1.]),
+        ]);
+        l2f::lattice_reduce(&mut basis, 0.49, 0.75);
+    }
+
+    #[test]
+    fn test_empty_construction() {
+        assert_eq!(Err(Empty), BigVector::try_from_vector(vec![]));
+        assert!(VectorF::try_from_vector(vec![]).is_err());
+        let vector = VectorF::try_from_vector(vec![1., 2.]).unwrap();
+        assert_eq!(2, vector.dimension());
+
+        assert_eq!(
+            Err(LatticeError::Empty),
+            Matrix::<Integer>::try_from_columns(vec![])
+        );
+        assert!(Matrix::try_from_columns(vec![BigVector::from_vector(vec![])]).is_err());
+        let basis = Matrix::try_from_columns(vec![VectorF::from_vector(vec![1., 0.])]).unwrap();
+        assert_eq!((1, 2), basis.dimensions());
+        assert_eq!(
+            Err(LatticeError::DimensionMismatch(2, 3)),
+            Matrix::try_from_columns(vec![
+                VectorF::from_vector(vec![1., 0.]),
+                VectorF::from_vector(vec![0., 1.]),
+                VectorF::from_vector(vec![0., 0., 1.]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_update_gso_row() {
+        let basis = generate::random_basis(6, 20, 7);
+        let (n, _) = basis.dimensions();
+        let mut gram_matrix: Matrix<Integer> = Matrix::init(n, n);
+        for i in 0..n {
+            for j in 0..n {
+                gram_matrix[i][j] = basis[i].dot(&basis[j]);
+            }
+        }
+
+        let mut mu: Matrix<rug::Rational> = Matrix::init(n, n);
+        let mut r: Matrix<rug::Rational> = Matrix::init(n, n);
+        for k in 0..n {
+            gram::update_gso_row(k, &gram_matrix, &mut mu, &mut r);
+        }
+        assert_eq!(metrics::gram_schmidt_r(&basis), r);
+        assert_eq!(rug::Rational::from(1), mu[n - 1][n - 1]);
+    }
+
+    #[test]
+    fn test_bkz() {
+        let original = generate::random_basis(8, 16, 3);
+        let mut lll_basis = original.clone();
+        bigl2::lattice_reduce(&mut lll_basis, 0.5, 0.99);
+
+        let mut basis = original.clone();
+        assert_eq!(0, bkz::bkz_reduce(&mut basis, 4, 0.99));
+        assert!(verify::is_lll_reduced(&basis, 0.99, 0.5));
+        assert_eq!(
+            hnf::hermite_normal_form(&original),
+            hnf::hermite_normal_form(&basis)
+        );
+        assert!(basis[0].dot(&basis[0]) <= lll_basis[0].dot(&lll_basis[0]));
+
+        // With a single block, the first vector is a shortest vector
+        let mut full = original.clone();
+        bkz::bkz_reduce(&mut full, 8, 0.99);
+        let shortest = full[0].dot(&full[0]);
+        assert!(shortest <= basis[0].dot(&basis[0]));
+        let sampled = svp::sample_short(&original, 50, 1);
+        assert!(shortest <= sampled.dot(&sampled));
+    }
+
+    #[test]
+    fn test_bkz2() {
+        let original = generate::random_basis(12, 16, 5);
+        let mut lll_basis = original.clone();
+        bigl2::lattice_reduce(&mut lll_basis, 0.5, 0.99);
+
+        let params = BkzParams::new(6)
+            .linear_pruning()
+            .rerandomizations(2)
+            .auto_abort(1., 2)
+            .seed(3);
+        let mut basis = original.clone();
+        assert_eq!(0, bkz::bkz2_reduce(&mut basis, &params));
+        assert!(verify::is_lll_reduced(&basis, 0.99, 0.5));
+        assert_eq!(
+            hnf::hermite_normal_form(&original),
+            hnf::hermite_normal_form(&basis)
+        );
+        assert!(basis[0].dot(&basis[0]) <= lll_basis[0].dot(&lll_basis[0]));
+
+        let mut single_tour = original.clone();
+        bkz::bkz2_reduce(&mut single_tour, &BkzParams::new(6).max_tours(1));
+        assert!(verify::is_lll_reduced(&single_tour, 0.99, 0.5));
+
+        assert_eq!(vec![1., 0.75, 0.5, 0.25], svp::linear_pruning(4));
+        let (_, r) = bigl2::lattice_reduce_full(&mut lll_basis, 0.5, 0.99);
+        assert!(metrics::gso_slope(&r) < 0.);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bkz2_unsound_pruning() {
+        BkzParams::new(3).pruning(vec![1., 0.5, 0.8]);
+    }
+
+    #[test]
+    fn test_hkz() {
+        let original = generate::random_basis(7, 12, 11);
+        let mut basis = original.clone();
+        assert_eq!(0, bkz::hkz_reduce(&mut basis));
+        assert!(verify::is_lll_reduced(&basis, 0.99, 0.5));
+        assert_eq!(
+            hnf::hermite_normal_form(&original),
+            hnf::hermite_normal_form(&basis)
+        );
+
+        // No projected lattice has a vector shorter than its first Gram-Schmidt vector
+        let (mu, norms) = metrics::gram_schmidt(&basis);
+        for k in 0..7 {
+            let block_mu = mu.submatrix(k..7, k..7);
+            assert!(svp::enumerate_shortest(&block_mu, &norms[k..], norms[k].clone()).is_none());
+        }
+
+        let mut bkz_basis = original.clone();
+        bkz::bkz_reduce(&mut bkz_basis, 7, 0.99);
+        assert_eq!(bkz_basis[0].dot(&bkz_basis[0]), basis[0].dot(&basis[0]));
+    }
+
+    #[test]
+    fn test_deep_lll() {
+        let original = generate::random_basis(10, 20, 13);
+        let mut basis = original.clone();
+        biglll::deep_reduce(&mut basis, 0.99);
+        assert!(verify::is_lll_reduced(&basis, 0.99, 0.5));
+        assert_eq!(
+            hnf::hermite_normal_form(&original),
+            hnf::hermite_normal_form(&basis)
+        );
+
+        let mut float_basis = Matrix::from_columns(vec![
+            VectorF::from_vector(vec![1., 0., 0., 1345.]),
+            VectorF::from_vector(vec![0., 1., 0., 35.]),
+            VectorF::from_vector(vec![0., 0., 1., 154.]),
+        ]);
+        let mut big_basis = float_basis.round_to_big();
+        lllf::deep_reduce(&mut float_basis, 0.99);
+        biglll::deep_reduce(&mut big_basis, 0.99);
+        assert_eq!(big_basis, float_basis.round_to_big());
+    }
+
+    #[test]
+    fn test_slide() {
+        let original = generate::random_basis(12, 16, 17);
+        let mut lll_basis = original.clone();
+        bigl2::lattice_reduce(&mut lll_basis, 0.5, 0.99);
+
+        let mut basis = original.clone();
+        assert_eq!(0, slide::slide_reduce(&mut basis, 4, 0.99));
+        assert!(verify::is_lll_reduced(&basis, 0.99, 0.5));
+        assert_eq!(
+            hnf::hermite_normal_form(&original),
+            hnf::hermite_normal_form(&basis)
+        );
+        assert!(basis[0].dot(&basis[0]) <= lll_basis[0].dot(&lll_basis[0]));
+
+        // Primal and dual blocks are reduced
+        for start in (0..12).step_by(4) {
+            assert!(!bkz::reduce_block(&mut basis, start, start + 4, 0.99, None));
+        }
+        for start in (1..9).step_by(4) {
+            assert!(!bkz::dual_reduce_block(&mut basis, start, start + 4, 0.99));
+        }
+
+        // A dual reduction makes the last Gram-Schmidt vector longer
+        let mut unreduced = generate::random_basis(6, 16, 17);
+        let (_, norms) = metrics::gram_schmidt(&unreduced);
+        assert!(bkz::dual_reduce_block(&mut unreduced, 0, 6, 0.99));
+        let (_, reduced_norms) = metrics::gram_schmidt(&unreduced);
+        assert!(reduced_norms[5] > norms[5]);
+        assert!(!bkz::dual_reduce_block(&mut unreduced, 0, 6, 0.99));
+    }
+
+    #[test]
+    fn test_sd_bkz() {
+        let original = generate::random_basis(10, 16, 19);
+        let mut lll_basis = original.clone();
+        bigl2::lattice_reduce(&mut lll_basis, 0.5, 0.99);
+
+        let mut basis = original.clone();
+        assert_eq!(0, bkz::sd_bkz_reduce(&mut basis, 4, 0.99));
+        assert!(verify::is_lll_reduced(&basis, 0.99, 0.5));
+        assert_eq!(
+            hnf::hermite_normal_form(&original),
+            hnf::hermite_normal_form(&basis)
+        );
+        assert!(basis[0].dot(&basis[0]) <= lll_basis[0].dot(&lll_basis[0]));
+        for k in 0..=6 {
+            assert!(!bkz::reduce_block(&mut basis, k, k + 4, 0.99, None));
+            assert!(!bkz::dual_reduce_block(&mut basis, k, k + 4, 0.99));
+        }
+
+        // Bases of rank smaller than the block size are only LLL-reduced
+        let mut small = original.submatrix(0..3, 0..10);
+        assert_eq!(0, bkz::sd_bkz_reduce(&mut small, 4, 0.99));
+        assert!(verify::is_lll_reduced(&small, 0.99, 0.5));
+    }
+
+    #[test]
+    fn test_gauss_reduce_vectors() {
+        type I = Integer;
+
+        // Rank-2 lattice in dimension 4
+        let b_0 = BigVector::from_vector(vec![I::from(1), I::from(2), I::from(3), I::from(1000)]);
+        let b_1 = BigVector::from_vector(vec![I::from(2), I::from(3), I::from(5), I::from(2001)]);
+        let (c_0, c_1) = gauss::gauss_reduce(b_0.clone(), b_1.clone());
+
+        let mut basis = Matrix::from_columns(vec![b_0, b_1]);
+        bigl2::lattice_reduce(&mut basis, 0.51, 0.99);
+        assert_eq!(basis[0].dot(&basis[0]), c_0.dot(&c_0));
+        assert!(c_0.dot(&c_0) <= c_1.dot(&c_1));
+        assert!(I::from(c_0.dot(&c_1).abs_ref()) * 2 <= c_0.dot(&c_0));
+        assert_eq!(
+            hnf::hermite_normal_form(&basis),
+            hnf::hermite_normal_form(&Matrix::from_columns(vec![c_0, c_1]))
+        );
+    }
+
+    #[test]
+    fn test_reduce_generating_set() {
+        let rows = [[2, 4, 6], [1, 2, 3], [0, 1, 1], [3, 7, 10], [5, 0, 4]];
+        let generators: Matrix<Integer> = Matrix::from_columns(
+            rows.iter()
+                .map(|row| BigVector::from_vector(row.iter().map(|&x| Integer::from(x)).collect()))
+                .collect(),
+        );
+        let basis = bigl2::reduce_generating_set(&generators, 0.51, 0.99);
+        assert_eq!((3, 3), basis.dimensions());
+        assert!(verify::is_lll_reduced(&basis, 0.99, 0.51));
+        assert_eq!(
+            hnf::hermite_normal_form(&generators),
+            hnf::hermite_normal_form(&basis)
+        );
+
+        let zeros: Matrix<Integer> = Matrix::init(2, 3);
+        let basis = bigl2::reduce_generating_set(&zeros, 0.51, 0.99);
+        assert_eq!((0, 3), basis.dimensions());
+    }
+
+    #[test]
+    fn test_reduce_with_removals() {
+        let rows = [
+            [1, 0, 0, 0],
+            [3, 1, 0, 0],
+            [0, 2, 1000, 0],
+            [5, 0, 7, 100000],
+        ];
+        let original: Matrix<Integer> = Matrix::from_columns(
+            rows.iter()
+                .map(|row| BigVector::from_vector(row.iter().map(|&x| Integer::from(x)).collect()))
+                .collect(),
+        );
+
+        let mut basis = original.clone();
+        let bound = Integer::from(10000);
+        let removed = bigl2::lattice_reduce_with_removals(&mut basis, 0.51, 0.99, &bound);
+        assert_eq!(2, removed);
+        assert_eq!((2, 4), basis.dimensions());
+        assert!(verify::is_lll_reduced(&basis, 0.99, 0.51));
+        let r = metrics::gram_schmidt_r(&basis);
+        assert!(r[1][1] <= bound);
+
+        let mut basis = original.clone();
+        let bound = Integer::from(1) << 40;
+        let removed = bigl2::lattice_reduce_with_removals(&mut basis, 0.51, 0.99, &bound);
+        assert_eq!(0, removed);
+        assert_eq!((4, 4), basis.dimensions());
+
+        // The long vector is swapped to the end by the reduction, then removed
+        type I = Integer;
+        let mut basis: Matrix<I> = Matrix::init(3, 3);
+        basis[0] = BigVector::from_vector(vec![I::from(1), I::from(0), I::from(0)]);
+        basis[1] = BigVector::from_vector(vec![I::from(0), I::from(0), I::from(100)]);
+        basis[2] = BigVector::from_vector(vec![I::from(3), I::from(1), I::from(0)]);
+        let removed = bigl2::lattice_reduce_with_removals(&mut basis, 0.51, 0.99, &I::from(50));
+        assert_eq!(1, removed);
+        assert_eq!("[1 0 0]\n[0 1 0]", basis.to_string());
+    }
+
+    #[test]
+    fn test_integral_lll() {
+        let original = generate::random_basis(10, 64, 23);
+        let mut basis = original.clone();
+        intlll::lattice_reduce(&mut basis, 0.99);
+        assert!(verify::is_lll_reduced(&basis, 0.99, 0.5));
+        assert_eq!(
+            hnf::hermite_normal_form(&original),
+            hnf::hermite_normal_form(&basis)
+        );
+
+        // Huge entries, as in the crate example
+        let mut basis: Matrix<Integer> = Matrix::init(3, 4);
+        basis[0] = BigVector::from_vector(vec![
+            Integer::from(1) << 10000,
+            Integer::from(0),
+            Integer::from(0),
+            Integer::from(1345),
+        ]);
+        basis[1] = BigVector::from_vector(vec![
+            Integer::from(0),
+            Integer::from(1),
+            Integer::from(0),
+            Integer::from(35),
+        ]);
+        basis[2] = BigVector::from_vector(vec![
+            Integer::from(0),
+            Integer::from(0),
+            Integer::from(1),
+            Integer::from(154),
+        ]);
+        let mut l2_basis = basis.clone();
+        intlll::lattice_reduce(&mut basis, 0.75);
+        bigl2::lattice_reduce(&mut l2_basis, 0.5, 0.75);
+        assert!(verify::is_lll_reduced(&basis, 0.75, 0.5));
+        assert_eq!(basis[0].dot(&basis[0]), l2_basis[0].dot(&l2_basis[0]));
+    }
+
+    #[cfg(feature = "mpfr")]
+    #[test]
+    fn test_floatl2_precision_bound() {
+        use crate::l2::floatl2;
+
+        assert_eq!(53, floatl2::precision_bound(2, 0.51, 0.99));
+        let bound = floatl2::precision_bound(100, 0.51, 0.99);
+        assert!((160..200).contains(&bound));
+        assert!(floatl2::precision_bound(100, 0.6, 0.75) > bound);
+
+        let basis = generate::random_basis(12, 200, 29);
+        let mut reduced = basis.clone();
+        assert_eq!(0, floatl2::lattice_reduce(&mut reduced, 0.51, 0.99));
+        assert!(verify::is_lll_reduced(&reduced, 0.99, 0.51));
+        assert_eq!(
+            hnf::hermite_normal_form(&basis),
+            hnf::hermite_normal_form(&reduced)
+        );
+    }
+
+    #[test]
+    fn test_bigl2_reduce_auto() {
+        let basis = generate::random_basis(10, 40, 7);
+        let mut reduced = basis.clone();
+        assert_eq!(0, bigl2::reduce_auto(&mut reduced, 0.51, 0.99));
+        assert!(verify::is_lll_reduced(&reduced, 0.99, 0.51));
+        assert_eq!(
+            hnf::hermite_normal_form(&basis),
+            hnf::hermite_normal_form(&reduced)
+        );
+
+        // Coefficients far beyond the precision of the double floating-point numbers
+        let weights: Vec<rug::Integer> = (0..8)
+            .map(|i| (rug::Integer::from(rug::Integer::u_pow_u(3, 120 + i)) + 7 * i) >> 40)
+            .collect();
+        let target = weights[1].clone() + &weights[4] + &weights[6];
+        let mut knapsack = generate::knapsack_lattice(&weights, &target);
+        let copy = knapsack.clone();
+        assert_eq!(0, bigl2::reduce_auto(&mut knapsack, 0.51, 0.99));
+        assert!(verify::is_lll_reduced(&knapsack, 0.99, 0.51));
+        assert_eq!(
+            hnf::hermite_normal_form(&copy),
+            hnf::hermite_normal_form(&knapsack)
+        );
+
+        // Linear dependencies
+        let mut dependent = Matrix::from_columns(vec![
+            BigVector::from_vector(vec![1.into(), 2.into(), 3.into()]),
+            BigVector::from_vector(vec![2.into(), 4.into(), 6.into()]),
+            BigVector::from_vector(vec![0.into(), 1.into(), 1.into()]),
+        ]);
+        assert_eq!(1, bigl2::reduce_auto(&mut dependent, 0.51, 0.99));
+    }
+
+    #[test]
+    fn test_lattice_reduce_transform() {
+        // reduced = U * input
+        let basis = generate::random_basis(6, 30, 5);
+        let columns = basis.transpose();
+        let check = |reduced: &Matrix<rug::Integer>, transform: &Matrix<rug::Integer>| {
+            for i in 0..6 {
+                for j in 0..6 {
+                    assert_eq!(transform[i].dot(&columns[j]), reduced[i][j]);
+                }
+            }
+        };
+
+        let mut reduced = basis.clone();
+        let transform = biglll::lattice_reduce_transform(&mut reduced);
+        check(&reduced, &transform);
+
+        let mut expected = basis.clone();
+        bigl2::lattice_reduce(&mut expected, 0.51, 0.99);
+        let mut reduced = basis.clone();
+        let (zeros, transform) = bigl2::lattice_reduce_transform(&mut reduced, 0.51, 0.99);
+        assert_eq!(0, zeros);
+        assert_eq!(expected, reduced);
+        check(&reduced, &transform);
+
+        // Floating-point reductions of a small basis
+        let input = basis.to_float();
+        let columns = input.transpose();
+        let check = |reduced: &Matrix<f64>, transform: &Matrix<f64>| {
+            for i in 0..6 {
+                for j in 0..6 {
+                    assert_eq!(transform[i].dot(&columns[j]), reduced[i][j]);
+                }
+            }
+        };
+        let mut reduced = input.clone();
+        let transform = lllf::lattice_reduce_transform(&mut reduced);
+        check(&reduced, &transform);
+        let mut reduced = input.clone();
+        let (_, transform) = l2f::lattice_reduce_transform(&mut reduced, 0.51, 0.99);
+        check(&reduced, &transform);
+    }
+
+    #[test]
+    fn test_lattice_reduce_gram_form() {
+        // Positive semidefinite form of rank 2 (the basis (1, 2), (2, 4), (0, 1))
+        let rows = [[5, 10, 2], [10, 20, 4], [2, 4, 1]];
+        let mut form = Matrix::from_columns(
+            rows.iter()
+                .map(|row| BigVector::from_vector(row.iter().map(|&x| x.into()).collect()))
+                .collect(),
+        );
+        let transform = gram::lattice_reduce_gram(&mut form, 0.51, 0.99);
+        assert_eq!(form[2][2], 0);
+        let det = form[0][0].clone() * &form[1][1] - form[0][1].clone() * &form[1][0];
+        assert_eq!(det, 1);
+        let inverse = transform.to_rational().inverse();
+        assert!(inverse.is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "the Gram matrix is not positive semidefinite")]
+    fn test_lattice_reduce_gram_indefinite() {
+        // Indefinite form x² + 4xy + y²
+        let mut form = Matrix::from_columns(vec![
+            BigVector::from_vector(vec![1.into(), 2.into()]),
+            BigVector::from_vector(vec![2.into(), 1.into()]),
+        ]);
+        gram::lattice_reduce_gram(&mut form, 0.51, 0.99);
+    }
+
+    #[test]
+    #[should_panic(expected = "the Gram matrix must be symmetric")]
+    fn test_lattice_reduce_gram_not_symmetric() {
+        let mut form = Matrix::from_columns(vec![
+            BigVector::from_vector(vec![1.into(), 0.into()]),
+            BigVector::from_vector(vec![1.into(), 1.into()]),
+        ]);
+        gram::lattice_reduce_gram(&mut form, 0.51, 0.99);
+    }
+
+    #[test]
+    fn test_bigl2_reducer() {
+        let basis = generate::random_basis(8, 40, 13);
+        let mut expected = basis.clone();
+        bigl2::lattice_reduce(&mut expected, 0.51, 0.99);
+
+        let mut reducer = bigl2::Reducer::new(basis, 0.51, 0.99);
+        assert!(!reducer.is_reduced());
+        reducer.reduce_until(4);
+        assert!(reducer.index() >= 4);
+        let (mu, _) = reducer.gso();
+        let prefix = reducer.basis().submatrix(0..4, 0..8);
+        assert!(verify::is_lll_reduced(&prefix, 0.99, 0.51));
+        assert!(mu[3][2].clone().abs() <= 0.51);
+
+        // Pausing and resuming gives the same basis as a single reduction
+        while !reducer.run(3) {}
+        assert_eq!(0, reducer.reduce());
+        assert_eq!(0, reducer.zeros());
+        assert_eq!(expected, reducer.into_basis());
+
+        // Linear dependencies
+        let dependent = Matrix::from_columns(vec![
+            BigVector::from_vector(vec![1.into(), 2.into()]),
+            BigVector::from_vector(vec![3.into(), 6.into()]),
+        ]);
+        let mut reducer = bigl2::Reducer::new(dependent, 0.51, 0.99);
+        assert_eq!(1, reducer.reduce());
+    }
+
+    #[test]
+    fn test_shortest_vector() {
+        // Exhaustive search over small coefficients of a reduced basis
+        let mut basis = generate::random_basis(5, 12, 3);
+        let shortest = svp::shortest_vector(&basis);
+        let norm = shortest.dot(&shortest);
+        assert!(norm > 0);
+
+        bigl2::lattice_reduce(&mut basis, 0.51, 0.99);
+        let mut best: Option<rug::Integer> = None;
+        for index in 1..5usize.pow(5) {
+            let mut v = BigVector::init(5);
+            let mut code = index;
+            for i in 0..5 {
+                let x = rug::Integer::from(code % 5) - 2;
+                code /= 5;
+                v = v.add(&basis[i].mulf(&x));
+            }
+            let candidate = v.dot(&v);
+            if candidate > 0 && best.as_ref().is_none_or(|best| candidate < *best) {
+                best = Some(candidate);
+            }
+        }
+        assert_eq!(Some(norm), best);
+
+        // Never longer than the heuristics, and in the lattice
+        let basis = generate::random_basis(12, 20, 8);
+        let shortest = svp::shortest_vector(&basis);
+        let sampled = svp::sample_short(&basis, 20, 1);
+        assert!(shortest.dot(&shortest) <= sampled.dot(&sampled));
+        let mut extended = basis.clone();
+        extended = extended.vstack(&Matrix::from_columns(vec![shortest]));
+        assert_eq!(1, bigl2::lattice_reduce(&mut extended, 0.51, 0.99));
+
+        let zero: Matrix<rug::Integer> = Matrix::init(2, 3);
+        let shortest = svp::shortest_vector(&zero);
+        assert_eq!(3, shortest.dimension());
+        assert_eq!(0, shortest.dot(&shortest));
+    }
+
+    #[test]
+    fn test_shortest_vector_pruned() {
+        assert_eq!(1., svp::success_probability(&[1.; 10]));
+        let linear = svp::success_probability(&svp::linear_pruning(10));
+        assert!(0. < linear && linear < 1.);
+        let extreme: Vec<f64> = svp::linear_pruning(10).iter().map(|x| x * x).collect();
+        assert!(svp::success_probability(&extreme) < linear);
+
+        let basis = generate::random_basis(10, 20, 4);
+        let exact = svp::shortest_vector(&basis);
+        let pruned = svp::shortest_vector_pruned(&basis, &svp::linear_pruning(10), 0.9, 2);
+        assert_eq!(exact.dot(&exact), pruned.dot(&pruned));
+
+        // The pruned vector is in the lattice
+        let mut extended = basis.vstack(&Matrix::from_columns(vec![pruned]));
+        assert_eq!(1, bigl2::lattice_reduce(&mut extended, 0.51, 0.99));
+    }
+
+    #[test]
+    fn test_gauss_sieve() {
+        let basis = generate::random_basis(10, 20, 6);
+        let exact = svp::shortest_vector(&basis);
+        let sieved = sieve::gauss_sieve(&basis, 200);
+        assert_eq!(exact.dot(&exact), sieved.dot(&sieved));
+
+        // The sieved vector is in the lattice
+        let mut extended = basis.vstack(&Matrix::from_columns(vec![sieved]));
+        assert_eq!(1, bigl2::lattice_reduce(&mut extended, 0.51, 0.99));
+
+        let zero: Matrix<rug::Integer> = Matrix::init(2, 3);
+        let sieved = sieve::gauss_sieve(&zero, 10);
+        assert_eq!(0, sieved.dot(&sieved));
+    }
+
+    #[test]
+    fn test_babai_rounding() {
+        let mut basis = generate::random_basis(6, 20, 9);
+        bigl2::lattice_reduce(&mut basis, 0.51, 0.99);
+        let coefficients = (0..6).map(|i| rug::Integer::from(1000 * i + 17)).collect();
+        let target = BigVector::from_vector(coefficients);
+
+        let rounding = cvp::babai_rounding(&basis, &target);
+        let plane = cvp::nearest_plane(&basis, &target);
+        for solution in &[&rounding, &plane] {
+            let mut vector = BigVector::init(6);
+            for i in 0..6 {
+                vector = vector.add(&basis[i].mulf(&solution.coords[i]));
+            }
+            assert_eq!(vector, solution.vector);
+            let error = target.sub(&solution.vector);
+            assert_eq!(error.dot(&error), solution.distance);
+            assert!(solution.distance >= cvp::distance_to_lattice(&basis, &target));
+        }
+
+        // Lattice vectors are their own solution
+        let point = basis[0].add(&basis[3].mulf(&rug::Integer::from(-2)));
+        let solution = cvp::babai_rounding(&basis, &point);
+        assert_eq!(solution.vector, point);
+        assert_eq!(solution.distance, 0);
+    }
+
+    #[test]
+    fn test_closest_vector_pruned() {
+        let basis = generate::random_basis(8, 16, 21);
+        let coefficients = (0..8).map(|i| rug::Integer::from(3000 * i - 7)).collect();
+        let target = BigVector::from_vector(coefficients);
+
+        let exact = cvp::closest_vector(&basis, &target, None);
+        assert_eq!(exact.vector, cvp::closest_vector_exact(&basis, &target));
+        assert_eq!(exact.distance, cvp::distance_to_lattice(&basis, &target));
+        let mut vector = BigVector::init(8);
+        for i in 0..8 {
+            vector = vector.add(&basis[i].mulf(&exact.coords[i]));
+        }
+        assert_eq!(vector, exact.vector);
+
+        let pruning = svp::linear_pruning(8);
+        let pruned = cvp::closest_vector(&basis, &target, Some(&pruning));
+        assert!(pruned.distance >= exact.distance);
+        let mut reduced = basis.clone();
+        bigl2::lattice_reduce(&mut reduced, 0.501, 0.998);
+        assert!(pruned.distance <= cvp::nearest_plane(&reduced, &target).distance);
+    }
+
+    #[test]
+    fn test_kannan_embedding() {
+        let basis = generate::random_basis(6, 20, 30);
+        let embedding = cvp::kannan_embedding(&basis, &basis[2], &rug::Integer::from(7));
+        assert_eq!(embedding.dimensions(), (7, 7));
+        assert_eq!(embedding[6][6], 7);
+        assert_eq!(embedding[0][6], 0);
+
+        // A lattice vector plus a small error
+        let mut reduced = basis.clone();
+        bigl2::lattice_reduce(&mut reduced, 0.51, 0.99);
+        let point = reduced[0].add(&reduced[4].mulf(&rug::Integer::from(3)));
+        let error = [1, 0, -1, 0, 0, 1]
+            .iter()
+            .map(|&x| rug::Integer::from(x))
+            .collect();
+        let error = BigVector::from_vector(error);
+        let target = point.add(&error);
+
+        let factor = rug::Integer::from(1);
+        let solution = cvp::closest_vector_embedding(&basis, &target, &factor).unwrap();
+        assert_eq!(solution.vector, point);
+        assert_eq!(solution.distance, 3);
+        assert_eq!(solution, cvp::closest_vector(&basis, &target, None));
+    }
+
+    #[test]
+    fn test_verify_size_reduced() {
+        let mut basis = generate::random_basis(6, 30, 2);
+        assert!(!verify::is_size_reduced(&basis, 0.51));
+        bigl2::lattice_reduce(&mut basis, 0.51, 0.99);
+        assert!(verify::is_size_reduced(&basis, 0.51));
+
+        // Size-reduced, but not LLL-reduced: a long vector before a short one
+        let swapped = Matrix::from_columns(vec![
+            BigVector::from_vector(vec![10.into(), 0.into()]),
+            BigVector::from_vector(vec![1.into(), 1.into()]),
+        ]);
+        assert!(verify::is_size_reduced(&swapped, 0.5));
+        assert!(!verify::is_lll_reduced(&swapped, 0.75, 0.5));
+
+        // The factors are given in the order delta, eta: |mu_10| = 1/2 and the Lovasz
+        // condition holds for delta <= 1/2
+        let boundary = Matrix::from_columns(vec![
+            BigVector::from_vector(vec![2.into(), 0.into()]),
+            BigVector::from_vector(vec![1.into(), 1.into()]),
+        ]);
+        assert!(verify::is_lll_reduced(&boundary, 0.3, 0.5));
+        assert!(!verify::is_lll_reduced(&boundary, 0.5, 0.3));
+
+        // Output of the floating-point reduction
+        let mut float = generate::random_basis(6, 20, 3).to_float();
+        assert!(!verify::is_lll_reduced_f(&float, 0.99, 0.51));
+        l2f::lattice_reduce(&mut float, 0.51, 0.99);
+        assert!(verify::is_size_reduced_f(&float, 0.51));
+        assert!(verify::is_lll_reduced_f(&float, 0.99, 0.51));
+    }
+
+    #[test]
+    fn test_l2_stats_counters() {
+        let basis = generate::random_basis(8, 40, 5);
+        let input_bits = (0..8)
+            .flat_map(|i| basis[i].iter())
+            .map(Integer::significant_bits)
+            .max()
+            .unwrap();
+
+        let mut reduced = basis.clone();
+        let (zeros, stats) =
+            bigl2::lattice_reduce_with_stats(&mut reduced, &ReductionParams::default()).unwrap();
+        assert_eq!(0, zeros);
+        assert!(stats.swaps > 0);
+        assert!(stats.size_reductions > 0);
+        assert!(stats.iterations >= stats.swaps);
+        assert!(stats.max_bits >= input_bits);
+
+        let mut expected = basis.clone();
+        bigl2::lattice_reduce(&mut expected, 0.51, 0.99);
+        assert_eq!(expected.to_string(), reduced.to_string());
+    }
+
+    #[test]
+    fn test_l2_progress_cancel() {
+        use std::ops::ControlFlow;
+
+        let basis = generate::random_basis(8, 40, 6);
+        let mut expected = basis.clone();
+        bigl2::lattice_reduce(&mut expected, 0.51, 0.99);
+
+        let mut reduced = basis.clone();
+        let mut calls = 0;
+        let zeros = bigl2::lattice_reduce_with_progress(
+            &mut reduced,
+            &ReductionParams::default(),
+            &mut |progress| {
+                calls += 1;
+                assert_eq!(calls, progress.iterations);
+                assert!(progress.index < progress.rank);
+                ControlFlow::Continue(())
+            },
+        );
+        assert_eq!(Ok(0), zeros);
+        assert!(calls > 0);
+        assert_eq!(expected.to_string(), reduced.to_string());
+
+        let mut cancelled = basis.clone();
+        let result = bigl2::lattice_reduce_with_progress(
+            &mut cancelled,
+            &ReductionParams::default(),
+            &mut |progress| {
+                if progress.iterations >= 3 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            },
+        );
+        assert_eq!(Err(ReductionError::Cancelled), result);
+        // The partially reduced basis generates the same lattice
+        bigl2::lattice_reduce(&mut cancelled, 0.51, 0.99);
+        assert_eq!(expected.to_string(), cancelled.to_string());
+    }
+
+    #[test]
+    fn test_reduction_budget() {
+        use std::time::Duration;
+
+        let basis = generate::random_basis(10, 40, 7);
+        let mut expected = basis.clone();
+        bigl2::lattice_reduce(&mut expected, 0.51, 0.99);
+
+        let mut partial = basis.clone();
+        let budget = ReductionBudget {
+            max_iterations: Some(5),
+            ..ReductionBudget::default()
+        };
+        let (error, progress) =
+            bigl2::lattice_reduce_with_budget(&mut partial, &ReductionParams::default(), budget)
+                .unwrap_err();
+        assert_eq!(ReductionError::IterationLimit, error);
+        assert_eq!(5, progress.iterations);
+        assert!(progress.index < progress.rank);
+
+        let budget = ReductionBudget {
+            max_duration: Some(Duration::from_secs(0)),
+            ..ReductionBudget::default()
+        };
+        let params = ReductionParams::default().max_iterations(1).budget(budget);
+        assert_eq!(
+            Err(ReductionError::TimeLimit),
+            bigl2::lattice_reduce_with(&mut partial, &params)
+        );
+
+        let budget = ReductionBudget {
+            max_iterations: Some(100_000),
+            max_duration: Some(Duration::from_secs(3600)),
+        };
+        assert_eq!(
+            Ok(0),
+            bigl2::lattice_reduce_with_budget(&mut partial, &ReductionParams::default(), budget)
+        );
+        assert_eq!(expected.to_string(), partial.to_string());
+
+        let mut bkz_basis = expected.clone();
+        let budget = ReductionBudget {
+            max_iterations: Some(0),
+            ..ReductionBudget::default()
+        };
+        let (error, progress) =
+            bkz::bkz2_reduce_with_budget(&mut bkz_basis, &BkzParams::new(4), budget).unwrap_err();
+        assert_eq!(ReductionError::IterationLimit, error);
+        assert_eq!(0, progress.iterations);
+        assert_eq!(expected.to_string(), bkz_basis.to_string());
+        assert_eq!(
+            Ok(0),
+            bkz::bkz2_reduce_with_budget(
+                &mut bkz_basis,
+                &BkzParams::new(4),
+                ReductionBudget::default()
+            )
+        );
+    }
+
+    #[test]
+    fn test_lattice_errors() {
+        let mut basis = generate::random_basis(4, 16, 2);
+        assert_eq!(
+            Err(LatticeError::RangeOutOfBounds {
+                start: 3,
+                end: 5,
+                bound: 4
+            }),
+            basis.try_submatrix(3..5, 0..1)
+        );
+        assert_eq!(
+            Err(LatticeError::DimensionMismatch(4, 3)),
+            basis.try_hstack(&Matrix::init(3, 1))
+        );
+        assert_eq!(
+            Err(LatticeError::IndexOutOfBounds { index: 4, bound: 4 }),
+            basis.try_swap_rows(0, 4)
+        );
+        assert_eq!(
+            Err(LatticeError::IndexOutOfBounds { index: 4, bound: 4 }),
+            basis.try_set(0, 4, Integer::from(1))
+        );
+        assert_eq!(
+            Err(LatticeError::RangeOutOfBounds {
+                start: usize::MAX,
+                end: usize::MAX,
+                bound: 4
+            }),
+            basis.try_assign_submatrix(usize::MAX, 0, &Matrix::init(1, 1))
+        );
+        let mismatch: LatticeError = basis[0]
+            .try_add(&BigVector::from_vector(vec![Integer::from(1)]))
+            .unwrap_err()
+            .into();
+        assert_eq!(LatticeError::DimensionMismatch(4, 1), mismatch);
+
+        let unchanged = basis.clone();
+        assert_eq!(
+            Err(LatticeError::InvalidDelta(1.)),
+            bigl2::try_lattice_reduce(&mut basis, 0.51, 1.)
+        );
+        assert_eq!(
+            Err(LatticeError::InvalidEta(0.4)),
+            bigl2::try_lattice_reduce(&mut basis, 0.4, 0.99)
+        );
+        assert_eq!(unchanged, basis);
+        assert_eq!(Ok(0), bigl2::try_lattice_reduce(&mut basis, 0.51, 0.99));
+
+        let rows = vec![
+            vec![Integer::from(1), Integer::from(2)],
+            vec![Integer::from(3)],
+        ];
+        assert_eq!(
+            Err(LatticeError::DimensionMismatch(2, 1)),
+            crate::try_reduce_int(rows, 0.51, 0.99)
+        );
+    }
+
+    #[test]
+    fn test_bigfloat_non_finite() {
+        use crate::scalars::BigFloat;
+
+        // The coefficients overflow the double floating-point numbers
+        let big: Integer = Integer::from(1) << 1100;
+        let rows = [[1, 0, 3], [2, 1, 0], [0, 5, 1]];
+        let mut basis = Matrix::from_columns(
+            rows.iter()
+                .map(|row| row.iter().map(|&x| big.clone() * x + x).collect())
+                .collect(),
+        );
+        let expected = basis.clone();
+        assert_eq!(
+            Err(ReductionError::Precision),
+            l2::lattice_reduce_stats::<BigFloat>(&mut basis, &ReductionParams::default(), None)
+                .map(|(zeros, _)| zeros)
+        );
+        // The basis still generates the same lattice
+        assert_eq!(
+            hnf::hermite_normal_form(&expected),
+            hnf::hermite_normal_form(&basis)
+        );
+    }
+
+    #[test]
+    fn test_l2f_non_finite() {
+        // The squared norms overflow the double floating-point numbers
+        let mut basis = Matrix::from_columns(vec![
+            VectorF::from_vector(vec![1e200, 0.]),
+            VectorF::from_vector(vec![3e200, 1e200]),
+        ]);
+        assert_eq!(
+            Err(LatticeError::Reduction(ReductionError::Precision)),
+            l2f::try_lattice_reduce(&mut basis, 0.51, 0.99)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "insufficient floating-point precision")]
+    fn test_l2f_non_finite_panics() {
+        let mut basis = Matrix::from_columns(vec![
+            VectorF::from_vector(vec![1e200, 0.]),
+            VectorF::from_vector(vec![3e200, 1e200]),
+        ]);
+        l2f::lattice_reduce(&mut basis, 0.51, 0.99);
+    }
 }
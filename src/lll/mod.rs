@@ -1,93 +1,384 @@
-//! The Lenstra-Lenstra-Lovasz algorithm [LLL82]
-
-use crate::matrix::Matrix;
-use crate::scalars::{Scalars, FromExt};
-use crate::vector::{Dot, Vector, Coefficient};
-
-/// Lattice reduction using the original Lenstra-Lenstra-Lovasz algorithm
-///
-/// This implementation uses generic Scalars for arithmetic operations.
-/// The value of `delta` is set to 0.75.
-///
-///   - `basis`: A generating matrix for the lattice
-///
-/// The basis is reduced in-place.
-pub(crate) fn lattice_reduce<S>(basis: &mut Matrix<S::Integer>)
-where
-    S: Scalars,
-    S::Integer: Coefficient,
-    Vector<S::Integer>: Dot<Output = S::Integer>,
-{
-    // Parameter delta in the Lovasz condition
-    let delta = S::Fraction::from_ext((3, 4));
-
-    let (n, _) = basis.dimensions();
-    let mut swap_condition = true;
-
-    while swap_condition {
-        // Perform rounded Gram-Schmidt orthogonalisation
-        for i in 0..n {
-            for k in 1..i {
-                let j = i - k;
-
-                let b_i = &basis[i];
-                let b_j = &basis[j];
-                let alpha: S::Integer = S::round_div(b_i.dot(&b_j), b_j.dot(&b_j));
-                basis[i] = b_i.sub(&b_j.mulf(&alpha));
-            }
-        }
-
-        // Check for the Lovasz condition and swap columns if appropriate
-        swap_condition = false;
-        for i in 0..n - 1 {
-            let b_i = &basis[i];
-            let b_ip1 = &basis[i + 1];
-
-            let lhs: S::Fraction = S::Fraction::from_ext(&b_i.dot(&b_i)) * &delta;
-
-            let alpha = S::round_div(b_ip1.dot(&b_i), b_i.dot(&b_i));
-            let vec_rhs = b_ip1.add(&b_i.mulf(&alpha));
-            let rhs = vec_rhs.dot(&vec_rhs);
-
-            if lhs > rhs {
-                basis.swap(i, i + 1);
-                swap_condition = true;
-                break;
-            }
-        }
-    }
-}
-
-pub mod biglll {
-    use crate::matrix::Matrix;
-    use crate::scalars::BigNum;
-
-    /// Lattice reduction using the original Lenstra-Lenstra-Lovasz algorithm
-    ///
-    /// This implementation uses generic `rug::Integer` and `rug::Fraction` for arithmetic operations.
-    /// The value of `delta` is set to 0.75.
-    ///
-    ///   - `basis`: A generating matrix for the lattice
-    ///
-    /// The basis is reduced in-place.
-    pub fn lattice_reduce(basis: &mut Matrix<rug::Integer>) {
-        super::lattice_reduce::<BigNum>(basis)
-    }
-}
-
-pub mod lllf {
-    use crate::matrix::Matrix;
-    use crate::scalars::Float;
-
-    /// Lattice reduction using the original Lenstra-Lenstra-Lovasz algorithm
-    ///
-    /// This implementation uses platform double floating-point numbers (IEEE 754) for arithmetic operations.
-    /// The value of `delta` is set to 0.75.
-    ///
-    ///   - `basis`: A generating matrix for the lattice
-    ///
-    /// The basis is reduced in-place.
-    pub fn lattice_reduce(basis: &mut Matrix<f64>) {
-        super::lattice_reduce::<Float>(basis)
-    }
-}
+//! The Lenstra-Lenstra-Lovasz algorithm [LLL82]
+
+use crate::l2::RowTracker;
+use crate::matrix::Matrix;
+use crate::scalars::{Scalars, FromExt};
+use crate::vector::{Dot, Vector, Coefficient};
+
+/// Lattice reduction using the original Lenstra-Lenstra-Lovasz algorithm
+///
+/// This implementation uses generic Scalars for arithmetic operations.
+/// The value of `delta` is set to 0.75.
+///
+///   - `basis`: A generating matrix for the lattice
+///   - `transform`: records the operations performed on `basis` (optional, see
+///     `l2::lattice_reduce_gso`)
+///
+/// The basis is reduced in-place.
+pub(crate) fn lattice_reduce<S>(
+    basis: &mut Matrix<S::Integer>,
+    mut transform: Option<&mut dyn RowTracker<S::Integer>>,
+) where
+    S: Scalars,
+    S::Integer: Coefficient,
+    Vector<S::Integer>: Dot<Output = S::Integer>,
+{
+    // Parameter delta in the Lovasz condition
+    let delta = S::Fraction::from_ext((3, 4));
+
+    let (n, _) = basis.dimensions();
+    let mut swap_condition = true;
+
+    while swap_condition {
+        // Perform rounded Gram-Schmidt orthogonalisation
+        for i in 0..n {
+            for k in 1..i {
+                let j = i - k;
+
+                let b_i = &basis[i];
+                let b_j = &basis[j];
+                let alpha: S::Integer = S::round_div(b_i.dot(b_j), b_j.dot(b_j));
+                basis[i] = b_i.sub(&b_j.mulf(&alpha));
+                if let Some(transform) = transform.as_deref_mut() {
+                    transform.sub_scaled(i, j, &alpha);
+                }
+            }
+        }
+
+        // Check for the Lovasz condition and swap columns if appropriate
+        swap_condition = false;
+        for i in 0..n - 1 {
+            let b_i = &basis[i];
+            let b_ip1 = &basis[i + 1];
+
+            let lhs: S::Fraction = S::Fraction::from_ext(&b_i.dot(b_i)) * &delta;
+
+            let alpha = S::round_div(b_ip1.dot(b_i), b_i.dot(b_i));
+            let vec_rhs = b_ip1.add(&b_i.mulf(&alpha));
+            let rhs = vec_rhs.dot(&vec_rhs);
+
+            if lhs > rhs {
+                basis.swap(i, i + 1);
+                if let Some(transform) = transform.as_deref_mut() {
+                    transform.swap_rows(i, i + 1);
+                }
+                swap_condition = true;
+                break;
+            }
+        }
+    }
+}
+
+/// Lattice reduction using LLL with deep insertions [SE94]
+///
+/// Instead of swapping `b_k` with `b_{k-1}` when the Lovasz condition fails, `b_k` is
+/// inserted at the first position `i` where `||π_i(b_k)||² < delta * ||b*_i||²`, `π_i`
+/// being the projection orthogonally to `b_0, ..., b_{i-1}`. The Gram-Schmidt
+/// orthogonalisation is computed again after each insertion.
+///
+///   - `basis`: A generating matrix for the lattice, of linearly independent vectors
+///   - `delta`: delta factor of the basis reduction
+///
+/// The basis is reduced in-place.
+pub(crate) fn deep_reduce<S>(basis: &mut Matrix<S::Integer>, delta: f64)
+where
+    S: Scalars,
+    S::Integer: Coefficient,
+    S::Fraction: Coefficient,
+    Vector<S::Integer>: Dot<Output = S::Integer>,
+{
+    assert!(
+        (0.25..1.).contains(&delta),
+        "delta must be in [1/4, 1), got {}",
+        delta
+    );
+    let delta = S::Fraction::from_ext(delta);
+
+    let (n, _) = basis.dimensions();
+    let (mut mu, mut norms) = gram_schmidt::<S>(basis);
+    let mut k = 1;
+    while k < n {
+        // Size reduction of b_k
+        for j in (0..k).rev() {
+            let x = S::round(&mu[k][j]);
+            basis[k] = basis[k].sub(&basis[j].mulf(&x));
+            let x = S::Fraction::from_ext(&x);
+            for l in 0..j {
+                let step = x.clone() * &mu[j][l];
+                mu[k][l] -= &step;
+            }
+            mu[k][j] -= &x;
+        }
+
+        // First position where the projection of b_k is too short
+        let mut projection = S::Fraction::from_ext(&basis[k].dot(&basis[k]));
+        let mut i = 0;
+        while i < k && projection >= delta.clone() * &norms[i] {
+            let step = mu[k][i].clone() * &mu[k][i] * &norms[i];
+            projection -= &step;
+            i += 1;
+        }
+
+        if i < k {
+            for j in (i..k).rev() {
+                basis.swap(j, j + 1);
+            }
+            let gso = gram_schmidt::<S>(basis);
+            mu = gso.0;
+            norms = gso.1;
+            k = i.max(1);
+        } else {
+            k += 1;
+        }
+    }
+}
+
+/// Gram-Schmidt coefficients `mu` and squared norms `||b*_i||²` of a basis
+fn gram_schmidt<S>(basis: &Matrix<S::Integer>) -> (Matrix<S::Fraction>, Vec<S::Fraction>)
+where
+    S: Scalars,
+    S::Integer: Coefficient,
+    S::Fraction: Coefficient,
+    Vector<S::Integer>: Dot<Output = S::Integer>,
+{
+    let (n, _) = basis.dimensions();
+    let mut mu: Matrix<S::Fraction> = Matrix::init(n, n);
+    let mut norms: Vec<S::Fraction> = Vec::with_capacity(n);
+    for i in 0..n {
+        for j in 0..=i {
+            let r = S::Fraction::from_ext(&basis[i].dot(&basis[j]))
+                - &(0..j)
+                    .map(|l| mu[j][l].clone() * &mu[i][l] * &norms[l])
+                    .sum::<S::Fraction>();
+            if j < i {
+                mu[i][j] = r / &norms[j];
+            } else {
+                mu[i][i] = S::Fraction::from(1);
+                norms.push(r);
+            }
+        }
+    }
+    (mu, norms)
+}
+
+pub mod biglll {
+    use crate::matrix::Matrix;
+    use crate::scalars::BigNum;
+
+    /// Lattice reduction using the original Lenstra-Lenstra-Lovasz algorithm
+    ///
+    /// This implementation uses generic `rug::Integer` and `rug::Fraction` for arithmetic operations.
+    /// The value of `delta` is set to 0.75.
+    ///
+    ///   - `basis`: A generating matrix for the lattice
+    ///
+    /// The basis is reduced in-place.
+    pub fn lattice_reduce(basis: &mut Matrix<rug::Integer>) {
+        super::lattice_reduce::<BigNum>(basis, None)
+    }
+
+    /// Lattice reduction using the original Lenstra-Lenstra-Lovasz algorithm, returning
+    /// the transformation
+    ///
+    ///   - `basis`: A generating matrix for the lattice
+    ///
+    /// The basis is reduced in-place. Returns the unimodular transformation `U` of the
+    /// reduction: the reduced basis is `U * basis`, where `basis` is the input basis.
+    pub fn lattice_reduce_transform(basis: &mut Matrix<rug::Integer>) -> Matrix<rug::Integer> {
+        let (n, _) = basis.dimensions();
+        let mut transform = Matrix::identity(n);
+        super::lattice_reduce::<BigNum>(basis, Some(&mut transform));
+        transform
+    }
+
+    /// Lattice reduction using LLL with deep insertions
+    ///
+    /// This implementation uses `rug::Integer` and `rug::Rational` for arithmetic operations.
+    ///
+    ///   - `basis`: A generating matrix for the lattice, of linearly independent vectors
+    ///   - `delta`: delta factor of the basis reduction
+    ///
+    /// The basis is reduced in-place.
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1
+    pub fn deep_reduce(basis: &mut Matrix<rug::Integer>, delta: f64) {
+        super::deep_reduce::<BigNum>(basis, delta)
+    }
+}
+
+pub mod lllf {
+    use crate::matrix::Matrix;
+    use crate::scalars::Float;
+
+    /// Lattice reduction using the original Lenstra-Lenstra-Lovasz algorithm
+    ///
+    /// This implementation uses platform double floating-point numbers (IEEE 754) for arithmetic operations.
+    /// The value of `delta` is set to 0.75.
+    ///
+    ///   - `basis`: A generating matrix for the lattice
+    ///
+    /// The basis is reduced in-place.
+    pub fn lattice_reduce(basis: &mut Matrix<f64>) {
+        super::lattice_reduce::<Float>(basis, None)
+    }
+
+    /// Lattice reduction using the original Lenstra-Lenstra-Lovasz algorithm, returning
+    /// the transformation
+    ///
+    ///   - `basis`: A generating matrix for the lattice
+    ///
+    /// The basis is reduced in-place. Returns the unimodular transformation `U` of the
+    /// reduction: the reduced basis is `U * basis`, where `basis` is the input basis.
+    pub fn lattice_reduce_transform(basis: &mut Matrix<f64>) -> Matrix<f64> {
+        let (n, _) = basis.dimensions();
+        let mut transform = Matrix::identity(n);
+        super::lattice_reduce::<Float>(basis, Some(&mut transform));
+        transform
+    }
+
+    /// Lattice reduction using LLL with deep insertions
+    ///
+    /// This implementation uses platform double floating-point numbers (IEEE 754) for arithmetic operations.
+    ///
+    ///   - `basis`: A generating matrix for the lattice, of linearly independent vectors
+    ///   - `delta`: delta factor of the basis reduction
+    ///
+    /// The basis is reduced in-place.
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1
+    pub fn deep_reduce(basis: &mut Matrix<f64>, delta: f64) {
+        super::deep_reduce::<Float>(basis, delta)
+    }
+}
+
+pub mod intlll {
+    use crate::matrix::Matrix;
+    use crate::vector::Dot;
+
+    use rug::{Integer, Rational};
+
+    /// Lattice reduction using the integral LLL algorithm [dW87]
+    ///
+    /// All the computations are done on integers: instead of the rational Gram-Schmidt
+    /// quantities, the reduction keeps the Gram determinants `d_i = prod_{j < i} ||b*_j||²`
+    /// (with `d_0 = 1`) and the scaled coefficients `λ_ij = d_{j+1} * mu_ij`, which are
+    /// integers, and all the divisions in their updates are exact. This avoids the `gcd`
+    /// computations of `rug::Rational` on inputs with huge entries.
+    ///
+    ///   - `basis`: A generating matrix for the lattice, of linearly independent vectors
+    ///   - `delta`: delta factor of the basis reduction
+    ///
+    /// The basis is reduced in-place: it is size-reduced (`|mu| <= 1/2`) and satisfies the
+    /// Lovasz condition with factor `delta`.
+    ///
+    /// # Panics
+    /// if delta < 1/4 or delta >= 1  
+    /// if the vectors of `basis` are linearly dependent
+    pub fn lattice_reduce(basis: &mut Matrix<Integer>, delta: f64) {
+        assert!(
+            (0.25..1.).contains(&delta),
+            "delta must be in [1/4, 1), got {}",
+            delta
+        );
+        let (p, q) = Rational::from_f64(delta).unwrap().into_numer_denom();
+
+        let (n, _) = basis.dimensions();
+        if n == 0 {
+            return;
+        }
+        let mut lambda: Matrix<Integer> = Matrix::init(n, n);
+        let mut d = vec![Integer::new(); n + 1];
+        d[0] = Integer::from(1);
+        d[1] = basis[0].dot(&basis[0]);
+        assert!(d[1] != 0, "the vectors must be linearly independent");
+
+        let mut k = 1;
+        let mut k_max = 0;
+        while k < n {
+            // Incremental Gram-Schmidt
+            if k > k_max {
+                k_max = k;
+                for j in 0..=k {
+                    let mut u = basis[k].dot(&basis[j]);
+                    for i in 0..j {
+                        u = (Integer::from(&d[i + 1] * &u)
+                            - Integer::from(&lambda[k][i] * &lambda[j][i]))
+                            / &d[i];
+                    }
+                    if j < k {
+                        lambda[k][j] = u;
+                    } else {
+                        assert!(u != 0, "the vectors must be linearly independent");
+                        d[k + 1] = u;
+                    }
+                }
+            }
+
+            // Lovasz condition: delta * d_k² > d_{k+1} * d_{k-1} + λ_{k,k-1}²
+            size_reduce(basis, &mut lambda, &d, k, k - 1);
+            let lhs = Integer::from(&q * &d[k + 1]) * &d[k - 1];
+            let rhs = Integer::from(&p * &d[k]) * &d[k]
+                - Integer::from(&q * &lambda[k][k - 1]) * &lambda[k][k - 1];
+            if lhs < rhs {
+                swap(basis, &mut lambda, &mut d, k, k_max);
+                k = (k - 1).max(1);
+            } else {
+                for l in (0..k - 1).rev() {
+                    size_reduce(basis, &mut lambda, &d, k, l);
+                }
+                k += 1;
+            }
+        }
+    }
+
+    /// Size-reduces `basis[k]` with respect to `basis[l]`
+    fn size_reduce(
+        basis: &mut Matrix<Integer>,
+        lambda: &mut Matrix<Integer>,
+        d: &[Integer],
+        k: usize,
+        l: usize,
+    ) {
+        if Integer::from(lambda[k][l].abs_ref()) * 2 <= d[l + 1] {
+            return;
+        }
+        let q: Integer = Rational::from((lambda[k][l].clone(), d[l + 1].clone()))
+            .round_ref()
+            .into();
+        basis[k] = basis[k].sub(&basis[l].mulf(&q));
+        lambda[k][l] -= Integer::from(&q * &d[l + 1]);
+        for i in 0..l {
+            let step = Integer::from(&q * &lambda[l][i]);
+            lambda[k][i] -= step;
+        }
+    }
+
+    /// Swaps `basis[k]` and `basis[k - 1]`, and updates the scaled Gram-Schmidt data
+    fn swap(
+        basis: &mut Matrix<Integer>,
+        lambda: &mut Matrix<Integer>,
+        d: &mut [Integer],
+        k: usize,
+        k_max: usize,
+    ) {
+        basis.swap(k, k - 1);
+        for j in 0..k - 1 {
+            let tmp = lambda[k][j].clone();
+            lambda[k][j] = lambda[k - 1][j].clone();
+            lambda[k - 1][j] = tmp;
+        }
+
+        let l = lambda[k][k - 1].clone();
+        let b = (Integer::from(&d[k - 1] * &d[k + 1]) + Integer::from(l.square_ref())) / &d[k];
+        for i in (k + 1)..=k_max {
+            let t = lambda[i][k].clone();
+            lambda[i][k] =
+                (Integer::from(&d[k + 1] * &lambda[i][k - 1]) - Integer::from(&l * &t)) / &d[k];
+            lambda[i][k - 1] =
+                (Integer::from(&b * &t) + Integer::from(&l * &lambda[i][k])) / &d[k + 1];
+        }
+        d[k] = b;
+    }
+}
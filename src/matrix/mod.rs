@@ -1,79 +1,563 @@
-//! Basic matrix structure for LLL
-
-use crate::vector::{Coefficient, Vector};
-
-use std::{
-    fmt::{self, Debug},
-    ops::{Index, IndexMut},
-};
-
-/// A `Matrix` is a collection of `Vector`s
-pub struct Matrix<T> {
-    /// Internal representation as a list of elements of type `T`
-    columns: Vec<Vector<T>>,
-
-    /// Dimensions of the matrix
-    dimensions: (usize, usize),
-}
-
-impl<T> Matrix<T>
-where
-    T: Coefficient,
-{
-    /// Initialise an empty `Matrix`
-    ///      - `col_num`: number of columns
-    ///      - `col_dim`: number of rows
-    pub fn init(col_num: usize, col_dim: usize) -> Self {
-        Self {
-            columns: vec![Vector::<T>::init(col_dim); col_num],
-            dimensions: (col_num, col_dim),
-        }
-    }
-
-    pub fn from_columns(columns: Vec<Vector<T>>) -> Self {
-        let dimensions = if let Some(col) = columns.first() {
-            (columns.len(), col.dimension())
-        } else {
-            (0, 0)
-        };
-        Self {
-            columns,
-            dimensions,
-        }
-    }
-
-    /// Return the matrix dimensions
-    pub fn dimensions(&self) -> (usize, usize) {
-        self.dimensions
-    }
-
-    /// Swap two columns of the matrix
-    pub fn swap(&mut self, i: usize, j: usize) {
-        self.columns.swap(i, j);
-    }
-}
-
-/// Direct access to a column
-impl<T> Index<usize> for Matrix<T> {
-    type Output = Vector<T>;
-
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.columns[index]
-    }
-}
-
-/// Direct access to a column (mutable)
-impl<T> IndexMut<usize> for Matrix<T> {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.columns[index]
-    }
-}
-
-impl<T> fmt::Debug for Matrix<T>
-where
-    T: Debug,
-{
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(f, "{:?}\n", self.columns)
-    }
-}
+//! Basic matrix structure for LLL
+
+use crate::error::{check_block, check_index, check_range, LatticeError};
+use crate::vector::{Coefficient, Vector};
+
+use std::{
+    fmt::{self, Debug, Display},
+    ops::{Index, IndexMut, Range},
+};
+
+/// A `Matrix` is a collection of `Vector`s
+///
+/// The vectors are the rows of the matrix: `matrix[i][j]` is the coefficient `j` of the
+/// vector `i`, and a `Matrix` made of `n` vectors of dimension `m` has `n` rows and `m`
+/// columns.
+///
+/// Equality and hashing compare the exact coefficients: they are available for the
+/// integer and rational types, not for `f64` (no `Eq` nor `Hash`).
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Matrix<T> {
+    /// Internal representation as a list of elements of type `T`
+    columns: Vec<Vector<T>>,
+
+    /// Dimensions of the matrix
+    dimensions: (usize, usize),
+}
+
+impl<T> Matrix<T>
+where
+    T: Coefficient,
+{
+    /// Initialise an empty `Matrix`
+    ///      - `col_num`: number of columns
+    ///      - `col_dim`: number of rows
+    ///
+    /// Allocates fresh vectors, see `reset` to reuse an existing matrix.
+    pub fn init(col_num: usize, col_dim: usize) -> Self {
+        Self {
+            columns: vec![Vector::<T>::init(col_dim); col_num],
+            dimensions: (col_num, col_dim),
+        }
+    }
+
+    /// Set all the coefficients to zero, keeping the dimensions
+    ///
+    /// The storage of the vectors is reused instead of being reallocated as with `init`,
+    /// which helps when a buffer matrix is needed repeatedly (e.g. in a loop over blocks).
+    pub fn reset(&mut self) {
+        for col in &mut self.columns {
+            for j in 0..col.dimension() {
+                col[j] = T::default();
+            }
+        }
+    }
+
+    /// Zero matrix made of `rows` vectors of dimension `cols`
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self::init(rows, cols)
+    }
+
+    /// Identity matrix of size `n`
+    pub fn identity(n: usize) -> Self {
+        let mut identity = Self::init(n, n);
+        for i in 0..n {
+            identity[i][i] = T::from(1);
+        }
+        identity
+    }
+
+    /// Create a matrix from its vectors (rows)
+    pub fn from_columns(columns: Vec<Vector<T>>) -> Self {
+        let dimensions = if let Some(col) = columns.first() {
+            (columns.len(), col.dimension())
+        } else {
+            (0, 0)
+        };
+        Self {
+            columns,
+            dimensions,
+        }
+    }
+
+    /// Create a matrix from its vectors (rows), rejecting an empty matrix
+    ///
+    /// Fails with `LatticeError::Empty` if there are no vectors or if the first one has
+    /// dimension zero, and with `LatticeError::DimensionMismatch` if the vectors do not all
+    /// have the dimension of the first one, instead of building a matrix on which the
+    /// reductions would panic.
+    pub fn try_from_columns(columns: Vec<Vector<T>>) -> Result<Self, LatticeError> {
+        let dimension = match columns.first() {
+            Some(col) if col.dimension() > 0 => col.dimension(),
+            _ => return Err(LatticeError::Empty),
+        };
+        if let Some(col) = columns.iter().find(|col| col.dimension() != dimension) {
+            return Err(LatticeError::DimensionMismatch(dimension, col.dimension()));
+        }
+        Ok(Self::from_columns(columns))
+    }
+
+    /// Return the matrix dimensions: the number of vectors and their dimension
+    pub fn dimensions(&self) -> (usize, usize) {
+        self.dimensions
+    }
+
+    /// Number of rows (the vectors of the basis)
+    pub fn rows(&self) -> usize {
+        self.dimensions.0
+    }
+
+    /// Number of columns (the dimension of the vectors)
+    pub fn cols(&self) -> usize {
+        self.dimensions.1
+    }
+
+    /// Coefficient `j` of the vector `i` (`self[i][j]`), or `None` if out of bounds
+    pub fn get(&self, i: usize, j: usize) -> Option<&T> {
+        let (col_num, col_dim) = self.dimensions;
+        if i < col_num && j < col_dim {
+            Some(&self.columns[i][j])
+        } else {
+            None
+        }
+    }
+
+    /// Set the coefficient `j` of the vector `i` (`self[i][j] = value`)
+    ///
+    /// # Panics
+    /// if `i` or `j` is out of bounds
+    pub fn set(&mut self, i: usize, j: usize, value: T) {
+        let (col_num, col_dim) = self.dimensions;
+        assert!(
+            i < col_num && j < col_dim,
+            "index ({}, {}) out of bounds for a {}x{} matrix",
+            i,
+            j,
+            col_num,
+            col_dim
+        );
+        self.columns[i][j] = value;
+    }
+
+    /// Set the coefficient `j` of the vector `i`, or an error if out of bounds
+    pub fn try_set(&mut self, i: usize, j: usize, value: T) -> Result<(), LatticeError> {
+        let (col_num, col_dim) = self.dimensions;
+        check_index(i, col_num)?;
+        check_index(j, col_dim)?;
+        self.columns[i][j] = value;
+        Ok(())
+    }
+
+    /// Swap two vectors of the matrix (alias of `swap_rows`)
+    pub fn swap(&mut self, i: usize, j: usize) {
+        self.swap_rows(i, j);
+    }
+
+    /// Swap the vectors `i` and `j` (the rows `i` and `j`)
+    ///
+    /// # Panics
+    /// if `i` or `j` is not smaller than the number of vectors
+    pub fn swap_rows(&mut self, i: usize, j: usize) {
+        self.columns.swap(i, j);
+    }
+
+    /// Subtract `x` times the vector `src` from the vector `dst`, in place
+    ///
+    /// # Panics
+    /// if `dst` or `src` is not smaller than the number of vectors, or if `dst == src`
+    pub(crate) fn sub_scaled(&mut self, dst: usize, src: usize, x: &T) {
+        let mut row = std::mem::replace(&mut self.columns[dst], Vector::from_vector(Vec::new()));
+        row.sub_mulf_assign(&self.columns[src], x);
+        self.columns[dst] = row;
+    }
+
+    /// Swap the vectors `i` and `j`, or an error if out of bounds
+    pub fn try_swap_rows(&mut self, i: usize, j: usize) -> Result<(), LatticeError> {
+        let (col_num, _) = self.dimensions;
+        check_index(i, col_num)?;
+        check_index(j, col_num)?;
+        self.columns.swap(i, j);
+        Ok(())
+    }
+
+    /// Swap the coefficients `i` and `j` of every vector
+    ///
+    /// # Panics
+    /// if `i` or `j` is not smaller than the dimension of the vectors
+    pub fn swap_columns(&mut self, i: usize, j: usize) {
+        self.try_swap_columns(i, j)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Swap the coefficients `i` and `j` of every vector, or an error if out of bounds
+    pub fn try_swap_columns(&mut self, i: usize, j: usize) -> Result<(), LatticeError> {
+        let (_, col_dim) = self.dimensions;
+        check_index(i, col_dim)?;
+        check_index(j, col_dim)?;
+        for col in &mut self.columns {
+            let tmp = col[i].clone();
+            col[i] = col[j].clone();
+            col[j] = tmp;
+        }
+        Ok(())
+    }
+
+    /// Multiply the vector `i` by a scalar
+    pub fn scale_row(&mut self, i: usize, s: &T) {
+        self.columns[i] = self.columns[i].mulf(s);
+    }
+
+    /// Add `s` times the vector `src` to the vector `dst`
+    pub fn add_scaled_row(&mut self, dst: usize, src: usize, s: &T) {
+        self.columns[dst] = self.columns[dst].add(&self.columns[src].mulf(s));
+    }
+
+    /// Extract a block of the matrix
+    ///
+    /// The block is made of the coefficients `cols` of the vectors `rows` (`self[i][j]`
+    /// for `i` in `rows` and `j` in `cols`).
+    ///
+    /// # Panics
+    /// if the ranges are out of the bounds of the matrix
+    pub fn submatrix(&self, rows: Range<usize>, cols: Range<usize>) -> Self {
+        let (col_num, col_dim) = self.dimensions;
+        assert!(
+            rows.start <= rows.end && rows.end <= col_num,
+            "row range {:?} out of bounds for {} rows",
+            rows,
+            col_num
+        );
+        assert!(
+            cols.start <= cols.end && cols.end <= col_dim,
+            "column range {:?} out of bounds for {} columns",
+            cols,
+            col_dim
+        );
+        self.block(rows, cols)
+    }
+
+    /// Extract a block of the matrix, or an error if the ranges are out of bounds
+    pub fn try_submatrix(
+        &self,
+        rows: Range<usize>,
+        cols: Range<usize>,
+    ) -> Result<Self, LatticeError> {
+        let (col_num, col_dim) = self.dimensions;
+        check_range(&rows, col_num)?;
+        check_range(&cols, col_dim)?;
+        Ok(self.block(rows, cols))
+    }
+
+    /// Block of the coefficients `cols` of the vectors `rows`, within the bounds
+    fn block(&self, rows: Range<usize>, cols: Range<usize>) -> Self {
+        let mut block = Self::init(rows.len(), cols.len());
+        for (i, row) in rows.enumerate() {
+            for (j, col) in cols.clone().enumerate() {
+                block[i][j] = self[row][col].clone();
+            }
+        }
+        block
+    }
+
+    /// Copy `block` into the matrix, its first coefficient going to `self[start_row][start_col]`
+    ///
+    /// # Panics
+    /// if the block does not fit in the matrix
+    pub fn assign_submatrix(&mut self, start_row: usize, start_col: usize, block: &Self) {
+        let (col_num, col_dim) = self.dimensions;
+        let (block_rows, block_cols) = block.dimensions;
+        assert!(
+            start_row + block_rows <= col_num && start_col + block_cols <= col_dim,
+            "block of dimensions {:?} at ({}, {}) out of bounds for dimensions {:?}",
+            block.dimensions,
+            start_row,
+            start_col,
+            self.dimensions
+        );
+        self.assign_block(start_row, start_col, block);
+    }
+
+    /// Copy `block` into the matrix, or an error if it does not fit
+    pub fn try_assign_submatrix(
+        &mut self,
+        start_row: usize,
+        start_col: usize,
+        block: &Self,
+    ) -> Result<(), LatticeError> {
+        let (col_num, col_dim) = self.dimensions;
+        let (block_rows, block_cols) = block.dimensions;
+        check_block(start_row, block_rows, col_num)?;
+        check_block(start_col, block_cols, col_dim)?;
+        self.assign_block(start_row, start_col, block);
+        Ok(())
+    }
+
+    /// Copy `block` at `(start_row, start_col)`, where it fits
+    fn assign_block(&mut self, start_row: usize, start_col: usize, block: &Self) {
+        let (block_rows, block_cols) = block.dimensions;
+        for i in 0..block_rows {
+            for j in 0..block_cols {
+                self[start_row + i][start_col + j] = block[i][j].clone();
+            }
+        }
+    }
+
+    /// Stack the vectors of `other` below the vectors of `self`
+    ///
+    /// # Panics
+    /// if the vectors of both matrices do not have the same dimension
+    pub fn vstack(&self, other: &Self) -> Self {
+        self.try_vstack(other)
+            .expect("cannot stack vectors of different dimensions")
+    }
+
+    /// Stack the vectors of `other` below the vectors of `self`, or an error if the vectors
+    /// of both matrices do not have the same dimension
+    pub fn try_vstack(&self, other: &Self) -> Result<Self, LatticeError> {
+        if self.dimensions.1 != other.dimensions.1 {
+            return Err(LatticeError::DimensionMismatch(
+                self.dimensions.1,
+                other.dimensions.1,
+            ));
+        }
+        Ok(Self {
+            columns: self.columns.iter().chain(&other.columns).cloned().collect(),
+            dimensions: (self.dimensions.0 + other.dimensions.0, self.dimensions.1),
+        })
+    }
+
+    /// Concatenate each vector of `self` with the corresponding vector of `other`
+    ///
+    /// # Panics
+    /// if both matrices do not have the same number of vectors
+    pub fn hstack(&self, other: &Self) -> Self {
+        self.try_hstack(other)
+            .expect("cannot concatenate matrices with different numbers of vectors")
+    }
+
+    /// Concatenate each vector of `self` with the corresponding vector of `other`, or an
+    /// error if both matrices do not have the same number of vectors
+    pub fn try_hstack(&self, other: &Self) -> Result<Self, LatticeError> {
+        if self.dimensions.0 != other.dimensions.0 {
+            return Err(LatticeError::DimensionMismatch(
+                self.dimensions.0,
+                other.dimensions.0,
+            ));
+        }
+        let columns = self
+            .columns
+            .iter()
+            .zip(&other.columns)
+            .map(|(left, right)| {
+                Vector::from_vector(
+                    (0..left.dimension())
+                        .map(|j| left[j].clone())
+                        .chain((0..right.dimension()).map(|j| right[j].clone()))
+                        .collect(),
+                )
+            })
+            .collect();
+        Ok(Self {
+            columns,
+            dimensions: (self.dimensions.0, self.dimensions.1 + other.dimensions.1),
+        })
+    }
+
+    /// Return the transposed matrix
+    pub fn transpose(&self) -> Self {
+        let (col_num, col_dim) = self.dimensions;
+        let mut transposed = Self::init(col_dim, col_num);
+        for i in 0..col_num {
+            for j in 0..col_dim {
+                transposed[j][i] = self[i][j].clone();
+            }
+        }
+        transposed
+    }
+}
+
+impl Matrix<rug::Integer> {
+    /// Convert to a floating-point matrix (see `BigVector::to_float`)
+    pub fn to_float(&self) -> Matrix<f64> {
+        Matrix::from_columns(self.columns.iter().map(|c| c.to_float()).collect())
+    }
+
+    /// Convert to a rational matrix (exact)
+    pub fn to_rational(&self) -> Matrix<rug::Rational> {
+        Matrix::from_columns(self.columns.iter().map(|c| c.to_rational()).collect())
+    }
+}
+
+impl Matrix<f64> {
+    /// Convert to an integer matrix (see `VectorF::round_to_big`)
+    pub fn round_to_big(&self) -> Matrix<rug::Integer> {
+        Matrix::from_columns(self.columns.iter().map(|c| c.round_to_big()).collect())
+    }
+}
+
+/// Conversion from a `nalgebra` matrix, whose rows are the vectors of the basis
+#[cfg(feature = "nalgebra")]
+impl From<&nalgebra::DMatrix<f64>> for Matrix<f64> {
+    fn from(matrix: &nalgebra::DMatrix<f64>) -> Self {
+        Self::from_columns(
+            (0..matrix.nrows())
+                .map(|i| (0..matrix.ncols()).map(|j| matrix[(i, j)]).collect())
+                .collect(),
+        )
+    }
+}
+
+/// Conversion to a `nalgebra` matrix, whose rows are the vectors of the basis
+#[cfg(feature = "nalgebra")]
+impl From<&Matrix<f64>> for nalgebra::DMatrix<f64> {
+    fn from(matrix: &Matrix<f64>) -> Self {
+        let (rows, cols) = matrix.dimensions();
+        nalgebra::DMatrix::from_fn(rows, cols, |i, j| matrix[i][j])
+    }
+}
+
+/// Elementary operation on the rows of a matrix (see `Matrix::apply_ops`)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RowOp {
+    /// Swap the rows `i` and `j`
+    Swap(usize, usize),
+
+    /// Subtract `scalar` times the row `src` from the row `dst`
+    SubScaled {
+        dst: usize,
+        src: usize,
+        scalar: rug::Integer,
+    },
+}
+
+impl Matrix<rug::Integer> {
+    /// Apply a sequence of row operations, in order
+    ///
+    /// Replaying the operations logged by a reduction (`bigl2::lattice_reduce_logged`) onto
+    /// the identity matrix gives the unimodular transformation of the reduction.
+    ///
+    /// # Panics
+    /// if an operation refers to a row out of bounds
+    pub fn apply_ops(&mut self, ops: &[RowOp]) {
+        for op in ops {
+            match op {
+                RowOp::Swap(i, j) => self.swap_rows(*i, *j),
+                RowOp::SubScaled { dst, src, scalar } => {
+                    let step = self.columns[*src].mulf(scalar);
+                    self.columns[*dst] -= &step;
+                }
+            }
+        }
+    }
+}
+
+/// Overwrite the coefficients with zeros (see the implementation for `BigVector`)
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Matrix<rug::Integer> {
+    fn zeroize(&mut self) {
+        for col in &mut self.columns {
+            col.zeroize();
+        }
+    }
+}
+
+impl Matrix<rug::Rational> {
+    /// Exact inverse of a square matrix (Gauss-Jordan elimination)
+    ///
+    /// Returns `None` if the matrix is not square or not invertible.
+    pub fn inverse(&self) -> Option<Self> {
+        let (n, m) = self.dimensions;
+        if n != m {
+            return None;
+        }
+
+        let mut left = self.clone();
+        let mut right = Self::init(n, n);
+        for i in 0..n {
+            right[i][i] = rug::Rational::from(1);
+        }
+
+        for i in 0..n {
+            let pivot = (i..n).find(|&k| left[k][i] != 0)?;
+            left.swap(i, pivot);
+            right.swap(i, pivot);
+
+            let inv = rug::Rational::from(left[i][i].recip_ref());
+            left[i] = left[i].mulf(&inv);
+            right[i] = right[i].mulf(&inv);
+
+            for k in 0..n {
+                if k != i && left[k][i] != 0 {
+                    let factor = left[k][i].clone();
+                    left[k] = left[k].sub(&left[i].mulf(&factor));
+                    right[k] = right[k].sub(&right[i].mulf(&factor));
+                }
+            }
+        }
+        Some(right)
+    }
+}
+
+/// Direct access to a column
+impl<T> Index<usize> for Matrix<T> {
+    type Output = Vector<T>;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.columns[index]
+    }
+}
+
+/// Direct access to a column (mutable)
+impl<T> IndexMut<usize> for Matrix<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.columns[index]
+    }
+}
+
+impl<T> fmt::Debug for Matrix<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:?}\n", self.columns)
+    }
+}
+
+/// Pretty-print the matrix, one vector per line, with right-aligned entries
+impl<T> fmt::Display for Matrix<T>
+where
+    T: Coefficient + Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let entries: Vec<Vec<String>> = self
+            .columns
+            .iter()
+            .map(|col| (0..col.dimension()).map(|j| col[j].to_string()).collect())
+            .collect();
+
+        let mut widths: Vec<usize> = Vec::new();
+        for row in &entries {
+            for (j, entry) in row.iter().enumerate() {
+                if j == widths.len() {
+                    widths.push(0);
+                }
+                widths[j] = widths[j].max(entry.len());
+            }
+        }
+
+        for (i, row) in entries.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "[")?;
+            for (j, entry) in row.iter().enumerate() {
+                if j > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{:>width$}", entry, width = widths[j])?;
+            }
+            write!(f, "]")?;
+        }
+        Ok(())
+    }
+}
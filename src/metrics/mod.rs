@@ -0,0 +1,158 @@
+//! Estimates of lattice invariants
+
+use crate::l2::bigl2;
+use crate::matrix::Matrix;
+use crate::vector::Dot;
+
+use rug::{Integer, Rational};
+
+/// Estimates of the successive minima of a lattice (see `successive_minima_estimates`)
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinimaEstimates {
+    /// Upper bounds on `λ_1, ..., λ_d`: the sorted norms of the reduced vectors
+    pub upper_bounds: Vec<f64>,
+
+    /// Lower bound on `λ_1`: `min_i ||b*_i||` over the reduced basis (`0` if the lattice
+    /// is `{0}`)
+    pub first_lower_bound: f64,
+}
+
+/// Estimates of the successive minima of a lattice
+///
+/// A copy of the basis is reduced (L² algorithm). The sorted norms of the reduced
+/// vectors are returned as `upper_bounds`: the i-th norm is an upper bound on the i-th
+/// successive minimum `λ_i`. The smallest norm `min_i ||b*_i||` of the Gram-Schmidt
+/// vectors of the reduced basis is returned as `first_lower_bound`: every nonzero lattice
+/// vector is at least that long, so the two bracket `λ_1`. These are approximations, not
+/// the exact minima (the gap grows exponentially with the dimension in the worst case).
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///
+/// The zero vectors coming from linear dependencies are not part of the result.
+pub fn successive_minima_estimates(basis: &Matrix<Integer>) -> MinimaEstimates {
+    let mut reduced = basis.clone();
+    let (_, r) = bigl2::lattice_reduce_full(&mut reduced, 0.501, 0.998);
+    let (n, _) = reduced.dimensions();
+
+    let mut upper_bounds: Vec<f64> = (0..n)
+        .map(|i| reduced[i].dot(&reduced[i]))
+        .filter(|norm| *norm != 0)
+        .map(|norm| norm.to_f64().sqrt())
+        .collect();
+    upper_bounds.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let smallest = (0..n)
+        .map(|i| r[i][i].to_f64())
+        .filter(|&norm| norm > 0.)
+        .fold(f64::INFINITY, f64::min);
+    let first_lower_bound = if smallest.is_finite() {
+        smallest.sqrt()
+    } else {
+        0.
+    };
+
+    MinimaEstimates {
+        upper_bounds,
+        first_lower_bound,
+    }
+}
+
+/// Logarithm of the potential of a basis
+///
+/// The potential `prod_i r[i][i]^(d - i)` of a basis of `d` vectors, where
+/// `r[i][i] = ||b*_i||²` (the `r` matrix of `bigl2::lattice_reduce_full`), strictly
+/// decreases at each swap of the LLL algorithm. Its natural logarithm is returned, the
+/// potential itself being usually too large for a `f64`.
+///
+/// Arguments:
+///  * r: the Gram-Schmidt matrix `r[i][j] = <b_i, b*_j>` of the basis
+///
+/// The zero entries of the diagonal (linearly dependent vectors) are skipped.
+pub fn log_potential(r: &Matrix<Rational>) -> f64 {
+    let (d, _) = r.dimensions();
+    (0..d)
+        .filter(|&i| r[i][i] != 0)
+        .map(|i| (d - i) as f64 * (ln(r[i][i].numer()) - ln(r[i][i].denom())))
+        .sum()
+}
+
+/// Slope of the Gram-Schmidt profile of a basis
+///
+/// The least-squares slope of the line fitting the points `(i, ln ||b*_i||)`: the flatter
+/// the profile (the closer the slope to zero), the better reduced the basis. BKZ 2.0 uses
+/// its convergence to stop the tours (see `bkz::BkzParams::auto_abort`).
+///
+/// Arguments:
+///  * r: the Gram-Schmidt matrix `r[i][j] = <b_i, b*_j>` of the basis
+///
+/// The zero entries of the diagonal (linearly dependent vectors) are skipped. Returns `0`
+/// if there are fewer than two points.
+pub fn gso_slope(r: &Matrix<Rational>) -> f64 {
+    let (d, _) = r.dimensions();
+    let points: Vec<(f64, f64)> = (0..d)
+        .filter(|&i| r[i][i] != 0)
+        .map(|i| (i as f64, (ln(r[i][i].numer()) - ln(r[i][i].denom())) / 2.))
+        .collect();
+    if points.len() < 2 {
+        return 0.;
+    }
+
+    let count = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / count;
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points
+        .iter()
+        .map(|(x, _)| (x - mean_x) * (x - mean_x))
+        .sum();
+    covariance / variance
+}
+
+/// Natural logarithm of a positive integer, without overflowing the `f64` range
+fn ln(n: &Integer) -> f64 {
+    let (mantissa, exponent) = n.to_f64_exp();
+    mantissa.ln() + f64::from(exponent) * std::f64::consts::LN_2
+}
+
+/// Exact Gram-Schmidt matrix `r[i][j] = <b_i, b*_j>` (for `j <= i`) of a basis
+///
+/// The columns of the linearly dependent vectors (`r[j][j] = 0`) are zero.
+pub(crate) fn gram_schmidt_r(basis: &Matrix<Integer>) -> Matrix<Rational> {
+    let (d, _) = basis.dimensions();
+    let mut r: Matrix<Rational> = Matrix::init(d, d);
+    let mut mu: Matrix<Rational> = Matrix::init(d, d);
+    for i in 0..d {
+        for j in 0..=i {
+            let mut r_ij = Rational::from(basis[i].dot(&basis[j]));
+            for l in 0..j {
+                r_ij -= Rational::from(&mu[j][l] * &r[i][l]);
+            }
+            if j < i && r[j][j] != 0 {
+                mu[i][j] = Rational::from(&r_ij / &r[j][j]);
+            }
+            r[i][j] = r_ij;
+        }
+    }
+    r
+}
+
+/// Exact Gram-Schmidt coefficients `mu` and squared norms `||b*_i||²` of a basis
+///
+/// The vectors of `basis` must be linearly independent.
+pub(crate) fn gram_schmidt(basis: &Matrix<Integer>) -> (Matrix<Rational>, Vec<Rational>) {
+    let (d, _) = basis.dimensions();
+    let r = gram_schmidt_r(basis);
+    let mut mu: Matrix<Rational> = Matrix::init(d, d);
+    for i in 0..d {
+        mu[i][i] = Rational::from(1);
+        for j in 0..i {
+            mu[i][j] = Rational::from(&r[i][j] / &r[j][j]);
+        }
+    }
+    let norms = (0..d).map(|i| r[i][i].clone()).collect();
+    (mu, norms)
+}
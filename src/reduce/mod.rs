@@ -0,0 +1,262 @@
+//! Generic lattice reduction
+//!
+//! The reductions of `l2::bigl2` and `l2::l2f` are instances of `lattice_reduce` with
+//! the `BigNum` and `Float` scalars:
+//!
+//! ```rust
+//! use lll_rs::matrix::Matrix;
+//! use lll_rs::reduce::{self, Float};
+//! use lll_rs::vector::VectorF;
+//!
+//! let mut basis: Matrix<f64> = Matrix::init(2, 2);
+//! basis[0] = VectorF::from_vector(vec![1., 1345.]);
+//! basis[1] = VectorF::from_vector(vec![0., 35.]);
+//! reduce::lattice_reduce::<Float>(&mut basis, 0.51, 0.99);
+//! ```
+
+use crate::l2::{ReductionParams, ReductionStats};
+use crate::matrix::Matrix;
+use crate::vector::{Coefficient, Dot, Vector};
+
+pub use crate::scalars::{BigNum, Float, FromExt, Scalars};
+
+use rug::rand::RandState;
+
+/// Lattice reduction (L² algorithm) with generic scalars
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * eta: eta factor of the basis reduction
+///  * delta: delta factor of the basis reduction
+///
+/// The basis is reduced in-place. The zero vectors coming from linear dependencies
+/// are moved to the end of the basis, and their number is returned.
+///
+/// # Panics
+/// if delta < 1/4 or delta >= 1  
+/// if eta < 1/2 or eta >= sqrt(delta)  
+/// if the floating-point Gram-Schmidt coefficients overflow to an infinity or a NaN (see
+/// `ReductionError::Precision`), which cannot happen with exact arithmetic
+pub fn lattice_reduce<S>(basis: &mut Matrix<S::Integer>, eta: f64, delta: f64) -> usize
+where
+    S: Scalars,
+    S::Integer: Coefficient + Send + Sync,
+    S::Fraction: Coefficient,
+    Vector<S::Integer>: Dot<Output = S::Integer>,
+    Vector<S::Fraction>: Dot<Output = S::Fraction>,
+{
+    crate::l2::lattice_reduce::<S>(basis, eta, delta)
+}
+
+/// Lattice reduction (L² algorithm) in several stages
+///
+/// Runs the reduction once per `(eta, delta)` pair of `schedule`, in order, each stage
+/// starting from the basis reduced by the previous one. Reducing first with loose
+/// parameters (e.g. `delta = 0.75`) then with tight ones (e.g. `delta = 0.99`) is usually
+/// faster than using the tight parameters directly on skewed bases: the final basis is
+/// reduced for the parameters of the last stage.
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * schedule: eta and delta factors of each stage
+///
+/// The basis is reduced in-place. Returns the statistics of all the stages added
+/// together.
+///
+/// # Panics
+/// if one of the stages has delta < 1/4 or delta >= 1, or eta < 1/2 or eta >= sqrt(delta)
+pub fn staged(basis: &mut Matrix<rug::Integer>, schedule: &[(f64, f64)]) -> ReductionStats {
+    let mut total = ReductionStats::default();
+    for &(eta, delta) in schedule {
+        let params = ReductionParams::default().eta(eta).delta(delta);
+        let (_, stats) = crate::l2::lattice_reduce_stats::<BigNum>(basis, &params, None)
+            .expect("the reduction has no iteration limit");
+        total.iterations += stats.iterations;
+        total.swaps += stats.swaps;
+        total.size_reductions += stats.size_reductions;
+        total.max_bits = total.max_bits.max(stats.max_bits);
+        total.duration += stats.duration;
+    }
+    total
+}
+
+/// Pairwise Gauss reduction of the adjacent vectors of a basis
+///
+/// Sweeps the pairs `(basis[i], basis[i + 1])` and replaces each of them by its
+/// Gauss-reduced basis (`gauss::reduce_2d`) when that makes the pair shorter (the sum of
+/// the squared norms decreases), until a full sweep makes no change. This is a cheap
+/// heuristic preprocessing before the L² reduction, that often shrinks the coefficients of
+/// skewed bases: the result is not reduced in general.
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///
+/// The basis is modified in-place and still generates the same lattice. The pairs of
+/// linearly dependent vectors are left unchanged.
+pub fn pairwise_preprocess(basis: &mut Matrix<rug::Integer>) {
+    let (n, dim) = basis.dimensions();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for i in 1..n {
+            let norm_0 = basis[i - 1].dot(&basis[i - 1]);
+            let norm_1 = basis[i].dot(&basis[i]);
+            let dot = basis[i - 1].dot(&basis[i]);
+            if rug::Integer::from(&norm_0 * &norm_1) == rug::Integer::from(dot.square_ref()) {
+                continue;
+            }
+
+            let mut pair = basis.submatrix(i - 1..i + 1, 0..dim);
+            crate::l2::gauss::reduce_2d(&mut pair);
+            if pair[0].dot(&pair[0]) + pair[1].dot(&pair[1]) < norm_0 + norm_1 {
+                basis.assign_submatrix(i - 1, 0, &pair);
+                changed = true;
+            }
+        }
+    }
+}
+
+/// Lattice reduction (L² algorithm) of randomly permuted bases, keeping the best result
+///
+/// The vectors of a copy of `basis` are permuted, then the copy is reduced, `tries` times.
+/// The first try uses the identity permutation, the next ones random permutations drawn
+/// from a generator seeded with `seed`. The reduced basis with the shortest first vector is
+/// returned (the first one found in case of ties), so that more tries never give a longer
+/// first vector.
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * eta: eta factor of the basis reduction
+///  * delta: delta factor of the basis reduction
+///  * tries: number of reductions
+///  * seed: seed of the random generator
+///
+/// # Panics
+/// if delta < 1/4 or delta >= 1  
+/// if eta < 1/2 or eta >= sqrt(delta)  
+/// if tries is zero
+pub fn best_of_random(
+    basis: &Matrix<rug::Integer>,
+    eta: f64,
+    delta: f64,
+    tries: usize,
+    seed: u64,
+) -> Matrix<rug::Integer> {
+    assert!(tries > 0);
+    let (n, _) = basis.dimensions();
+    let mut rand = RandState::new();
+    rand.seed(&rug::Integer::from(seed));
+
+    let mut best: Option<(rug::Integer, Matrix<rug::Integer>)> = None;
+    for attempt in 0..tries {
+        let mut permuted = basis.clone();
+        if attempt > 0 {
+            // Fisher-Yates shuffle
+            for i in (1..n).rev() {
+                let j = rug::Integer::from(i + 1).random_below(&mut rand);
+                permuted.swap(i, j.to_usize().unwrap());
+            }
+        }
+        crate::l2::bigl2::lattice_reduce(&mut permuted, eta, delta);
+
+        let norm = if n > 0 {
+            permuted[0].dot(&permuted[0])
+        } else {
+            rug::Integer::new()
+        };
+        if best.as_ref().is_none_or(|(best_norm, _)| norm < *best_norm) {
+            best = Some((norm, permuted));
+        }
+    }
+    best.map(|(_, basis)| basis).unwrap()
+}
+
+/// Lattice reduction (L² algorithm) of a real lattice given by a Cholesky factor
+///
+/// The rows of `l` are the vectors of a basis of a real lattice: for a positive-definite
+/// quadratic form `Q = L L^T`, this is the lattice whose Gram matrix is `Q`. The basis is
+/// reduced with floating-point arithmetic, keeping track of the unimodular transformation.
+///
+/// Arguments:
+///  * l: the basis, made of linearly independent vectors (`L L^T` positive-definite)
+///  * eta: eta factor of the basis reduction
+///  * delta: delta factor of the basis reduction
+///
+/// Returns the unimodular transformation `U` and the reduced basis `U L` (computed from
+/// `U` and `l`). The coefficients of `U` are exact as long as they stay below `2^53`.
+///
+/// # Panics
+/// if delta < 1/4 or delta >= 1  
+/// if eta < 1/2 or eta >= sqrt(delta)  
+/// if the vectors of `l` are linearly dependent
+pub fn from_cholesky(l: &Matrix<f64>, eta: f64, delta: f64) -> (Matrix<rug::Integer>, Matrix<f64>) {
+    let (n, dim) = l.dimensions();
+    let mut basis = l.clone();
+    let mut transform: Matrix<f64> = Matrix::identity(n);
+    let params = ReductionParams::default().eta(eta).delta(delta);
+    let (zeros, _) =
+        crate::l2::lattice_reduce_stats::<Float>(&mut basis, &params, Some(&mut transform))
+            .expect("the reduction has no iteration limit");
+    assert_eq!(zeros, 0, "the quadratic form is not positive-definite");
+
+    let transform = transform.round_to_big();
+    let mut reduced: Matrix<f64> = Matrix::init(n, dim);
+    for i in 0..n {
+        for j in 0..n {
+            reduced[i] = reduced[i].add(&l[j].mulf(&transform[i][j].to_f64()));
+        }
+    }
+    (transform, reduced)
+}
+
+/// Lazy lattice reduction (L² algorithm) of a sequence of bases
+///
+/// Each basis is reduced when the returned iterator reaches it, so that no work is done
+/// until `next` is called. With an iteration limit in `params`, the bases for which the
+/// limit is reached are returned partially reduced.
+///
+/// Arguments:
+///  * iter: the bases
+///  * params: parameters of the basis reductions
+///
+/// # Panics
+/// if delta < 1/4 or delta >= 1  
+/// if eta < 1/2 or eta >= sqrt(delta)
+pub fn reduce_all<'a, I>(
+    iter: I,
+    params: &'a ReductionParams,
+) -> impl Iterator<Item = Matrix<rug::Integer>> + 'a
+where
+    I: IntoIterator<Item = Matrix<rug::Integer>>,
+    I::IntoIter: 'a,
+{
+    iter.into_iter().map(move |mut basis| {
+        let _ = crate::l2::bigl2::lattice_reduce_with(&mut basis, params);
+        basis
+    })
+}
+
+/// Parallel lattice reduction (L² algorithm) of a collection of bases
+///
+/// Same as `reduce_all`, the bases being reduced in parallel (with `rayon`) when the
+/// returned iterator is consumed.
+///
+/// # Panics
+/// if delta < 1/4 or delta >= 1  
+/// if eta < 1/2 or eta >= sqrt(delta)
+#[cfg(feature = "rayon")]
+pub fn par_reduce_all<'a, I>(
+    iter: I,
+    params: &'a ReductionParams,
+) -> impl rayon::iter::ParallelIterator<Item = Matrix<rug::Integer>> + 'a
+where
+    I: rayon::iter::IntoParallelIterator<Item = Matrix<rug::Integer>>,
+    I::Iter: 'a,
+{
+    use rayon::prelude::*;
+
+    iter.into_par_iter().map(move |mut basis| {
+        let _ = crate::l2::bigl2::lattice_reduce_with(&mut basis, params);
+        basis
+    })
+}
@@ -0,0 +1,46 @@
+//! Integer relations between real numbers
+
+use crate::l2::bigl2;
+use crate::matrix::Matrix;
+
+use rug::{Integer, Rational};
+
+/// Integer relation detection
+///
+/// Looks for a small integer vector `c` such that `sum c_i * values[i]` is (close to) zero,
+/// by reducing the lattice generated by the vectors `(e_i, round(scale * values[i]))`,
+/// where `e_i` is the i-th unit vector. The scale should be close to the inverse of the
+/// precision of `values`.
+///
+/// Arguments:
+///  * values: the real numbers
+///  * scale: scaling factor of the values
+///
+/// Returns the coefficients of the relation, or `None` if the shortest vector found does
+/// not give a relation (its residual is larger than the rounding error).
+pub fn find_integer_relation(values: &[f64], scale: u64) -> Option<Vec<Integer>> {
+    let n = values.len();
+    if n == 0 {
+        return None;
+    }
+
+    let mut basis: Matrix<Integer> = Matrix::init(n, n + 1);
+    for (i, value) in values.iter().enumerate() {
+        let scaled = Rational::from_f64(*value)? * Rational::from(scale);
+        basis[i][i] = Integer::from(1);
+        basis[i][n] = scaled.round_ref().into();
+    }
+    bigl2::lattice_reduce(&mut basis, 0.51, 0.99);
+
+    let coefficients: Vec<Integer> = (0..n).map(|i| basis[0][i].clone()).collect();
+    let l1: Integer = coefficients
+        .iter()
+        .map(|c| Integer::from(c.abs_ref()))
+        .sum();
+
+    // Each rounded value is off by at most 1/2
+    if l1 == 0 || Integer::from(basis[0][n].abs_ref()) * 2 > l1 {
+        return None;
+    }
+    Some(coefficients)
+}
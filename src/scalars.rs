@@ -1,7 +1,8 @@
 use rug::{Integer, Rational};
 use std::{cmp, ops};
 
-pub(crate) trait FromExt<T> {
+/// Conversion between the scalar types of the reductions
+pub trait FromExt<T> {
     fn from_ext(_: T) -> Self;
 }
 
@@ -15,7 +16,15 @@ macro_rules! impl_from_ext {
     };
 }
 
-pub(crate) trait Scalars {
+/// Arithmetic used by the reductions
+///
+/// `Integer` is the type of the coefficients of the basis, and `Fraction` the type of the
+/// Gram-Schmidt coefficients.
+///
+/// The scalars may be complex (see `gaussian::GaussianNum`): the dot product then
+/// conjugates its second argument, and the values compared by the reductions (norms,
+/// absolute values and the parameters) are real.
+pub trait Scalars {
     type Integer;
     type Fraction: cmp::PartialOrd<Self::Integer>
         + for<'a> FromExt<&'a Self::Integer>
@@ -27,9 +36,41 @@ pub(crate) trait Scalars {
         + for<'a> ops::Div<&'a Self::Fraction, Output = Self::Fraction>
         + for<'a> ops::SubAssign<&'a Self::Fraction>;
 
+    /// Whether the L² reduction computes the Gram-Schmidt vectors explicitly, with a
+    /// second orthogonalisation pass on cancellation (for inexact arithmetic)
+    const REORTHOGONALIZE: bool = false;
+
+    /// Nearest integer
     fn round(n: &Self::Fraction) -> Self::Integer;
+
+    /// Whether `round(f)` is defined
+    ///
+    /// Always `true` by default. The floating-point coefficients that overflow to an
+    /// infinity or a NaN cannot be rounded to a big integer.
+    fn can_round(_f: &Self::Fraction) -> bool {
+        true
+    }
+
+    /// Nearest integer of the quotient `n / d`
     fn round_div(n: Self::Integer, d: Self::Integer) -> Self::Integer;
+
+    /// Absolute value
     fn abs(f: Self::Fraction) -> Self::Fraction;
+
+    /// Complex conjugate
+    ///
+    /// The identity by default (real scalars).
+    fn conj(f: Self::Fraction) -> Self::Fraction {
+        f
+    }
+
+    /// Number of bits of the integer part of the absolute value (`0` for zero)
+    fn bit_length(n: &Self::Integer) -> u32;
+
+    /// Round `f` to the nearest multiple of `2^-bits` if its denominator is larger than `2^bits`
+    ///
+    /// Does nothing by default (floating-point numbers have bounded denominators).
+    fn bound_denominator(_f: &mut Self::Fraction, _bits: u32) {}
 }
 
 impl_from_ext!(&f64, f64, |f: &f64| *f);
@@ -37,15 +78,22 @@ impl_from_ext!((f64, f64), f64, |(n, d)| n / d);
 impl_from_ext!(f64, f64, |f| f);
 impl_from_ext!((i32, i32), f64, |(n, d)| f64::from(n) / f64::from(d));
 
-pub(crate) struct Float;
+/// Platform double floating-point numbers (IEEE 754)
+pub struct Float;
 impl Scalars for Float {
     type Integer = f64;
     type Fraction = f64;
 
+    const REORTHOGONALIZE: bool = true;
+
     fn round(f: &Self::Fraction) -> Self::Integer {
         f.round()
     }
 
+    fn can_round(f: &Self::Fraction) -> bool {
+        f.is_finite()
+    }
+
     fn round_div(n: Self::Integer, d: Self::Integer) -> Self::Integer {
         (n / d).round()
     }
@@ -53,6 +101,14 @@ impl Scalars for Float {
     fn abs(f: Self::Fraction) -> Self::Fraction {
         f.abs()
     }
+
+    fn bit_length(n: &Self::Integer) -> u32 {
+        if n.abs() < 1. {
+            0
+        } else {
+            n.abs().log2().floor() as u32 + 1
+        }
+    }
 }
 
 impl_from_ext!(&Integer, Rational, |f: &Integer| Rational::from(f));
@@ -62,7 +118,8 @@ impl_from_ext!((Integer, Integer), Rational, |(n, d)| Rational::from((
 impl_from_ext!(f64, Rational, |f: f64| Rational::from_f64(f).unwrap());
 impl_from_ext!((i32, i32), Rational, |(n, d)| Rational::from((n, d)));
 
-pub(crate) struct BigNum;
+/// `rug::Integer` coefficients and `rug::Rational` Gram-Schmidt coefficients
+pub struct BigNum;
 impl Scalars for BigNum {
     type Integer = rug::Integer;
     type Fraction = rug::Rational;
@@ -79,4 +136,53 @@ impl Scalars for BigNum {
     fn abs(f: Self::Fraction) -> Self::Fraction {
        f.abs()
     }
+
+    fn bit_length(n: &Self::Integer) -> u32 {
+        n.significant_bits()
+    }
+
+    fn bound_denominator(f: &mut Self::Fraction, bits: u32) {
+        let scale = Integer::from(1) << bits;
+        if *f.denom() > scale {
+            let numerator: Integer = Rational::from(&*f * &scale).round_ref().into();
+            *f = Rational::from((numerator, scale));
+        }
+    }
+}
+
+impl_from_ext!(&Integer, f64, |f: &Integer| f.to_f64());
+impl_from_ext!((Integer, Integer), f64, |(n, d): (Integer, Integer)| {
+    n.to_f64() / d.to_f64()
+});
+
+/// `rug::Integer` coefficients and platform double Gram-Schmidt coefficients
+///
+/// The operations on the basis are exact: a loss of precision can prevent the reduction
+/// from terminating, but the basis always generates the same lattice.
+pub struct BigFloat;
+impl Scalars for BigFloat {
+    type Integer = rug::Integer;
+    type Fraction = f64;
+
+    const REORTHOGONALIZE: bool = true;
+
+    fn round(f: &Self::Fraction) -> Self::Integer {
+        Integer::from_f64(f.round()).expect("cannot round a non-finite coefficient")
+    }
+
+    fn can_round(f: &Self::Fraction) -> bool {
+        f.is_finite()
+    }
+
+    fn round_div(n: Self::Integer, d: Self::Integer) -> Self::Integer {
+        BigNum::round_div(n, d)
+    }
+
+    fn abs(f: Self::Fraction) -> Self::Fraction {
+        f.abs()
+    }
+
+    fn bit_length(n: &Self::Integer) -> u32 {
+        n.significant_bits()
+    }
 }
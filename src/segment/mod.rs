@@ -0,0 +1,130 @@
+//! Segment LLL [KS01]
+//!
+//! The basis is split into segments of `block_size` vectors. Each pair of adjacent
+//! segments is reduced locally: the L² algorithm runs on the Gram matrix of the
+//! projections of the vectors of the pair orthogonally to the previous vectors, and
+//! the resulting transformation is applied to the basis. This keeps the expensive
+//! operations local to windows of `2 * block_size` vectors.
+
+use crate::gram;
+use crate::l2::bigl2;
+use crate::matrix::Matrix;
+use crate::vector::{BigVector, Dot};
+
+use rug::{Integer, Rational};
+
+/// Lattice reduction (segment LLL)
+///
+/// The windows of two adjacent segments are reduced until none of them changes. A final
+/// L² pass then size-reduces the vectors with respect to the previous segments; it starts
+/// from a nearly reduced basis, so it is cheap.
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * eta: eta factor of the basis reduction
+///  * delta: delta factor of the basis reduction
+///  * block_size: number of vectors of the segments
+///
+/// The basis is reduced in-place. The zero vectors coming from linear dependencies
+/// are moved to the end of the basis, and their number is returned.
+///
+/// # Panics
+/// if delta < 1/4 or delta >= 1  
+/// if eta < 1/2 or eta >= sqrt(delta)  
+/// if block_size is zero
+pub fn lattice_reduce(
+    basis: &mut Matrix<Integer>,
+    eta: f64,
+    delta: f64,
+    block_size: usize,
+) -> usize {
+    assert!(block_size > 0);
+    let (n, _) = basis.dimensions();
+
+    if n > 2 * block_size {
+        loop {
+            let mut changed = false;
+            for start in (0..n - 1).step_by(block_size) {
+                let end = n.min(start + 2 * block_size);
+                changed |= reduce_window(basis, start, end, eta, delta);
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    bigl2::lattice_reduce(basis, eta, delta)
+}
+
+/// Reduce the projections of `basis[start..end]` orthogonally to `basis[..start]`
+///
+/// Returns whether the basis changed.
+pub(crate) fn reduce_window(
+    basis: &mut Matrix<Integer>,
+    start: usize,
+    end: usize,
+    eta: f64,
+    delta: f64,
+) -> bool {
+    let size = end - start;
+    let mut projected = projected_gram(basis, start, end);
+    let transform = gram::lattice_reduce_gram(&mut projected, eta, delta);
+    if transform == Matrix::identity(size) {
+        return false;
+    }
+
+    let (_, dim) = basis.dimensions();
+    let window: Vec<BigVector> = (0..size)
+        .map(|i| {
+            (0..size).fold(BigVector::init(dim), |acc, j| {
+                acc.add(&basis[start + j].mulf(&transform[i][j]))
+            })
+        })
+        .collect();
+    for (i, vector) in window.into_iter().enumerate() {
+        basis[start + i] = vector;
+    }
+    true
+}
+
+/// Gram matrix of the projections of `basis[start..end]` orthogonally to `basis[..start]`,
+/// scaled to have integer coefficients
+fn projected_gram(basis: &Matrix<Integer>, start: usize, end: usize) -> Matrix<Integer> {
+    // r[i][j] = <b_i, b*_j> for j < start, and the squared norms r[j][j] of the b*_j
+    let mut r: Matrix<Rational> = Matrix::init(end, start);
+    for i in 0..end {
+        for j in 0..start.min(i + 1) {
+            r[i][j] = Rational::from(basis[i].dot(&basis[j]))
+                - (0..j)
+                    .filter(|&l| r[l][l] != 0)
+                    .map(|l| Rational::from(&r[i][l] * &r[j][l]) / &r[l][l])
+                    .sum::<Rational>();
+        }
+    }
+
+    let size = end - start;
+    let mut projected: Matrix<Rational> = Matrix::init(size, size);
+    let mut denominator = Integer::from(1);
+    for i in 0..size {
+        for j in 0..=i {
+            let (b_i, b_j) = (start + i, start + j);
+            projected[i][j] = Rational::from(basis[b_i].dot(&basis[b_j]))
+                - (0..start)
+                    .filter(|&l| r[l][l] != 0)
+                    .map(|l| Rational::from(&r[b_i][l] * &r[b_j][l]) / &r[l][l])
+                    .sum::<Rational>();
+            denominator.lcm_mut(projected[i][j].denom());
+        }
+    }
+
+    let mut scaled: Matrix<Integer> = Matrix::init(size, size);
+    for i in 0..size {
+        for j in 0..=i {
+            let entry = Rational::from(&projected[i][j] * &denominator);
+            scaled[i][j] = entry.numer().clone();
+            scaled[j][i] = scaled[i][j].clone();
+        }
+    }
+    scaled
+}
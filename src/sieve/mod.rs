@@ -0,0 +1,111 @@
+//! Gauss sieve [MV10]
+//!
+//! The sieve keeps a list of lattice vectors that are pairwise Gauss-reduced: no vector
+//! of the list can be shortened by adding a multiple of another one. New vectors are
+//! sampled, reduced against the list, and the vectors of the list that the new one can
+//! shorten are taken out of the list to be reduced again. A vector reduced to zero is a
+//! collision: when there are enough collisions, the list most likely contains a shortest
+//! vector of the lattice.
+//!
+//! The sieve is an alternative to the enumerations of the `svp` module: it needs memory
+//! exponential in the rank of the lattice, but its running time grows more slowly.
+
+use crate::l2::bigl2;
+use crate::matrix::Matrix;
+use crate::vector::{BigVector, Dot};
+
+use rug::{rand::RandState, Integer};
+
+/// Shortest nonzero vector of a lattice (Gauss sieve)
+///
+/// The basis is first reduced (L² algorithm), and its vectors are the first vectors of
+/// the sieve. The next vectors are random combinations of the reduced basis vectors
+/// with coefficients `-1`, `0` and `1` (the random generator has a fixed seed, so the
+/// result is deterministic).
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice (linear dependencies are allowed)
+///  * target_collisions: number of vectors reduced to zero after which the sieve stops
+///
+/// Returns the shortest vector of the list: with enough collisions (a few hundreds in
+/// small dimensions), it is a shortest nonzero vector of the lattice. The zero vector is
+/// returned if the lattice is `{0}`.
+pub fn gauss_sieve(basis: &Matrix<Integer>, target_collisions: usize) -> BigVector {
+    let (_, dim) = basis.dimensions();
+    let mut reduced = basis.clone();
+    let zeros = bigl2::lattice_reduce(&mut reduced, 0.51, 0.99);
+    let (n, _) = reduced.dimensions();
+    let rank = n - zeros;
+    if rank == 0 {
+        return BigVector::init(dim);
+    }
+
+    let mut rand = RandState::new();
+    let mut list: Vec<BigVector> = Vec::new();
+    let mut stack: Vec<BigVector> = (0..rank).rev().map(|i| reduced[i].clone()).collect();
+    let mut collisions = 0;
+
+    while collisions < target_collisions {
+        let mut v = match stack.pop() {
+            Some(v) => v,
+            None => sample(&reduced, rank, &mut rand),
+        };
+
+        // Reducing the new vector against the list, until it cannot be shortened
+        while list.iter().any(|w| reduce(&mut v, w)) {}
+        if v.dot(&v) == 0 {
+            collisions += 1;
+            continue;
+        }
+
+        // The vectors of the list that the new one shortens are reduced again
+        let mut i = 0;
+        while i < list.len() {
+            let mut w = list[i].clone();
+            if reduce(&mut w, &v) {
+                list.swap_remove(i);
+                stack.push(w);
+            } else {
+                i += 1;
+            }
+        }
+        list.push(v);
+    }
+
+    list.into_iter()
+        .min_by_key(|v| v.dot(v))
+        .expect("the basis vectors are in the list")
+}
+
+/// Random nonzero combination of the first `rank` vectors of `basis`, with coefficients
+/// `-1`, `0` and `1`
+fn sample(basis: &Matrix<Integer>, rank: usize, rand: &mut RandState) -> BigVector {
+    let (_, dim) = basis.dimensions();
+    loop {
+        let mut v = BigVector::init(dim);
+        for i in 0..rank {
+            let x = Integer::from(Integer::from(3).random_below_ref(rand)) - 1;
+            if x != 0 {
+                v += &basis[i].mulf(&x);
+            }
+        }
+        if v.dot(&v) != 0 {
+            return v;
+        }
+    }
+}
+
+/// Shortens `v` with the nearest multiple of `w`, if `||v - x w|| < ||v||` for some
+/// integer `x`
+///
+/// Returns whether `v` changed.
+fn reduce(v: &mut BigVector, w: &BigVector) -> bool {
+    let norm = w.dot(w);
+    let product = v.dot(w);
+    if Integer::from(product.abs_ref()) * 2 <= norm {
+        return false;
+    }
+    let (x, _) = product.div_rem_round(norm);
+    *v -= &w.mulf(&x);
+    true
+}
@@ -0,0 +1,70 @@
+//! Slide reduction [GN08]
+//!
+//! The basis is split into disjoint blocks of `block_size` vectors. Slide reduction
+//! alternates two steps until the basis does not change:
+//!  * the primal blocks `basis[i * k..(i + 1) * k]` are SVP-reduced: their first
+//!    Gram-Schmidt vector is a shortest vector of the projected block,
+//!  * the dual blocks `basis[i * k + 1..(i + 1) * k + 1]`, shifted by one vector, are dual
+//!    SVP-reduced: their last Gram-Schmidt vector is as long as possible.
+//!
+//! The reduced basis achieves the approximation factor `((1 + ε) γ_k)^((n - k) / (k - 1))`
+//! of Mordell's inequality on its first vector, where `γ_k` is Hermite's constant. The
+//! SVP oracles are the exact enumerations of the `bkz` module.
+
+use crate::bkz;
+use crate::l2::bigl2;
+use crate::matrix::Matrix;
+
+use rug::Integer;
+
+/// Lattice reduction (slide reduction)
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * block_size: number of vectors of the blocks
+///  * delta: delta factor of the basis reduction
+///
+/// The basis is reduced in-place. The zero vectors coming from linear dependencies are
+/// moved to the end of the basis, and their number is returned. The last primal block is
+/// shorter if the rank of the lattice is not a multiple of `block_size`, and the dual
+/// blocks that do not fit are skipped.
+///
+/// # Panics
+/// if delta < 1/4 or delta >= 1  
+/// if block_size < 2
+pub fn slide_reduce(basis: &mut Matrix<Integer>, block_size: usize, delta: f64) -> usize {
+    assert!(block_size >= 2);
+    let zeros = bigl2::lattice_reduce(basis, bkz::ETA, delta);
+    let (n, _) = basis.dimensions();
+    let rank = n - zeros;
+
+    loop {
+        // Primal steps, until all the blocks are SVP-reduced
+        loop {
+            let mut changed = false;
+            for start in (0..rank).step_by(block_size) {
+                let end = rank.min(start + block_size);
+                if end - start >= 2 {
+                    changed |= bkz::reduce_block(basis, start, end, delta, None);
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        // Dual steps
+        let mut changed = false;
+        for start in (1..rank).step_by(block_size) {
+            let end = start + block_size;
+            if end > rank {
+                break;
+            }
+            changed |= bkz::dual_reduce_block(basis, start, end, delta);
+        }
+        if !changed {
+            break;
+        }
+    }
+    zeros
+}
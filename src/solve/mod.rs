@@ -0,0 +1,107 @@
+//! Solvers for problems reducible to lattice reduction
+
+use crate::generate;
+use crate::hnf;
+use crate::l2::bigl2;
+use crate::matrix::Matrix;
+use crate::vector::BigVector;
+
+use rug::Integer;
+
+/// Subset-sum (knapsack) solver
+///
+/// Looks for a subset of `weights` whose sum is `target`, by reducing the Lagarias-Odlyzko
+/// lattice (see `generate::knapsack_lattice`) and scanning the reduced basis for a vector
+/// encoding a solution. This succeeds with high probability for low-density instances,
+/// i.e. when the weights are large compared to their number.
+///
+/// Arguments:
+///  * weights: weights of the subset-sum problem
+///  * target: target sum
+///
+/// Returns the selection of weights as a vector of booleans, or `None` if no solution
+/// was found in the reduced basis.
+pub fn subset_sum(weights: &[Integer], target: &Integer) -> Option<Vec<bool>> {
+    let n = weights.len();
+    let mut basis = generate::knapsack_lattice(weights, target);
+    bigl2::lattice_reduce(&mut basis, 0.51, 0.99);
+
+    for i in 0..=n {
+        let vector = &basis[i];
+        if vector[n] != 0 {
+            continue;
+        }
+
+        // The solution may appear as (x, 0) or (-x, 0)
+        for sign in &[1, -1] {
+            if (0..n).all(|j| vector[j] == 0 || vector[j] == *sign) {
+                let selection: Vec<bool> = (0..n).map(|j| vector[j] != 0).collect();
+                let sum: Integer = weights
+                    .iter()
+                    .zip(&selection)
+                    .filter(|(_, &selected)| selected)
+                    .map(|(weight, _)| weight)
+                    .sum();
+                if sum == *target {
+                    return Some(selection);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Integer solution of a linear system `A x = b`
+///
+/// The vectors of `a` are the rows of `A`. The Hermite Normal Form of the transpose
+/// `U A^T = H` is computed (see `hnf::hermite_normal_form_with_transform`), so that the
+/// system becomes `H^T y = b` with `x = U^T y`, which is solved by substitution on the
+/// pivots of `H`.
+///
+/// Arguments:
+///  * a: the matrix of the system, made of `m` vectors of dimension `n`
+///  * b: the right-hand side, of dimension `m`
+///
+/// Returns a solution `x` of dimension `n`, or `None` if the system has no integer
+/// solution.
+///
+/// # Panics
+/// if the dimension of `b` is not the number of vectors of `a`
+pub fn diophantine(a: &Matrix<Integer>, b: &BigVector) -> Option<BigVector> {
+    let (m, n) = a.dimensions();
+    assert_eq!(m, b.dimension());
+
+    let (h, u) = hnf::hermite_normal_form_with_transform(&a.transpose());
+    let (rank, _) = h.dimensions();
+
+    // Forward substitution on the pivots: column p_k of H^T y involves y_0, ..., y_k
+    let mut y: Vec<Integer> = Vec::with_capacity(rank);
+    let mut col = 0;
+    for k in 0..rank {
+        while h[k][col] == 0 {
+            col += 1;
+        }
+        let mut rest = b[col].clone();
+        for (l, y_l) in y.iter().enumerate() {
+            rest -= Integer::from(&h[l][col] * y_l);
+        }
+        if !rest.is_divisible(&h[k][col]) {
+            return None;
+        }
+        y.push(rest.div_exact(&h[k][col]));
+    }
+
+    // The equations without pivot must also hold
+    for i in 0..m {
+        let value: Integer = (0..rank).map(|k| Integer::from(&h[k][i] * &y[k])).sum();
+        if value != b[i] {
+            return None;
+        }
+    }
+
+    Some(
+        y.iter()
+            .enumerate()
+            .fold(BigVector::init(n), |acc, (k, y_k)| acc.add(&u[k].mulf(y_k))),
+    )
+}
@@ -0,0 +1,386 @@
+//! Shortest vector problem (SVP): exact enumeration and heuristics
+
+use crate::bkz;
+use crate::cvp;
+use crate::l2::bigl2;
+use crate::matrix::Matrix;
+use crate::metrics;
+use crate::vector::{BigVector, Dot};
+
+use rug::{rand::RandState, Integer, Rational};
+
+/// Approximate shortest vector by sampling random targets
+///
+/// The basis is first reduced (L² algorithm). Then `samples` random targets are drawn
+/// around the origin, each coefficient uniformly in `[-m, m]` where `m` is the largest
+/// coefficient (in absolute value) of the first reduced vector, and Babai's nearest plane
+/// algorithm gives a lattice vector close to each of them. These vectors and their
+/// pairwise differences are short lattice vectors: the shortest nonzero one is kept.
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * samples: number of random targets
+///  * seed: seed of the random generator
+///
+/// The result is never longer than the first vector of the reduced basis. The zero
+/// vector is returned if the lattice is `{0}`.
+pub fn sample_short(basis: &Matrix<Integer>, samples: usize, seed: u64) -> BigVector {
+    let (d, n) = basis.dimensions();
+    let mut reduced = basis.clone();
+    let zeros = bigl2::lattice_reduce(&mut reduced, 0.51, 0.99);
+    let rank = d - zeros;
+    if rank == 0 {
+        return BigVector::init(n);
+    }
+    let reduced = reduced.submatrix(0..rank, 0..n);
+
+    let mut rand = RandState::new();
+    rand.seed(&Integer::from(seed));
+    let radius = reduced[0].norm_linf();
+    let width = Integer::from(&radius * 2u32) + 1u32;
+
+    let mut best = reduced[0].clone();
+    let mut best_norm = best.dot(&best);
+    let mut found: Vec<BigVector> = Vec::with_capacity(samples);
+    for _ in 0..samples {
+        let target: BigVector = (0..n)
+            .map(|_| Integer::from(width.random_below_ref(&mut rand)) - &radius)
+            .collect();
+        let close = cvp::nearest_plane(&reduced, &target).vector;
+
+        for candidate in found
+            .iter()
+            .map(|other| close.sub(other))
+            .chain(std::iter::once(close.clone()))
+        {
+            let norm = candidate.dot(&candidate);
+            if norm != 0 && norm < best_norm {
+                best = candidate;
+                best_norm = norm;
+            }
+        }
+        found.push(close);
+    }
+    best
+}
+
+/// Shortest vector of the reduced basis
+///
+/// Reduces `basis` (L² algorithm) and returns its shortest nonzero vector (the first one
+/// in case of ties). This is an approximation of the shortest vector of the lattice: its
+/// norm is at most `(1 / (delta - eta²))^((d - 1) / 2)` times `λ_1` for a basis of `d`
+/// vectors, and it is often much better in practice (see `sample_short` for a heuristic
+/// improvement).
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * eta: eta factor of the basis reduction
+///  * delta: delta factor of the basis reduction
+///
+/// The basis is reduced in-place. The zero vector is returned if the lattice is `{0}`.
+///
+/// # Panics
+/// if delta < 1/4 or delta >= 1  
+/// if eta < 1/2 or eta >= sqrt(delta)
+pub fn shortest_reduced(basis: &mut Matrix<Integer>, eta: f64, delta: f64) -> BigVector {
+    let (d, n) = basis.dimensions();
+    let zeros = bigl2::lattice_reduce(basis, eta, delta);
+
+    let mut shortest: Option<(Integer, usize)> = None;
+    for i in 0..d - zeros {
+        let norm = basis[i].dot(&basis[i]);
+        if shortest.as_ref().is_none_or(|(best, _)| norm < *best) {
+            shortest = Some((norm, i));
+        }
+    }
+    match shortest {
+        Some((_, i)) => basis[i].clone(),
+        None => BigVector::init(n),
+    }
+}
+
+/// Shortest nonzero vector of a lattice
+///
+/// The basis is first reduced (L² algorithm), then a Schnorr-Euchner enumeration
+/// [SE94] of the lattice vectors shorter than the first reduced vector finds a shortest
+/// nonzero vector. The result is exact, but the running time is exponential in the rank of
+/// the lattice: this is practical up to a rank of about 40 to 50.
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice (linear dependencies are allowed)
+///
+/// Returns a shortest nonzero vector (there are at least two of them, `v` and `-v`).
+/// The zero vector is returned if the lattice is `{0}`.
+pub fn shortest_vector(basis: &Matrix<Integer>) -> BigVector {
+    let (_, dim) = basis.dimensions();
+    let mut reduced = basis.clone();
+    let zeros = bigl2::lattice_reduce(&mut reduced, 0.51, 0.99);
+    let (n, _) = reduced.dimensions();
+    let rank = n - zeros;
+    if rank == 0 {
+        return BigVector::init(dim);
+    }
+
+    let reduced = reduced.submatrix(0..rank, 0..dim);
+    let (mu, norms) = metrics::gram_schmidt(&reduced);
+    // The first vector is a candidate: the bound is strict, and the norms are integers
+    let bound = Rational::from(reduced[0].dot(&reduced[0]) + 1);
+    let coords = enumerate_shortest(&mu, &norms, bound).expect("the first vector is a candidate");
+    coords
+        .iter()
+        .enumerate()
+        .fold(BigVector::init(dim), |acc, (i, x)| {
+            acc.add(&reduced[i].mulf(x))
+        })
+}
+
+/// Linear pruning coefficients for an enumeration in dimension `dimension`
+///
+/// The coefficient of level `i` is `(dimension - i) / dimension`: the squared norm of the
+/// projection of the searched vector orthogonally to the first `i` vectors must be below
+/// this fraction of the enumeration radius. The first coefficient is `1`.
+pub fn linear_pruning(dimension: usize) -> Vec<f64> {
+    (0..dimension)
+        .map(|i| (dimension - i) as f64 / dimension as f64)
+        .collect()
+}
+
+/// Number of random directions drawn by `success_probability`
+const PROBABILITY_SAMPLES: usize = 4096;
+
+/// Estimated probability that a pruned enumeration finds the shortest vector
+///
+/// Following Gama, Nguyen and Regev [GNR10], the coordinates of the shortest vector in
+/// the Gram-Schmidt basis are modelled as a uniformly random direction: the probability is
+/// the fraction of the unit sphere where the squared norm of the coordinates from level
+/// `i` on is at most `pruning[i]`, for all the levels. It is estimated by sampling random
+/// directions (with a fixed seed, so the estimate is deterministic).
+///
+/// # Panics
+/// if the coefficients are not sound: the first one is `1`, and they are nonincreasing
+/// and positive
+pub fn success_probability(pruning: &[f64]) -> f64 {
+    check_pruning(pruning);
+    let d = pruning.len();
+    let mut rand = RandState::new();
+    let mut uniform = || (f64::from(rand.bits(32)) + 0.5) / 4_294_967_296.;
+
+    let mut successes = 0;
+    for _ in 0..PROBABILITY_SAMPLES {
+        // Gaussian coordinates (Box-Muller) give a uniformly random direction, the squared
+        // norms of its projections are the sums of the last squared coordinates
+        let mut tails = vec![0.; d + 1];
+        for i in (0..d).rev() {
+            let (u, v) = (uniform(), uniform());
+            let x = (-2. * u.ln()).sqrt() * (2. * std::f64::consts::PI * v).cos();
+            tails[i] = tails[i + 1] + x * x;
+        }
+        if (0..d).all(|i| tails[i] <= pruning[i] * tails[0]) {
+            successes += 1;
+        }
+    }
+    successes as f64 / PROBABILITY_SAMPLES as f64
+}
+
+/// Shortest nonzero vector of a lattice by pruned enumeration, with rerandomized retries
+///
+/// Same as `shortest_vector`, but the enumeration is pruned with the coefficients
+/// `pruning` (see `linear_pruning`): each enumeration finds the shortest vector with the
+/// probability estimated by `success_probability`. The enumeration is repeated on
+/// rerandomized bases (random unimodular transformations, reduced again with L²) until
+/// the probability that one of them succeeded reaches `target_probability`. With
+/// extreme pruning, the coefficients are chosen with a low success probability, and the
+/// many fast enumerations are cheaper than a single one with a high probability.
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice (linear dependencies are allowed)
+///  * pruning: the pruning coefficients, one per vector of a basis of the lattice
+///  * target_probability: probability of finding the shortest vector
+///  * seed: seed of the random generator of the rerandomizations
+///
+/// Returns the shortest nonzero vector found: with probability `target_probability` (in
+/// the model of `success_probability`), this is a shortest vector of the lattice. The zero
+/// vector is returned if the lattice is `{0}`.
+///
+/// # Panics
+/// if the coefficients are not sound, or if their number is not the rank of the lattice  
+/// if target_probability is not in `(0, 1)`
+pub fn shortest_vector_pruned(
+    basis: &Matrix<Integer>,
+    pruning: &[f64],
+    target_probability: f64,
+    seed: u64,
+) -> BigVector {
+    check_pruning(pruning);
+    assert!(
+        0. < target_probability && target_probability < 1.,
+        "the target probability must be in (0, 1)"
+    );
+    let (_, dim) = basis.dimensions();
+    let mut reduced = basis.clone();
+    let zeros = bigl2::lattice_reduce(&mut reduced, bkz::ETA, DELTA);
+    let (n, _) = reduced.dimensions();
+    let rank = n - zeros;
+    if rank == 0 {
+        return BigVector::init(dim);
+    }
+    assert_eq!(
+        rank,
+        pruning.len(),
+        "one pruning coefficient per vector is needed"
+    );
+    let mut reduced = reduced.submatrix(0..rank, 0..dim);
+
+    // The probability of each trial is at least the resolution of its estimate
+    let probability = success_probability(pruning).max(1. / PROBABILITY_SAMPLES as f64);
+    let trials = if probability >= target_probability {
+        1
+    } else {
+        ((1. - target_probability).ln() / (1. - probability).ln()).ceil() as usize
+    };
+
+    let mut rand = RandState::new();
+    rand.seed(&Integer::from(seed));
+    let mut best = reduced[0].clone();
+    for trial in 0..trials {
+        if trial > 0 {
+            bkz::rerandomize(&mut reduced, 0, rank, DELTA, &mut rand);
+        }
+        let (mu, norms) = metrics::gram_schmidt(&reduced);
+        let bound = Rational::from(best.dot(&best));
+        if let Some(coords) = enumerate_pruned(&mu, &norms, bound, pruning) {
+            best = coords
+                .iter()
+                .enumerate()
+                .fold(BigVector::init(dim), |acc, (i, x)| {
+                    acc.add(&reduced[i].mulf(x))
+                });
+        }
+    }
+    best
+}
+
+/// delta factor of the reductions of the enumerated bases
+const DELTA: f64 = 0.99;
+
+/// Checks that pruning coefficients are sound: the first one is `1`, and they are
+/// nonincreasing and positive
+///
+/// # Panics
+/// if the coefficients are not sound
+pub(crate) fn check_pruning(pruning: &[f64]) {
+    assert!(
+        pruning.first().is_none_or(|&first| first == 1.),
+        "the first pruning coefficient must be 1"
+    );
+    assert!(
+        pruning.windows(2).all(|pair| pair[1] <= pair[0])
+            && pruning.iter().all(|&coefficient| coefficient > 0.),
+        "the pruning coefficients must be positive and nonincreasing"
+    );
+}
+
+/// State of the enumeration of the short vectors of a lattice
+struct Enumeration<'a> {
+    mu: &'a Matrix<Rational>,
+    norms: &'a [Rational],
+    pruning: Vec<Rational>,
+    coords: Vec<Integer>,
+    best_coords: Option<Vec<Integer>>,
+    best_norm: Rational,
+}
+
+impl<'a> Enumeration<'a> {
+    /// Explore all the coefficients for level `i` that keep the partial squared norm
+    /// strictly below the best norm found so far (scaled by the pruning coefficient of
+    /// the level)
+    ///
+    /// When the coefficients of the levels above are all zero (`zero_above`), only the
+    /// nonnegative coefficients are explored: `v` and `-v` have the same norm.
+    fn search(&mut self, i: usize, partial: Rational, zero_above: bool) {
+        let center = if zero_above {
+            Rational::new()
+        } else {
+            -(i + 1..self.coords.len())
+                .map(|j| Rational::from(&self.coords[j]) * &self.mu[j][i])
+                .sum::<Rational>()
+        };
+        let start: Integer = center.round_ref().into();
+        let steps: &[i32] = if zero_above { &[1] } else { &[1, -1] };
+
+        for step in steps {
+            let mut x = if *step > 0 {
+                start.clone()
+            } else {
+                start.clone() - 1
+            };
+            loop {
+                let offset = Rational::from(&x) - &center;
+                let norm = partial.clone() + offset.square() * &self.norms[i];
+                if norm >= Rational::from(&self.best_norm * &self.pruning[i]) {
+                    break;
+                }
+                self.coords[i] = x.clone();
+                if i > 0 {
+                    self.search(i - 1, norm, zero_above && x == 0);
+                } else if !(zero_above && x == 0) {
+                    self.best_norm = norm;
+                    self.best_coords = Some(self.coords.clone());
+                }
+                x += *step;
+            }
+        }
+    }
+}
+
+/// Coordinates of the shortest nonzero vector of a lattice, from its Gram-Schmidt data
+///
+/// `mu` and `norms` are the Gram-Schmidt coefficients and the squared norms `||b*_i||²`
+/// of a basis of linearly independent vectors. Only the vectors of squared norm strictly
+/// below `bound` are considered: returns the coordinates in the basis of the shortest of
+/// them, or `None` if there is none.
+///
+/// The search is an exact Schnorr-Euchner enumeration, it is exponential in the number
+/// of vectors.
+pub(crate) fn enumerate_shortest(
+    mu: &Matrix<Rational>,
+    norms: &[Rational],
+    bound: Rational,
+) -> Option<Vec<Integer>> {
+    enumerate_pruned(mu, norms, bound, &vec![1.; norms.len()])
+}
+
+/// Pruned enumeration of the shortest nonzero vector of a lattice
+///
+/// Same as `enumerate_shortest`, but the subtrees of level `i` whose partial squared norm
+/// exceeds `pruning[i]` times the current radius are cut (see `linear_pruning`). The
+/// search is much faster, but it can miss the shortest vector.
+///
+/// # Panics
+/// if `pruning` and `norms` have different lengths
+pub(crate) fn enumerate_pruned(
+    mu: &Matrix<Rational>,
+    norms: &[Rational],
+    bound: Rational,
+    pruning: &[f64],
+) -> Option<Vec<Integer>> {
+    let d = norms.len();
+    assert_eq!(d, pruning.len());
+    if d == 0 {
+        return None;
+    }
+
+    let mut enumeration = Enumeration {
+        mu,
+        norms,
+        pruning: pruning
+            .iter()
+            .map(|&coefficient| Rational::from_f64(coefficient).expect("finite pruning"))
+            .collect(),
+        coords: vec![Integer::new(); d],
+        best_coords: None,
+        best_norm: bound,
+    };
+    enumeration.search(d - 1, Rational::new(), true);
+    enumeration.best_coords
+}
@@ -1,159 +1,649 @@
-//! Basic vector structures for LLL
-use std::{
-    fmt,
-    ops::{self, Index, IndexMut},
-};
-
-pub type VectorF = Vector<f64>;
-pub type BigVector = Vector<rug::Integer>;
-
-/// Implementation of a vector without generic coefficients
-#[derive(Clone)]
-pub struct Vector<T> {
-    /// Internal representation as a list of coefficients
-    coefficients: Vec<T>,
-}
-
-pub trait Coefficient:
-    From<u32>
-    + Clone
-    + Default
-    + for<'a> ops::Add<&'a Self, Output = Self>
-    + for<'a> ops::Sub<&'a Self, Output = Self>
-    + for<'a> std::ops::Mul<&'a Self, Output = Self>
-    + std::iter::Sum<Self>
-{
-}
-
-impl<T> Coefficient for T where
-    T: From<u32>
-        + Clone
-        + Default
-        + for<'a> ops::Add<&'a Self, Output = Self>
-        + for<'a> ops::Sub<&'a Self, Output = Self>
-        + for<'a> std::ops::Mul<&'a Self, Output = Self>
-        + std::iter::Sum<Self>
-{
-}
-
-impl<T> Vector<T>
-where
-    T: Coefficient,
-{
-    #![allow(dead_code)]
-    fn basis_vector(dimension: usize, position: usize) -> Self {
-        assert!(position < dimension);
-
-        let coefficients = (0..dimension)
-            .map(|i| {
-                if i == position {
-                    T::from(1)
-                } else {
-                    T::from(0)
-                }
-            })
-            .collect();
-
-        Self { coefficients }
-    }
-
-    pub fn init(dimension: usize) -> Self {
-        Self {
-            coefficients: vec![Default::default(); dimension],
-        }
-    }
-
-    pub fn dimension(&self) -> usize {
-        self.coefficients.len()
-    }
-
-    pub fn add(&self, other: &Self) -> Self {
-        let n = self.dimension();
-
-        assert_eq!(n, other.dimension());
-
-        Self::from_vector(
-            (0..n)
-                .map(|i| self.coefficients[i].clone() + &other.coefficients[i])
-                .collect(),
-        )
-    }
-
-    pub fn sub(&self, other: &Self) -> Self {
-        let n = self.dimension();
-
-        assert_eq!(n, other.dimension());
-
-        Self::from_vector(
-            (0..n)
-                .map(|i| self.coefficients[i].clone() - &other.coefficients[i])
-                .collect(),
-        )
-    }
-
-    /// Create an instance from a `Vec`
-    pub fn from_vector(coefficients: Vec<T>) -> Self {
-        Self { coefficients }
-    }
-
-    /// Multiplication by a scalar
-    pub fn mulf(&self, other: &T) -> Self {
-        let n = self.dimension();
-
-        Self::from_vector(
-            (0..n)
-                .map(|i| self.coefficients[i].clone() * other)
-                .collect(),
-        )
-    }
-}
-
-pub(crate) trait Dot {
-    type Output;
-    fn dot(&self, other: &Self) -> Self::Output;
-}
-
-impl Dot for BigVector {
-    type Output = rug::Integer;
-    fn dot(&self, other: &Self) -> Self::Output {
-        self.coefficients
-            .iter()
-            .zip(&other.coefficients)
-            .map(|(coeff_r, coeff_l)| coeff_r * coeff_l)
-            .sum()
-    }
-}
-
-impl Dot for VectorF {
-    type Output = f64;
-    fn dot(&self, other: &Self) -> Self::Output {
-        self.coefficients
-            .iter()
-            .zip(&other.coefficients)
-            .map(|(coeff_r, coeff_l)| coeff_r * coeff_l)
-            .sum()
-    }
-}
-
-impl<T> Index<usize> for Vector<T> {
-    type Output = T;
-
-    fn index(&self, index: usize) -> &T {
-        &self.coefficients[index]
-    }
-}
-
-impl<T> IndexMut<usize> for Vector<T> {
-    fn index_mut(&mut self, index: usize) -> &mut T {
-        &mut self.coefficients[index]
-    }
-}
-
-impl<T> fmt::Debug for Vector<T>
-where
-    T: fmt::Debug,
-{
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self.coefficients)
-    }
-}
+//! Basic vector structures for LLL
+use std::{
+    fmt,
+    iter::FromIterator,
+    ops::{self, Index, IndexMut},
+};
+
+pub type VectorF = Vector<f64>;
+pub type BigVector = Vector<rug::Integer>;
+pub type RationalVector = Vector<rug::Rational>;
+pub type VectorI64 = Vector<i64>;
+pub type GaussianVector = Vector<crate::gaussian::GaussianInteger>;
+pub type GaussianRationalVector = Vector<crate::gaussian::GaussianRational>;
+pub type VectorC = Vector<crate::complex::Complex>;
+
+/// Implementation of a vector without generic coefficients
+///
+/// Equality and hashing compare the exact coefficients: they are available for the
+/// integer and rational types, not for `f64` (no `Eq` nor `Hash`).
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Vector<T> {
+    /// Internal representation as a list of coefficients
+    coefficients: Vec<T>,
+}
+
+/// Error of an operation between vectors of different dimensions
+///
+/// Holds the dimensions of the left and right operands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DimensionMismatch(pub usize, pub usize);
+
+impl fmt::Display for DimensionMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "dimension mismatch: {} and {}", self.0, self.1)
+    }
+}
+
+impl std::error::Error for DimensionMismatch {}
+
+/// Error of the construction of a vector (or a matrix) without coefficients
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Empty;
+
+impl fmt::Display for Empty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "empty coefficient list")
+    }
+}
+
+impl std::error::Error for Empty {}
+
+pub trait Coefficient:
+    From<u32>
+    + Clone
+    + Default
+    + for<'a> ops::Add<&'a Self, Output = Self>
+    + for<'a> ops::Sub<&'a Self, Output = Self>
+    + for<'a> std::ops::Mul<&'a Self, Output = Self>
+    + std::iter::Sum<Self>
+{
+}
+
+impl<T> Coefficient for T where
+    T: From<u32>
+        + Clone
+        + Default
+        + for<'a> ops::Add<&'a Self, Output = Self>
+        + for<'a> ops::Sub<&'a Self, Output = Self>
+        + for<'a> std::ops::Mul<&'a Self, Output = Self>
+        + std::iter::Sum<Self>
+{
+}
+
+impl<T> Vector<T>
+where
+    T: Coefficient,
+{
+    #![allow(dead_code)]
+    fn basis_vector(dimension: usize, position: usize) -> Self {
+        assert!(position < dimension);
+
+        let coefficients = (0..dimension)
+            .map(|i| {
+                if i == position {
+                    T::from(1)
+                } else {
+                    T::from(0)
+                }
+            })
+            .collect();
+
+        Self { coefficients }
+    }
+
+    pub fn init(dimension: usize) -> Self {
+        Self {
+            coefficients: vec![Default::default(); dimension],
+        }
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.coefficients.len()
+    }
+
+    /// Addition, or an error if the dimensions differ
+    pub fn try_add(&self, other: &Self) -> Result<Self, DimensionMismatch> {
+        self.check_dimension(other)?;
+        Ok(self
+            .coefficients
+            .iter()
+            .zip(&other.coefficients)
+            .map(|(coeff_r, coeff_l)| coeff_r.clone() + coeff_l)
+            .collect())
+    }
+
+    /// Subtraction, or an error if the dimensions differ
+    pub fn try_sub(&self, other: &Self) -> Result<Self, DimensionMismatch> {
+        self.check_dimension(other)?;
+        Ok(self
+            .coefficients
+            .iter()
+            .zip(&other.coefficients)
+            .map(|(coeff_r, coeff_l)| coeff_r.clone() - coeff_l)
+            .collect())
+    }
+
+    /// # Panics
+    /// if the dimensions differ
+    pub fn add(&self, other: &Self) -> Self {
+        self.try_add(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// # Panics
+    /// if the dimensions differ
+    pub fn sub(&self, other: &Self) -> Self {
+        self.try_sub(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Create an instance from a `Vec`
+    pub fn from_vector(coefficients: Vec<T>) -> Self {
+        Self { coefficients }
+    }
+
+    /// Create an instance from a `Vec`, rejecting an empty list of coefficients
+    ///
+    /// Zero-dimensional vectors are accepted by `from_vector`, but the reductions cannot
+    /// handle them.
+    pub fn try_from_vector(coefficients: Vec<T>) -> Result<Self, Empty> {
+        if coefficients.is_empty() {
+            return Err(Empty);
+        }
+        Ok(Self::from_vector(coefficients))
+    }
+
+    /// Multiplication by a scalar
+    pub fn mulf(&self, other: &T) -> Self {
+        let n = self.dimension();
+
+        Self::from_vector(
+            (0..n)
+                .map(|i| self.coefficients[i].clone() * other)
+                .collect(),
+        )
+    }
+
+    /// In-place subtraction of `other` multiplied by the scalar `x`, without allocating a
+    /// new vector
+    ///
+    /// # Panics
+    /// if the dimensions differ
+    pub fn sub_mulf_assign(&mut self, other: &Self, x: &T) {
+        self.check_dimension(other)
+            .unwrap_or_else(|e| panic!("{}", e));
+        for (coeff_r, coeff_l) in self.coefficients.iter_mut().zip(&other.coefficients) {
+            let coeff = std::mem::take(coeff_r);
+            *coeff_r = coeff - &(coeff_l.clone() * x);
+        }
+    }
+}
+
+impl<T> Vector<T>
+where
+    T: for<'a> ops::AddAssign<&'a T> + for<'a> ops::SubAssign<&'a T>,
+{
+    /// In-place addition, without allocating a new vector
+    ///
+    /// # Panics
+    /// if the dimensions differ
+    pub fn add_assign(&mut self, other: &Self) {
+        self.check_dimension(other)
+            .unwrap_or_else(|e| panic!("{}", e));
+        for (coeff_r, coeff_l) in self.coefficients.iter_mut().zip(&other.coefficients) {
+            *coeff_r += coeff_l;
+        }
+    }
+
+    /// In-place subtraction, without allocating a new vector
+    ///
+    /// # Panics
+    /// if the dimensions differ
+    pub fn sub_assign(&mut self, other: &Self) {
+        self.check_dimension(other)
+            .unwrap_or_else(|e| panic!("{}", e));
+        for (coeff_r, coeff_l) in self.coefficients.iter_mut().zip(&other.coefficients) {
+            *coeff_r -= coeff_l;
+        }
+    }
+}
+
+impl<'a, T> ops::AddAssign<&'a Vector<T>> for Vector<T>
+where
+    T: for<'b> ops::AddAssign<&'b T> + for<'b> ops::SubAssign<&'b T>,
+{
+    fn add_assign(&mut self, other: &'a Vector<T>) {
+        Vector::add_assign(self, other)
+    }
+}
+
+impl<'a, T> ops::SubAssign<&'a Vector<T>> for Vector<T>
+where
+    T: for<'b> ops::AddAssign<&'b T> + for<'b> ops::SubAssign<&'b T>,
+{
+    fn sub_assign(&mut self, other: &'a Vector<T>) {
+        Vector::sub_assign(self, other)
+    }
+}
+
+impl BigVector {
+    /// Convert to a `VectorF`
+    ///
+    /// Coefficients that cannot be represented exactly are rounded towards zero.
+    pub fn to_float(&self) -> VectorF {
+        VectorF::from_vector(self.coefficients.iter().map(|c| c.to_f64()).collect())
+    }
+
+    /// Convert to a `RationalVector` (exact)
+    pub fn to_rational(&self) -> RationalVector {
+        RationalVector::from_vector(self.coefficients.iter().map(rug::Rational::from).collect())
+    }
+
+    /// Sum of the absolute values of the coefficients
+    pub fn norm_l1(&self) -> rug::Integer {
+        self.coefficients
+            .iter()
+            .map(|c| rug::Integer::from(c.abs_ref()))
+            .sum()
+    }
+
+    /// Vector of the absolute values of the coefficients
+    pub fn abs(&self) -> Self {
+        self.coefficients
+            .iter()
+            .map(|c| rug::Integer::from(c.abs_ref()))
+            .collect()
+    }
+
+    /// Check whether all the coefficients are zero
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.iter().all(|c| *c == 0)
+    }
+
+    /// Signs of the coefficients: `-1`, `0` or `1`
+    pub fn signum_coefficients(&self) -> Vec<i32> {
+        self.coefficients
+            .iter()
+            .map(|c| match c.cmp0() {
+                std::cmp::Ordering::Less => -1,
+                std::cmp::Ordering::Equal => 0,
+                std::cmp::Ordering::Greater => 1,
+            })
+            .collect()
+    }
+
+    /// Largest absolute value of the coefficients
+    pub fn norm_linf(&self) -> rug::Integer {
+        self.coefficients
+            .iter()
+            .map(|c| rug::Integer::from(c.abs_ref()))
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Orthogonal projection on the line spanned by `other` (exact)
+    ///
+    /// # Panics
+    /// if `other` is the zero vector
+    pub fn project_onto(&self, other: &Self) -> RationalVector {
+        let factor = rug::Rational::from((self.dot(other), other.dot(other)));
+        other.to_rational().mulf(&factor)
+    }
+
+    /// Component orthogonal to `other` (exact): `self - self.project_onto(other)`
+    ///
+    /// # Panics
+    /// if `other` is the zero vector
+    pub fn reject_from(&self, other: &Self) -> RationalVector {
+        self.to_rational().sub(&self.project_onto(other))
+    }
+}
+
+/// Overwrite the coefficients with zeros
+///
+/// `rug::Integer` does not clear its memory when it is dropped or reassigned: setting a
+/// coefficient to zero only resets its size. Each coefficient is first overwritten with a
+/// value filling its whole allocated storage, with zero limbs except the highest one
+/// (`2^(capacity - 1)`), then set to zero. To clear a vector when it is dropped, wrap it
+/// in `zeroize::Zeroizing`.
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for BigVector {
+    fn zeroize(&mut self) {
+        use rug::Assign;
+
+        for c in &mut self.coefficients {
+            let bits = c.capacity();
+            if bits > 0 {
+                // Assigning by reference copies into the existing storage
+                let filler = rug::Integer::from(1) << (bits as u32 - 1);
+                c.assign(&filler);
+            }
+            c.assign(0);
+        }
+    }
+}
+
+impl VectorF {
+    /// Sum of the absolute values of the coefficients
+    pub fn norm_l1(&self) -> f64 {
+        self.coefficients.iter().map(|c| c.abs()).sum()
+    }
+
+    /// Largest absolute value of the coefficients
+    pub fn norm_linf(&self) -> f64 {
+        self.coefficients.iter().fold(0., |acc, c| acc.max(c.abs()))
+    }
+
+    /// Orthogonal projection on the line spanned by `other`
+    pub fn project_onto(&self, other: &Self) -> Self {
+        other.mulf(&(self.dot(other) / other.dot(other)))
+    }
+
+    /// Component orthogonal to `other`: `self - self.project_onto(other)`
+    pub fn reject_from(&self, other: &Self) -> Self {
+        self.sub(&self.project_onto(other))
+    }
+
+    /// Division by a scalar
+    ///
+    /// # Panics
+    /// if `d` is zero
+    pub fn divf(&self, d: f64) -> Self {
+        assert!(d != 0., "division by zero");
+        self.coefficients.iter().map(|c| c / d).collect()
+    }
+
+    /// Angle with `other`, in radians (between `0` and `pi`)
+    pub fn angle(&self, other: &Self) -> f64 {
+        let cosine = self.dot(other) / (self.dot(self) * other.dot(other)).sqrt();
+        cosine.clamp(-1., 1.).acos()
+    }
+
+    /// Check whether `|cos(angle)| <= tol` between `self` and `other`
+    pub fn is_orthogonal(&self, other: &Self, tol: f64) -> bool {
+        let cosine = self.dot(other) / (self.dot(self) * other.dot(other)).sqrt();
+        cosine.abs() <= tol
+    }
+
+    /// Convert to a `BigVector`, rounding each coefficient to the nearest integer
+    ///
+    /// Ties are rounded half to even (`0.5` becomes `0`, `1.5` becomes `2`).
+    ///
+    /// # Panics
+    /// if a coefficient is not finite
+    pub fn round_to_big(&self) -> BigVector {
+        BigVector::from_vector(
+            self.coefficients
+                .iter()
+                .map(|c| {
+                    rug::Integer::from_f64(c.round_ties_even())
+                        .expect("cannot round a non-finite coefficient")
+                })
+                .collect(),
+        )
+    }
+}
+
+impl RationalVector {
+    /// Division by a scalar
+    ///
+    /// # Panics
+    /// if `d` is zero
+    pub fn divf(&self, d: &rug::Rational) -> Self {
+        assert!(*d != 0, "division by zero");
+        self.coefficients
+            .iter()
+            .map(|c| rug::Rational::from(c / d))
+            .collect()
+    }
+}
+
+impl VectorI64 {
+    /// Create an instance from a `Vec`, checking that dot products cannot overflow
+    ///
+    /// Dot products of `VectorI64`s are accumulated in `i128`. This returns `None` unless
+    /// `n * max|c|² <= i128::MAX`, where `n` is the dimension and `max|c|` the largest
+    /// absolute value of the coefficients, so that the dot product of two vectors
+    /// created this way (with the same dimension) always fits.
+    pub fn checked_from_vector(coefficients: Vec<i64>) -> Option<Self> {
+        let largest = coefficients
+            .iter()
+            .map(|c| c.unsigned_abs())
+            .max()
+            .unwrap_or(0);
+        let square = u128::from(largest) * u128::from(largest);
+        let bound = square.checked_mul(coefficients.len() as u128)?;
+        if bound > i128::MAX as u128 {
+            return None;
+        }
+        Some(Self::from_vector(coefficients))
+    }
+
+    /// Convert to a `BigVector` (exact)
+    pub fn to_big(&self) -> BigVector {
+        self.coefficients
+            .iter()
+            .map(|&c| rug::Integer::from(c))
+            .collect()
+    }
+}
+
+/// Multiplication by a scalar, for generic code over the vector types
+pub trait Scale {
+    /// Type of the scalars (the type of the coefficients)
+    type Scalar;
+
+    /// Multiply every coefficient by `s`
+    fn scale(&self, s: &Self::Scalar) -> Self;
+}
+
+impl<T> Scale for Vector<T>
+where
+    T: Coefficient,
+{
+    type Scalar = T;
+    fn scale(&self, s: &Self::Scalar) -> Self {
+        self.mulf(s)
+    }
+}
+
+/// Dot product
+pub trait Dot {
+    type Output;
+    fn dot(&self, other: &Self) -> Self::Output;
+}
+
+/// Dot product through references, for generic code over `&Vector`s
+impl<V> Dot for &V
+where
+    V: Dot + ?Sized,
+{
+    type Output = V::Output;
+    fn dot(&self, other: &Self) -> Self::Output {
+        (**self).dot(*other)
+    }
+}
+
+/// Dot product whose result has the type of the coefficients
+///
+/// Generic code can require `V: InnerProduct` to scale vectors and compute dot products
+/// with the same scalar type. Implemented for every vector type whose `Dot::Output` is
+/// its `Scale::Scalar` (all of them except `VectorI64`, whose dot products are `i128`).
+pub trait InnerProduct: Scale + Dot<Output = <Self as Scale>::Scalar> {}
+
+impl<V> InnerProduct for V where V: Scale + Dot<Output = <V as Scale>::Scalar> {}
+
+impl Dot for BigVector {
+    type Output = rug::Integer;
+    fn dot(&self, other: &Self) -> Self::Output {
+        self.coefficients
+            .iter()
+            .zip(&other.coefficients)
+            .map(|(coeff_r, coeff_l)| coeff_r * coeff_l)
+            .sum()
+    }
+}
+
+impl Dot for RationalVector {
+    type Output = rug::Rational;
+    fn dot(&self, other: &Self) -> Self::Output {
+        self.coefficients
+            .iter()
+            .zip(&other.coefficients)
+            .map(|(coeff_r, coeff_l)| rug::Rational::from(coeff_r * coeff_l))
+            .sum()
+    }
+}
+
+impl Dot for VectorI64 {
+    type Output = i128;
+    fn dot(&self, other: &Self) -> Self::Output {
+        self.coefficients
+            .iter()
+            .zip(&other.coefficients)
+            .map(|(&coeff_r, &coeff_l)| i128::from(coeff_r) * i128::from(coeff_l))
+            .sum()
+    }
+}
+
+/// Dot product that detects overflows
+pub trait CheckedDot {
+    /// Dot product of `self` and `other`, or `None` if it overflows an `i128`
+    fn checked_dot(&self, other: &Self) -> Option<i128>;
+}
+
+impl CheckedDot for VectorI64 {
+    fn checked_dot(&self, other: &Self) -> Option<i128> {
+        self.coefficients
+            .iter()
+            .zip(&other.coefficients)
+            .try_fold(0i128, |acc, (&coeff_r, &coeff_l)| {
+                acc.checked_add(i128::from(coeff_r) * i128::from(coeff_l))
+            })
+    }
+}
+
+/// Complex dot product, conjugating `other`
+impl Dot for GaussianVector {
+    type Output = crate::gaussian::GaussianInteger;
+    fn dot(&self, other: &Self) -> Self::Output {
+        self.coefficients
+            .iter()
+            .zip(&other.coefficients)
+            .map(|(coeff_r, coeff_l)| coeff_r.clone() * &coeff_l.conj())
+            .sum()
+    }
+}
+
+/// Complex dot product, conjugating `other`
+impl Dot for GaussianRationalVector {
+    type Output = crate::gaussian::GaussianRational;
+    fn dot(&self, other: &Self) -> Self::Output {
+        self.coefficients
+            .iter()
+            .zip(&other.coefficients)
+            .map(|(coeff_r, coeff_l)| coeff_r.clone() * &coeff_l.conj())
+            .sum()
+    }
+}
+
+/// Hermitian dot product, conjugating `other`
+impl Dot for VectorC {
+    type Output = crate::complex::Complex;
+    fn dot(&self, other: &Self) -> Self::Output {
+        self.coefficients
+            .iter()
+            .zip(&other.coefficients)
+            .map(|(coeff_r, coeff_l)| *coeff_r * &coeff_l.conj())
+            .sum()
+    }
+}
+
+impl Dot for VectorF {
+    type Output = f64;
+    fn dot(&self, other: &Self) -> Self::Output {
+        self.coefficients
+            .iter()
+            .zip(&other.coefficients)
+            .map(|(coeff_r, coeff_l)| coeff_r * coeff_l)
+            .sum()
+    }
+}
+
+impl<T> Vector<T>
+where
+    Self: Dot,
+{
+    /// Dot product, or an error if the dimensions differ
+    pub fn try_dot(&self, other: &Self) -> Result<<Self as Dot>::Output, DimensionMismatch> {
+        self.check_dimension(other)?;
+        Ok(self.dot(other))
+    }
+}
+
+impl<T> Vector<T> {
+    /// Check that `self` and `other` have the same dimension
+    fn check_dimension(&self, other: &Self) -> Result<(), DimensionMismatch> {
+        if self.coefficients.len() == other.coefficients.len() {
+            Ok(())
+        } else {
+            Err(DimensionMismatch(
+                self.coefficients.len(),
+                other.coefficients.len(),
+            ))
+        }
+    }
+
+    /// Iterate over the coefficients
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.coefficients.iter()
+    }
+}
+
+impl<T> FromIterator<T> for Vector<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            coefficients: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> IntoIterator for Vector<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.coefficients.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Vector<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.coefficients.iter()
+    }
+}
+
+impl<T> Index<usize> for Vector<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.coefficients[index]
+    }
+}
+
+impl<T> IndexMut<usize> for Vector<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.coefficients[index]
+    }
+}
+
+impl<T> fmt::Debug for Vector<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.coefficients)
+    }
+}
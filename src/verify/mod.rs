@@ -0,0 +1,121 @@
+//! Checks on the output of the reductions
+//!
+//! The checks use exact rational arithmetic, whatever the arithmetic of the reduction:
+//! the floating-point bases are converted exactly to rationals.
+
+use crate::matrix::Matrix;
+use crate::vector::{Dot, RationalVector};
+
+use rug::{Integer, Rational};
+
+/// Check that a basis is LLL-reduced
+///
+/// The check uses exact arithmetic: the basis is `(delta, eta)`-LLL-reduced when its
+/// Gram-Schmidt coefficients satisfy `|mu_ij| <= eta` for `j < i`, and the Lovasz
+/// condition `delta * ||b*_{i-1}||² <= ||b*_i||² + mu_{i,i-1}² * ||b*_{i-1}||²` holds.
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * delta: delta factor of the basis reduction
+///  * eta: eta factor of the basis reduction
+///
+/// Unlike the reductions, the factors are given in the order `delta, eta`.
+///
+/// The zero vectors at the end of the basis (see `bigl2::lattice_reduce`) are ignored.
+/// Returns `false` if the other vectors are not linearly independent.
+pub fn is_lll_reduced(basis: &Matrix<Integer>, delta: f64, eta: f64) -> bool {
+    check(&basis.to_rational(), eta, Some(delta))
+}
+
+/// Check that a basis is size-reduced
+///
+/// The check uses exact arithmetic: the basis is `eta`-size-reduced when its Gram-Schmidt
+/// coefficients satisfy `|mu_ij| <= eta` for `j < i`.
+///
+/// Arguments:
+///  * basis: A generating matrix for the lattice
+///  * eta: eta factor of the basis reduction
+///
+/// The zero vectors at the end of the basis are ignored. Returns `false` if the other
+/// vectors are not linearly independent.
+pub fn is_size_reduced(basis: &Matrix<Integer>, eta: f64) -> bool {
+    check(&basis.to_rational(), eta, None)
+}
+
+/// Check that a floating-point basis is LLL-reduced
+///
+/// Same as `is_lll_reduced`, the coefficients of the basis are converted exactly to
+/// rationals (useful to check the output of `l2f`).
+///
+/// # Panics
+/// if a coefficient of the basis is not finite
+pub fn is_lll_reduced_f(basis: &Matrix<f64>, delta: f64, eta: f64) -> bool {
+    check(&to_rational(basis), eta, Some(delta))
+}
+
+/// Check that a floating-point basis is size-reduced
+///
+/// Same as `is_size_reduced`, the coefficients of the basis are converted exactly to
+/// rationals.
+///
+/// # Panics
+/// if a coefficient of the basis is not finite
+pub fn is_size_reduced_f(basis: &Matrix<f64>, eta: f64) -> bool {
+    check(&to_rational(basis), eta, None)
+}
+
+fn to_rational(basis: &Matrix<f64>) -> Matrix<Rational> {
+    let (n, _) = basis.dimensions();
+    Matrix::from_columns(
+        (0..n)
+            .map(|i| {
+                RationalVector::from_vector(
+                    basis[i]
+                        .iter()
+                        .map(|&x| Rational::from_f64(x).expect("finite coefficients"))
+                        .collect(),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Checks the size-reduction condition, and the Lovasz condition when `delta` is given
+fn check(basis: &Matrix<Rational>, eta: f64, delta: Option<f64>) -> bool {
+    let (n, _) = basis.dimensions();
+    let d = (0..n)
+        .rev()
+        .find(|&i| basis[i].dot(&basis[i]) != 0)
+        .map_or(0, |i| i + 1);
+
+    let eta = Rational::from_f64(eta).unwrap();
+    let delta = delta.map(|delta| Rational::from_f64(delta).unwrap());
+
+    let mut r: Matrix<Rational> = Matrix::init(d, d);
+    let mut mu: Matrix<Rational> = Matrix::init(d, d);
+    for i in 0..d {
+        for j in 0..=i {
+            r[i][j] = basis[i].dot(&basis[j])
+                - (0..j)
+                    .map(|index| Rational::from(&mu[j][index] * &r[i][index]))
+                    .sum::<Rational>();
+            if j < i {
+                mu[i][j] = Rational::from(&r[i][j] / &r[j][j]);
+                if Rational::from(mu[i][j].abs_ref()) > eta {
+                    return false;
+                }
+            }
+        }
+        if r[i][i] == 0 {
+            return false;
+        }
+        if let (Some(delta), true) = (&delta, i > 0) {
+            let lhs = Rational::from(delta * &r[i - 1][i - 1]);
+            let rhs = Rational::from(mu[i][i - 1].square_ref()) * &r[i - 1][i - 1] + &r[i][i];
+            if lhs > rhs {
+                return false;
+            }
+        }
+    }
+    true
+}